@@ -0,0 +1,159 @@
+//! `#[derive(SolveModel)]` for the GLPK API client SDK
+//!
+//! Use it through the `derive` feature of `glpk-api-sdk`, which re-exports
+//! the macro next to the `SolveModel` trait it implements. The attributes
+//! are described on the trait.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{parse_macro_input, Data, DeriveInput, Expr, Fields, Ident, LitStr, Path};
+
+#[proc_macro_derive(SolveModel, attributes(solve_model, var))]
+pub fn derive_solve_model(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// A field turned into a variable
+struct ModelVar {
+    field: Ident,
+    id: LitStr,
+    lower: Expr,
+    upper: Expr,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "SolveModel can't be derived for generic types",
+        ));
+    }
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "SolveModel needs a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "SolveModel can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut constraints: Vec<Path> = Vec::new();
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("solve_model")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("constraints") {
+                constraints.push(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `constraints = path::to::function`"))
+            }
+        })?;
+    }
+
+    let vars = fields
+        .iter()
+        .map(|field| {
+            let ident = field.ident.clone().expect("named field");
+            parse_var(&ident, &field.attrs)
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let name = &input.ident;
+    let vis = &input.vis;
+    let vars_name = format_ident!("{}Vars", name);
+    let vars_doc = format!("Handles to the variables of [`{}`], for writing constraints", name);
+    let field = vars.iter().map(|var| &var.field).collect::<Vec<_>>();
+    let id = vars.iter().map(|var| &var.id).collect::<Vec<_>>();
+    let lower = vars.iter().map(|var| &var.lower);
+    let upper = vars.iter().map(|var| &var.upper);
+
+    Ok(quote! {
+        #[doc = #vars_doc]
+        #[derive(Debug, Clone, Copy)]
+        #vis struct #vars_name {
+            #( pub #field: ::glpk_api_sdk::Var, )*
+        }
+
+        impl ::glpk_api_sdk::SolveModel for #name {
+            type Vars = #vars_name;
+
+            fn variables() -> ::std::vec::Vec<::glpk_api_sdk::Variable> {
+                ::std::vec![
+                    #( ::glpk_api_sdk::Variable::new(#id, #lower, #upper), )*
+                ]
+            }
+
+            fn vars() -> Self::Vars {
+                let builder = ::glpk_api_sdk::SolveRequestBuilder::new()
+                    .add_variables(<Self as ::glpk_api_sdk::SolveModel>::variables());
+                #vars_name {
+                    #( #field: builder.var(#id).expect("variable was just added"), )*
+                }
+            }
+
+            fn builder() -> ::glpk_api_sdk::SolveRequestBuilder {
+                let vars = <Self as ::glpk_api_sdk::SolveModel>::vars();
+                let builder = ::glpk_api_sdk::SolveRequestBuilder::new()
+                    .add_variables(<Self as ::glpk_api_sdk::SolveModel>::variables());
+                #( let builder = #constraints(&vars, builder); )*
+                builder
+            }
+
+            fn from_solution(solution: &::glpk_api_sdk::Solution) -> ::std::option::Option<Self> {
+                ::std::option::Option::Some(Self {
+                    #( #field: ::std::convert::TryFrom::try_from(solution.value(#id)?).ok()?, )*
+                })
+            }
+        }
+    })
+}
+
+/// Read the `#[var(...)]` attribute of a field
+fn parse_var(field: &Ident, attrs: &[syn::Attribute]) -> syn::Result<ModelVar> {
+    let mut id = None;
+    let mut lower = None;
+    let mut upper = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("var")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("lower") {
+                lower = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("upper") {
+                upper = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("binary") {
+                lower = Some(syn::parse_quote!(0));
+                upper = Some(syn::parse_quote!(1));
+            } else if meta.path.is_ident("id") {
+                id = Some(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error("expected `lower`, `upper`, `binary` or `id`"));
+            }
+            Ok(())
+        })?;
+    }
+
+    match (lower, upper) {
+        (Some(lower), Some(upper)) => Ok(ModelVar {
+            field: field.clone(),
+            id: id.unwrap_or_else(|| LitStr::new(&field.unraw().to_string(), Span::call_site())),
+            lower,
+            upper,
+        }),
+        _ => Err(syn::Error::new_spanned(
+            field,
+            "needs bounds: `#[var(lower = ..., upper = ...)]` or `#[var(binary)]`",
+        )),
+    }
+}
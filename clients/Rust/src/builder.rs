@@ -1,8 +1,11 @@
 use crate::error::{GlpkError, Result};
+use crate::expr::{LinExpr, Sense, Var, VarRef};
 use crate::types::{
-    IntegerSparseMatrix, Objective, Shape, SolveRequest, SolverDirection,
-    SparseLEIntegerPolyhedron, Variable,
+    IntegerSparseMatrix, Objective, Shape, SolveRequest, SolverBackend, SolverDirection,
+    SolverOption, SparseLEIntegerPolyhedron, Variable,
 };
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
 
 /// Builder for constructing solve requests with a fluent API
 #[derive(Debug, Default)]
@@ -14,6 +17,12 @@ pub struct SolveRequestBuilder {
     b: Vec<i32>,
     objectives: Vec<Objective>,
     direction: Option<SolverDirection>,
+    solver: Option<SolverBackend>,
+    time_limit: Option<Duration>,
+    mip_gap: Option<f64>,
+    solver_options: HashMap<String, SolverOption>,
+    /// First problem found while adding constraints, reported by `build`
+    invalid: Option<String>,
 }
 
 impl SolveRequestBuilder {
@@ -22,6 +31,61 @@ impl SolveRequestBuilder {
         Self::default()
     }
 
+    /// Start from a model in CPLEX LP format
+    ///
+    /// Every column must be declared integer in a `General` or `Binary`
+    /// section, and `>=` and `=` constraints become `<=` rows. Coefficients
+    /// and right-hand sides must be integers. Needs the `formats` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use glpk_api_sdk::SolveRequestBuilder;
+    ///
+    /// let request = SolveRequestBuilder::from_lp_str(
+    ///     "Maximize\n obj: x + 2 y\nSubject To\n c1: x + y <= 4\nBounds\n x <= 3\nGeneral\n x y\nEnd\n",
+    /// )
+    /// .unwrap()
+    /// .build()
+    /// .unwrap();
+    /// assert_eq!(request.polyhedron.b, vec![4]);
+    /// ```
+    #[cfg(feature = "formats")]
+    pub fn from_lp_str(input: &str) -> Result<Self> {
+        crate::formats::read_lp(input).map(Self::from_request)
+    }
+
+    /// Start from a model in fixed or free MPS format
+    ///
+    /// Every column must be integer, between `INTORG`/`INTEND` markers or
+    /// with a `BV`, `LI` or `UI` bound, and `G` and `E` rows become `<=`
+    /// rows. Coefficients and right-hand sides must be integers; `RANGES`
+    /// are not supported. Needs the `formats` feature.
+    #[cfg(feature = "formats")]
+    pub fn from_mps_str(input: &str) -> Result<Self> {
+        crate::formats::read_mps(input).map(Self::from_request)
+    }
+
+    /// A builder holding everything in `request`, to extend further
+    #[cfg(feature = "formats")]
+    fn from_request(request: SolveRequest) -> Self {
+        let polyhedron = request.polyhedron;
+        Self {
+            variables: polyhedron.variables,
+            constraint_rows: polyhedron.a.rows,
+            constraint_cols: polyhedron.a.cols,
+            constraint_vals: polyhedron.a.vals,
+            b: polyhedron.b,
+            objectives: request.objectives,
+            direction: Some(request.direction),
+            solver: request.solver,
+            time_limit: request.time_limit_seconds.map(Duration::from_secs_f64),
+            mip_gap: request.mip_rel_gap,
+            solver_options: request.solver_options,
+            invalid: None,
+        }
+    }
+
     /// Add a decision variable
     ///
     /// # Example
@@ -37,6 +101,34 @@ impl SolveRequestBuilder {
         self
     }
 
+    /// Add a decision variable and return a reference to it, for use in
+    /// constraints and objectives instead of its column index
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use glpk_api_sdk::{Sense::Le, SolveRequestBuilder, SolverDirection, Variable};
+    ///
+    /// let mut builder = SolveRequestBuilder::new();
+    /// let x = builder.add_var(Variable::new("x", 0, 10));
+    /// let y = builder.add_var(Variable::new("y", 0, 10));
+    ///
+    /// let request = builder
+    ///     .constrain(&x + 2 * &y, Le, 8)
+    ///     .add_objective_terms([(&x, 1.0), (&y, 1.0)])
+    ///     .direction(SolverDirection::Maximize)
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn add_var(&mut self, variable: Variable) -> VarRef {
+        let var = Var {
+            col: self.variables.len(),
+        };
+        let id = variable.id.clone();
+        self.variables.push(variable);
+        VarRef { var, id }
+    }
+
     /// Add multiple decision variables
     ///
     /// # Example
@@ -55,6 +147,240 @@ impl SolveRequestBuilder {
         self
     }
 
+    /// Add a binary variable, bounded to `[0, 1]`, for each id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use glpk_api_sdk::SolveRequestBuilder;
+    ///
+    /// let builder = SolveRequestBuilder::new().add_binary_variables(["x1", "x2", "x3"]);
+    /// ```
+    pub fn add_binary_variables<I>(mut self, ids: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.variables
+            .extend(ids.into_iter().map(|id| Variable::new(id, 0, 1)));
+        self
+    }
+
+    /// Add `count` variables with the same bounds, named `{prefix}1` up to
+    /// `{prefix}{count}`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use glpk_api_sdk::SolveRequestBuilder;
+    ///
+    /// // y1, y2, ..., y100, each in [0, 10]
+    /// let builder = SolveRequestBuilder::new().add_variables_with_bounds("y", 100, 0, 10);
+    /// assert!(builder.var("y100").is_some());
+    /// ```
+    pub fn add_variables_with_bounds(
+        mut self,
+        prefix: &str,
+        count: usize,
+        lower: i32,
+        upper: i32,
+    ) -> Self {
+        self.variables.extend(
+            (1..=count).map(|i| Variable::new(format!("{}{}", prefix, i), lower, upper)),
+        );
+        self
+    }
+
+    /// Handle to the variable with the given id, for writing constraints
+    /// with [`constrain`](Self::constrain)
+    ///
+    /// Returns `None` if no such variable has been added.
+    pub fn var(&self, id: &str) -> Option<Var> {
+        self.variables
+            .iter()
+            .position(|variable| variable.id == id)
+            .map(|col| Var { col })
+    }
+
+    /// Add a constraint written as an expression over variable handles
+    ///
+    /// `Ge` and `Eq` constraints are sent as one and two `≤` rows, and a
+    /// constant in the expression is moved to the right-hand side.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use glpk_api_sdk::{Sense::Le, SolveRequestBuilder, Variable};
+    ///
+    /// let builder = SolveRequestBuilder::new()
+    ///     .add_variable(Variable::new("x", 0, 100))
+    ///     .add_variable(Variable::new("y", 0, 100));
+    /// let (x, y) = (builder.var("x").unwrap(), builder.var("y").unwrap());
+    ///
+    /// // 2x + 3y ≤ 100
+    /// let builder = builder.constrain(2 * x + 3 * y, Le, 100);
+    /// ```
+    pub fn constrain(self, expr: impl Into<LinExpr>, sense: Sense, rhs: i32) -> Self {
+        let expr = expr.into();
+        let terms = expr.terms();
+        let rhs = rhs - expr.constant();
+        let negated: Vec<(usize, i32)> = terms.iter().map(|&(col, coeff)| (col, -coeff)).collect();
+        match sense {
+            Sense::Le => self.push_row(&terms, rhs),
+            Sense::Ge => self.push_row(&negated, -rhs),
+            Sense::Eq => self.push_row(&terms, rhs).push_row(&negated, -rhs),
+        }
+    }
+
+    /// Append `sum(coeff * x[col]) ≤ rhs` as the next row
+    fn push_row(mut self, terms: &[(usize, i32)], rhs: i32) -> Self {
+        let row = self.b.len() as i32;
+        for &(col, coeff) in terms {
+            self.constraint_rows.push(row);
+            self.constraint_cols.push(col as i32);
+            self.constraint_vals.push(coeff);
+        }
+        self.b.push(rhs);
+        self
+    }
+
+    /// Add a constraint from one coefficient per variable added so far,
+    /// in the order they were added
+    ///
+    /// Zero coefficients are left out of the sparse matrix. `build` fails if
+    /// the number of coefficients doesn't match the number of variables.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use glpk_api_sdk::{SolveRequestBuilder, Variable};
+    ///
+    /// // x1 + 2*x3 ≤ 5
+    /// let builder = SolveRequestBuilder::new()
+    ///     .add_variables(vec![
+    ///         Variable::new("x1", 0, 1),
+    ///         Variable::new("x2", 0, 1),
+    ///         Variable::new("x3", 0, 1),
+    ///         Variable::new("x4", 0, 1),
+    ///     ])
+    ///     .add_dense_constraint(vec![1, 0, 2, 0], 5);
+    /// ```
+    pub fn add_dense_constraint(mut self, coefficients: Vec<i32>, b_value: i32) -> Self {
+        if coefficients.len() != self.variables.len() && self.invalid.is_none() {
+            self.invalid = Some(format!(
+                "Dense constraint {} has {} coefficients for {} variables",
+                self.b.len(),
+                coefficients.len(),
+                self.variables.len()
+            ));
+        }
+        let terms: Vec<(usize, i32)> = coefficients
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, coeff)| coeff != 0)
+            .collect();
+        self.push_row(&terms, b_value)
+    }
+
+    /// Add the constraints `A x ≤ b` from a dense matrix with one column
+    /// per variable added so far
+    ///
+    /// Zero entries are left out of the sparse matrix. `build` fails if the
+    /// shape of `a` doesn't match the variables and `b`. Needs the `ndarray`
+    /// feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use glpk_api_sdk::{SolveRequestBuilder, Variable};
+    /// use ndarray::array;
+    ///
+    /// // x1 + x2 ≤ 1, x2 + x3 ≤ 1
+    /// let builder = SolveRequestBuilder::new()
+    ///     .add_variables(vec![
+    ///         Variable::new("x1", 0, 1),
+    ///         Variable::new("x2", 0, 1),
+    ///         Variable::new("x3", 0, 1),
+    ///     ])
+    ///     .add_matrix_constraints(array![[1, 1, 0], [0, 1, 1]].view(), &[1, 1]);
+    /// ```
+    #[cfg(feature = "ndarray")]
+    pub fn add_matrix_constraints(mut self, a: ndarray::ArrayView2<'_, i32>, b: &[i32]) -> Self {
+        self.check_matrix_shape(a.dim(), b.len());
+        for (row, &b_value) in a.rows().into_iter().zip(b) {
+            let terms: Vec<(usize, i32)> = row
+                .iter()
+                .copied()
+                .enumerate()
+                .filter(|&(_, coeff)| coeff != 0)
+                .collect();
+            self = self.push_row(&terms, b_value);
+        }
+        self
+    }
+
+    /// Add the constraints `A x ≤ b` from a CSR or CSC matrix with one
+    /// column per variable added so far
+    ///
+    /// `build` fails if the shape of `a` doesn't match the variables and `b`.
+    /// Needs the `sprs` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use glpk_api_sdk::{SolveRequestBuilder, Variable};
+    /// use sprs::TriMat;
+    ///
+    /// // x1 + x2 ≤ 1, x2 + x3 ≤ 1
+    /// let mut a = TriMat::new((2, 3));
+    /// a.add_triplet(0, 0, 1);
+    /// a.add_triplet(0, 1, 1);
+    /// a.add_triplet(1, 1, 1);
+    /// a.add_triplet(1, 2, 1);
+    /// let a = a.to_csr::<usize>();
+    ///
+    /// let builder = SolveRequestBuilder::new()
+    ///     .add_variables(vec![
+    ///         Variable::new("x1", 0, 1),
+    ///         Variable::new("x2", 0, 1),
+    ///         Variable::new("x3", 0, 1),
+    ///     ])
+    ///     .add_csmat_constraints(a.view(), &[1, 1]);
+    /// ```
+    #[cfg(feature = "sprs")]
+    pub fn add_csmat_constraints(mut self, a: sprs::CsMatView<'_, i32>, b: &[i32]) -> Self {
+        self.check_matrix_shape(a.shape(), b.len());
+        let mut rows = vec![Vec::new(); a.rows()];
+        for (&coeff, (row, col)) in a.iter() {
+            if coeff != 0 {
+                rows[row].push((col, coeff));
+            }
+        }
+        for (terms, &b_value) in rows.iter().zip(b) {
+            self = self.push_row(terms, b_value);
+        }
+        self
+    }
+
+    #[cfg(any(feature = "ndarray", feature = "sprs"))]
+    fn check_matrix_shape(&mut self, (nrows, ncols): (usize, usize), b_len: usize) {
+        if self.invalid.is_some() {
+            return;
+        }
+        if ncols != self.variables.len() {
+            self.invalid = Some(format!(
+                "Constraint matrix has {} columns for {} variables",
+                ncols,
+                self.variables.len()
+            ));
+        } else if nrows != b_len {
+            self.invalid = Some(format!(
+                "Constraint matrix has {} rows but b has {} values",
+                nrows, b_len
+            ));
+        }
+    }
+
     /// Add a constraint row to the constraint matrix A
     ///
     /// The constraint is of the form: sum(A[row, col] * x[col]) ≤ b
@@ -89,6 +415,58 @@ impl SolveRequestBuilder {
         self
     }
 
+    /// Add a constraint `sum(vals[i] * x[cols[i]]) ≥ b`
+    ///
+    /// The API only takes `≤` rows, so this adds the negated row
+    /// `-sum(...) ≤ -b`. The row index is the next free one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use glpk_api_sdk::SolveRequestBuilder;
+    ///
+    /// // x0 + x1 ≥ 1
+    /// let builder = SolveRequestBuilder::new()
+    ///     .add_geq_constraint(vec![0, 1], vec![1, 1], 1);
+    /// ```
+    pub fn add_geq_constraint(self, cols: Vec<i32>, vals: Vec<i32>, b_value: i32) -> Self {
+        self.add_sparse(cols, vals, Sense::Ge, b_value)
+    }
+
+    /// Add a constraint `sum(vals[i] * x[cols[i]]) = b`
+    ///
+    /// The API only takes `≤` rows, so this adds two rows: `sum(...) ≤ b`
+    /// and `-sum(...) ≤ -b`. The row indices are the next free ones.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use glpk_api_sdk::SolveRequestBuilder;
+    ///
+    /// // x0 + 2*x1 = 4
+    /// let builder = SolveRequestBuilder::new()
+    ///     .add_eq_constraint(vec![0, 1], vec![1, 2], 4);
+    /// ```
+    pub fn add_eq_constraint(self, cols: Vec<i32>, vals: Vec<i32>, b_value: i32) -> Self {
+        self.add_sparse(cols, vals, Sense::Eq, b_value)
+    }
+
+    fn add_sparse(mut self, cols: Vec<i32>, vals: Vec<i32>, sense: Sense, b_value: i32) -> Self {
+        if cols.len() != vals.len() && self.invalid.is_none() {
+            self.invalid = Some(format!(
+                "Constraint {} has {} columns but {} values",
+                self.b.len(),
+                cols.len(),
+                vals.len()
+            ));
+        }
+        let mut expr = LinExpr::new();
+        for (col, val) in cols.into_iter().zip(vals) {
+            expr = expr + val * Var { col: col as usize };
+        }
+        self.constrain(expr, sense, b_value)
+    }
+
     /// Set the constraint matrix A in one go
     ///
     /// This sets all the sparse matrix data at once, replacing any previously added constraints.
@@ -164,6 +542,22 @@ impl SolveRequestBuilder {
         self
     }
 
+    /// Add an objective function from variable references and their
+    /// coefficients
+    ///
+    /// Coefficients of a variable given more than once are summed.
+    pub fn add_objective_terms<'a>(
+        mut self,
+        terms: impl IntoIterator<Item = (&'a VarRef, f64)>,
+    ) -> Self {
+        let mut objective = Objective::new();
+        for (var, coeff) in terms {
+            *objective.entry(var.id.clone()).or_insert(0.0) += coeff;
+        }
+        self.objectives.push(objective);
+        self
+    }
+
     /// Add multiple objective functions
     pub fn add_objectives(mut self, objectives: Vec<Objective>) -> Self {
         self.objectives.extend(objectives);
@@ -185,6 +579,46 @@ impl SolveRequestBuilder {
         self
     }
 
+    /// Solve with `solver` instead of the server's default backend
+    pub fn solver(mut self, solver: SolverBackend) -> Self {
+        self.solver = Some(solver);
+        self
+    }
+
+    /// Stop after `limit` and return the best solution found so far
+    ///
+    /// Solutions cut short are reported with [`Status::TimeLimit`](crate::Status::TimeLimit).
+    pub fn time_limit(mut self, limit: Duration) -> Self {
+        self.time_limit = Some(limit);
+        self
+    }
+
+    /// Stop once the relative gap between the best solution and the best
+    /// bound is at most `gap`, e.g. `0.01` for 1%
+    pub fn mip_gap(mut self, gap: f64) -> Self {
+        self.mip_gap = Some(gap);
+        self
+    }
+
+    /// Set a backend parameter by name, over the server's defaults
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use glpk_api_sdk::{SolveRequestBuilder, SolverBackend};
+    /// use std::time::Duration;
+    ///
+    /// let builder = SolveRequestBuilder::new()
+    ///     .solver(SolverBackend::Gurobi)
+    ///     .time_limit(Duration::from_secs(30))
+    ///     .mip_gap(0.01)
+    ///     .solver_option("MIPFocus", 1);
+    /// ```
+    pub fn solver_option(mut self, name: impl Into<String>, value: impl Into<SolverOption>) -> Self {
+        self.solver_options.insert(name.into(), value.into());
+        self
+    }
+
     /// Build the solve request
     ///
     /// # Errors
@@ -194,7 +628,15 @@ impl SolveRequestBuilder {
     /// - No objectives have been added
     /// - No direction has been set
     /// - The constraint matrix dimensions don't match
+    /// - A dense constraint has the wrong number of coefficients
+    /// - Two variables have the same id
+    /// - A row or column index is negative, or past the last constraint or
+    ///   variable
     pub fn build(self) -> Result<SolveRequest> {
+        if let Some(invalid) = self.invalid {
+            return Err(GlpkError::InvalidRequest(invalid));
+        }
+
         if self.variables.is_empty() {
             return Err(GlpkError::InvalidRequest(
                 "At least one variable is required".to_string(),
@@ -211,6 +653,21 @@ impl SolveRequestBuilder {
             GlpkError::InvalidRequest("Direction (maximize/minimize) must be set".to_string())
         })?;
 
+        if self.time_limit == Some(Duration::ZERO) {
+            return Err(GlpkError::InvalidRequest(
+                "Time limit must be positive".to_string(),
+            ));
+        }
+
+        if let Some(gap) = self.mip_gap {
+            if !(gap >= 0.0 && gap.is_finite()) {
+                return Err(GlpkError::InvalidRequest(format!(
+                    "MIP gap must be a non-negative number, got {}",
+                    gap
+                )));
+            }
+        }
+
         let nrows = self.b.len();
         let ncols = self.variables.len();
 
@@ -223,6 +680,34 @@ impl SolveRequestBuilder {
             ));
         }
 
+        let mut ids = HashSet::with_capacity(ncols);
+        if let Some(duplicate) = self.variables.iter().find(|v| !ids.insert(v.id.as_str())) {
+            return Err(GlpkError::InvalidRequest(format!(
+                "Variable id {} is used more than once",
+                duplicate.id
+            )));
+        }
+
+        for (i, (&row, &col)) in self
+            .constraint_rows
+            .iter()
+            .zip(&self.constraint_cols)
+            .enumerate()
+        {
+            if row < 0 || row as usize >= nrows {
+                return Err(GlpkError::InvalidRequest(format!(
+                    "Row index {} at position {} is out of bounds [0, {})",
+                    row, i, nrows
+                )));
+            }
+            if col < 0 || col as usize >= ncols {
+                return Err(GlpkError::InvalidRequest(format!(
+                    "Column index {} at position {} is out of bounds [0, {})",
+                    col, i, ncols
+                )));
+            }
+        }
+
         let matrix = IntegerSparseMatrix {
             rows: self.constraint_rows,
             cols: self.constraint_cols,
@@ -240,6 +725,10 @@ impl SolveRequestBuilder {
             polyhedron,
             objectives: self.objectives,
             direction,
+            solver: self.solver,
+            time_limit_seconds: self.time_limit.map(|limit| limit.as_secs_f64()),
+            mip_rel_gap: self.mip_gap,
+            solver_options: self.solver_options,
         })
     }
 }
@@ -261,6 +750,39 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_builder_rejects_duplicate_ids() {
+        let result = SolveRequestBuilder::new()
+            .add_variable(Variable::new("x", 0, 1))
+            .add_variable(Variable::new("y", 0, 1))
+            .add_variable(Variable::new("x", 0, 1))
+            .add_objective([("x".to_string(), 1.0)].into())
+            .direction(SolverDirection::Maximize)
+            .build();
+
+        assert!(matches!(result, Err(GlpkError::InvalidRequest(e)) if e.contains("Variable id x")));
+    }
+
+    #[test]
+    fn test_builder_rejects_out_of_bounds_indices() {
+        let builder = || {
+            SolveRequestBuilder::new()
+                .add_variable(Variable::new("x", 0, 1))
+                .add_variable(Variable::new("y", 0, 1))
+                .add_objective([("x".to_string(), 1.0)].into())
+                .direction(SolverDirection::Maximize)
+        };
+
+        let result = builder().add_constraint(vec![0, 0], vec![1, 2], vec![1, 1], 1).build();
+        assert!(matches!(result, Err(GlpkError::InvalidRequest(e)) if e == "Column index 2 at position 1 is out of bounds [0, 2)"));
+
+        let result = builder().add_constraint(vec![0, 1], vec![0, 1], vec![1, 1], 1).build();
+        assert!(matches!(result, Err(GlpkError::InvalidRequest(e)) if e == "Row index 1 at position 1 is out of bounds [0, 1)"));
+
+        let result = builder().add_constraint(vec![-1], vec![0], vec![1], 1).build();
+        assert!(matches!(result, Err(GlpkError::InvalidRequest(e)) if e.starts_with("Row index -1")));
+    }
+
     #[test]
     fn test_builder_no_variables() {
         let result = SolveRequestBuilder::new()
@@ -290,4 +812,264 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_constrain_compiles_to_triplets() {
+        let builder = SolveRequestBuilder::new()
+            .add_variable(Variable::new("x", 0, 10))
+            .add_variable(Variable::new("y", 0, 10))
+            .add_constraint(vec![0], vec![0], vec![1], 5);
+        let (x, y) = (builder.var("x").unwrap(), builder.var("y").unwrap());
+        assert!(builder.var("z").is_none());
+
+        let request = builder
+            .constrain(2 * x + 3 * y + 1, Sense::Le, 100)
+            .constrain(y - x, Sense::Eq, 2)
+            .add_objective([("x".to_string(), 1.0)].into())
+            .direction(SolverDirection::Maximize)
+            .build()
+            .unwrap();
+
+        let a = &request.polyhedron.a;
+        assert_eq!(a.rows, vec![0, 1, 1, 2, 2, 3, 3]);
+        assert_eq!(a.cols, vec![0, 0, 1, 1, 0, 1, 0]);
+        assert_eq!(a.vals, vec![1, 2, 3, 1, -1, -1, 1]);
+        assert_eq!(request.polyhedron.b, vec![5, 99, 2, -2]);
+        assert_eq!(a.shape.nrows, 4);
+    }
+
+    #[test]
+    fn test_var_refs_name_columns_and_ids() {
+        let mut builder = SolveRequestBuilder::new().add_variable(Variable::new("a", 0, 1));
+        let x = builder.add_var(Variable::new("x", 0, 10));
+        let y = builder.add_var(Variable::new("y", 0, 10));
+        assert_eq!((x.col(), x.id()), (1, "x"));
+        assert_eq!(builder.var("y"), Some(y.var()));
+
+        let request = builder
+            .constrain(&x - 3 * &y, Sense::Le, 2)
+            .add_objective_terms([(&y, 2.0), (&x, 1.0), (&y, 0.5)])
+            .direction(SolverDirection::Minimize)
+            .build()
+            .unwrap();
+
+        let a = &request.polyhedron.a;
+        assert_eq!(a.cols, vec![1, 2]);
+        assert_eq!(a.vals, vec![1, -3]);
+        assert_eq!(request.objectives[0]["x"], 1.0);
+        assert_eq!(request.objectives[0]["y"], 2.5);
+    }
+
+    #[test]
+    fn test_geq_and_eq_constraints_emit_le_rows() {
+        let request = SolveRequestBuilder::new()
+            .add_variable(Variable::new("x0", 0, 5))
+            .add_variable(Variable::new("x1", 0, 5))
+            .add_geq_constraint(vec![0, 1], vec![1, 1], 1)
+            .add_eq_constraint(vec![0, 1], vec![1, 2], 4)
+            .add_objective([("x0".to_string(), 1.0)].into())
+            .direction(SolverDirection::Maximize)
+            .build()
+            .unwrap();
+
+        let a = &request.polyhedron.a;
+        assert_eq!(a.rows, vec![0, 0, 1, 1, 2, 2]);
+        assert_eq!(a.cols, vec![0, 1, 0, 1, 0, 1]);
+        assert_eq!(a.vals, vec![-1, -1, 1, 2, -1, -2]);
+        assert_eq!(request.polyhedron.b, vec![-1, 4, -4]);
+    }
+
+    #[cfg(feature = "formats")]
+    #[test]
+    fn test_from_mps_str_can_be_extended() {
+        let mps = "ROWS\n N obj\n G c1\nCOLUMNS\n x obj 1 c1 1\nRHS\n RHS c1 2\nBOUNDS\n UI BND x 5\nENDATA\n";
+        let mut builder = SolveRequestBuilder::from_mps_str(mps).unwrap();
+        let y = builder.add_var(Variable::new("y", 0, 1));
+        let x = builder.var("x").unwrap();
+
+        let request = builder.constrain(x + &y, Sense::Le, 4).build().unwrap();
+
+        assert_eq!(request.direction, SolverDirection::Minimize);
+        assert_eq!(request.polyhedron.b, vec![-2, 4]);
+        assert_eq!(request.polyhedron.a.vals, vec![-1, 1, 1]);
+        assert_eq!(request.polyhedron.variables[0].bound, (0, 5));
+    }
+
+    #[test]
+    fn test_dense_constraint_skips_zeros() {
+        let request = SolveRequestBuilder::new()
+            .add_variables(vec![
+                Variable::new("x1", 0, 1),
+                Variable::new("x2", 0, 1),
+                Variable::new("x3", 0, 1),
+            ])
+            .add_dense_constraint(vec![1, 0, 2], 5)
+            .add_dense_constraint(vec![0, -1, 0], 0)
+            .add_objective([("x1".to_string(), 1.0)].into())
+            .direction(SolverDirection::Maximize)
+            .build()
+            .unwrap();
+
+        let a = &request.polyhedron.a;
+        assert_eq!(a.rows, vec![0, 0, 1]);
+        assert_eq!(a.cols, vec![0, 2, 1]);
+        assert_eq!(a.vals, vec![1, 2, -1]);
+        assert_eq!(request.polyhedron.b, vec![5, 0]);
+    }
+
+    #[test]
+    fn test_dense_constraint_needs_one_coefficient_per_variable() {
+        let result = SolveRequestBuilder::new()
+            .add_variable(Variable::new("x1", 0, 1))
+            .add_dense_constraint(vec![1, 1], 1)
+            .add_objective([("x1".to_string(), 1.0)].into())
+            .direction(SolverDirection::Maximize)
+            .build();
+
+        assert!(matches!(
+            result,
+            Err(GlpkError::InvalidRequest(message))
+                if message == "Dense constraint 0 has 2 coefficients for 1 variables"
+        ));
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn test_matrix_constraints() {
+        let request = SolveRequestBuilder::new()
+            .add_variables(vec![
+                Variable::new("x1", 0, 1),
+                Variable::new("x2", 0, 1),
+                Variable::new("x3", 0, 1),
+            ])
+            .add_dense_constraint(vec![1, 1, 1], 2)
+            .add_matrix_constraints(ndarray::array![[1, 0, 2], [0, -1, 0]].view(), &[5, 0])
+            .add_objective([("x1".to_string(), 1.0)].into())
+            .direction(SolverDirection::Maximize)
+            .build()
+            .unwrap();
+
+        let a = &request.polyhedron.a;
+        assert_eq!(a.rows, vec![0, 0, 0, 1, 1, 2]);
+        assert_eq!(a.cols, vec![0, 1, 2, 0, 2, 1]);
+        assert_eq!(a.vals, vec![1, 1, 1, 1, 2, -1]);
+        assert_eq!(request.polyhedron.b, vec![2, 5, 0]);
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn test_matrix_constraints_check_shape() {
+        let result = SolveRequestBuilder::new()
+            .add_variable(Variable::new("x1", 0, 1))
+            .add_matrix_constraints(ndarray::array![[1], [1]].view(), &[1])
+            .add_objective([("x1".to_string(), 1.0)].into())
+            .direction(SolverDirection::Maximize)
+            .build();
+
+        assert!(matches!(
+            result,
+            Err(GlpkError::InvalidRequest(message))
+                if message == "Constraint matrix has 2 rows but b has 1 values"
+        ));
+    }
+
+    #[cfg(feature = "sprs")]
+    #[test]
+    fn test_csmat_constraints_accept_csc() {
+        let mut triplets = sprs::TriMat::new((2, 3));
+        triplets.add_triplet(1, 1, -1);
+        triplets.add_triplet(0, 2, 2);
+        triplets.add_triplet(0, 0, 1);
+        let csc = triplets.to_csc::<usize>();
+
+        let request = SolveRequestBuilder::new()
+            .add_variables(vec![
+                Variable::new("x1", 0, 1),
+                Variable::new("x2", 0, 1),
+                Variable::new("x3", 0, 1),
+            ])
+            .add_csmat_constraints(csc.view(), &[5, 0])
+            .add_objective([("x1".to_string(), 1.0)].into())
+            .direction(SolverDirection::Maximize)
+            .build()
+            .unwrap();
+
+        let a = &request.polyhedron.a;
+        assert_eq!(a.rows, vec![0, 0, 1]);
+        assert_eq!(a.cols, vec![0, 2, 1]);
+        assert_eq!(a.vals, vec![1, 2, -1]);
+        assert_eq!(request.polyhedron.b, vec![5, 0]);
+    }
+
+    #[test]
+    fn test_bulk_variable_helpers() {
+        let builder = SolveRequestBuilder::new()
+            .add_binary_variables(["a", "b"])
+            .add_variables_with_bounds("x", 3, -5, 5);
+
+        let variables: Vec<(&str, (i32, i32))> = builder
+            .variables
+            .iter()
+            .map(|v| (v.id.as_str(), v.bound))
+            .collect();
+        assert_eq!(
+            variables,
+            vec![
+                ("a", (0, 1)),
+                ("b", (0, 1)),
+                ("x1", (-5, 5)),
+                ("x2", (-5, 5)),
+                ("x3", (-5, 5)),
+            ]
+        );
+        assert_eq!(builder.var("x2").unwrap().col(), 3);
+    }
+
+    #[test]
+    fn test_solver_settings() {
+        let request = SolveRequestBuilder::new()
+            .add_variable(Variable::new("x", 0, 1))
+            .add_objective([("x".to_string(), 1.0)].into())
+            .direction(SolverDirection::Maximize)
+            .solver(SolverBackend::LpSolve)
+            .time_limit(Duration::from_millis(1500))
+            .mip_gap(0.05)
+            .solver_option("MIPFocus", 1)
+            .solver_option("Method", "barrier")
+            .build()
+            .unwrap();
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["solver"], "lpsolve");
+        assert_eq!(json["time_limit_seconds"], 1.5);
+        assert_eq!(json["mip_rel_gap"], 0.05);
+        assert_eq!(json["solver_options"]["MIPFocus"], 1);
+        assert_eq!(json["solver_options"]["Method"], "barrier");
+    }
+
+    #[test]
+    fn test_solver_settings_are_left_out_by_default() {
+        let request = SolveRequestBuilder::new()
+            .add_variable(Variable::new("x", 0, 1))
+            .add_objective([("x".to_string(), 1.0)].into())
+            .direction(SolverDirection::Maximize)
+            .build()
+            .unwrap();
+
+        let json = serde_json::to_value(&request).unwrap();
+        let keys: Vec<&String> = json.as_object().unwrap().keys().collect();
+        assert_eq!(keys.len(), 3);
+    }
+
+    #[test]
+    fn test_rejects_negative_mip_gap() {
+        let result = SolveRequestBuilder::new()
+            .add_variable(Variable::new("x", 0, 1))
+            .add_objective([("x".to_string(), 1.0)].into())
+            .direction(SolverDirection::Maximize)
+            .mip_gap(-0.1)
+            .build();
+
+        assert!(matches!(result, Err(GlpkError::InvalidRequest(_))));
+    }
 }
@@ -1,6 +1,74 @@
 use crate::error::{GlpkError, Result};
-use crate::types::{SolveRequest, SolveResponse};
-use reqwest::{Client, Url};
+use crate::interceptor::{Interceptor, Interceptors};
+use crate::metrics::{MetricsHook, RequestMetrics};
+use crate::retry::{random_u64, RetryPolicy};
+use crate::types::{Solution, SolveRequest, SolveResponse};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use futures_util::stream::{self, StreamExt};
+use reqwest::header::{CONTENT_ENCODING, CONTENT_TYPE};
+use reqwest::{Client, RequestBuilder, Response, Url};
+use serde::Deserialize;
+use std::future::Future;
+use std::io::Write;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+use web_time::Instant;
+
+/// The calls an application makes to the API, implemented by [`GlpkClient`]
+///
+/// Code written against this trait instead of the client can be tested
+/// with `MockGlpkClient` from the `mock` feature, without a server.
+///
+/// # Example
+///
+/// ```
+/// use glpk_api_sdk::{GlpkApi, Result, SolveRequest};
+///
+/// async fn best_objective(api: &impl GlpkApi, request: SolveRequest) -> Result<Option<f64>> {
+///     let response = api.solve(request).await?;
+///     Ok(response.best().map(|solution| solution.objective))
+/// }
+/// ```
+pub trait GlpkApi {
+    /// Check whether the server is healthy
+    fn health_check(&self) -> impl Future<Output = Result<bool>> + MaybeSend;
+
+    /// Solve one or more linear programming problems
+    fn solve(
+        &self,
+        request: SolveRequest,
+    ) -> impl Future<Output = Result<SolveResponse>> + MaybeSend;
+}
+
+impl GlpkApi for GlpkClient {
+    fn health_check(&self) -> impl Future<Output = Result<bool>> + MaybeSend {
+        GlpkClient::health_check(self)
+    }
+
+    fn solve(
+        &self,
+        request: SolveRequest,
+    ) -> impl Future<Output = Result<SolveResponse>> + MaybeSend {
+        GlpkClient::solve(self, request)
+    }
+}
+
+/// `Send`, except in WebAssembly where requests are browser promises that
+/// stay on one thread
+#[cfg(not(target_arch = "wasm32"))]
+pub trait MaybeSend: Send {}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<T: Send> MaybeSend for T {}
+
+/// `Send`, except in WebAssembly where requests are browser promises that
+/// stay on one thread
+#[cfg(target_arch = "wasm32")]
+pub trait MaybeSend {}
+
+#[cfg(target_arch = "wasm32")]
+impl<T> MaybeSend for T {}
 
 /// HTTP client for interacting with the GLPK REST API
 #[derive(Debug, Clone)]
@@ -8,6 +76,11 @@ pub struct GlpkClient {
     client: Client,
     base_url: Url,
     api_key: Option<String>,
+    retry: RetryPolicy,
+    solve_timeout: Option<Duration>,
+    compress_requests: bool,
+    interceptors: Interceptors,
+    metrics: Option<MetricsHook>,
 }
 
 impl GlpkClient {
@@ -32,12 +105,38 @@ impl GlpkClient {
             client: Client::new(),
             base_url,
             api_key: None,
+            retry: RetryPolicy::none(),
+            solve_timeout: None,
+            compress_requests: false,
+            interceptors: Interceptors::default(),
+            metrics: None,
         })
     }
 
+    /// Start building a client with timeouts and other settings
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use glpk_api_sdk::GlpkClient;
+    /// use std::time::Duration;
+    ///
+    /// let client = GlpkClient::builder()
+    ///     .base_url("http://localhost:9000")
+    ///     .connect_timeout(Duration::from_secs(2))
+    ///     .timeout(Duration::from_secs(10))
+    ///     .solve_timeout(Duration::from_secs(120))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn builder() -> GlpkClientBuilder {
+        GlpkClientBuilder::default()
+    }
+
     /// Create a new GLPK API client with custom reqwest client
     ///
-    /// This allows you to configure timeouts, proxies, etc.
+    /// This allows you to configure proxies, TLS, etc. For timeouts,
+    /// [`GlpkClient::builder`] is simpler.
     pub fn with_client(base_url: impl AsRef<str>, client: Client) -> Result<Self> {
         let base_url = Url::parse(base_url.as_ref())
             .map_err(|e| GlpkError::InvalidUrl(e.to_string()))?;
@@ -46,6 +145,11 @@ impl GlpkClient {
             client,
             base_url,
             api_key: None,
+            retry: RetryPolicy::none(),
+            solve_timeout: None,
+            compress_requests: false,
+            interceptors: Interceptors::default(),
+            metrics: None,
         })
     }
 
@@ -67,6 +171,119 @@ impl GlpkClient {
         self
     }
 
+    /// Retry requests that fail to connect or get a 5xx response
+    ///
+    /// Requests are sent once unless a policy is set.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use glpk_api_sdk::{GlpkClient, RetryPolicy};
+    ///
+    /// let client = GlpkClient::new("http://localhost:9000")
+    ///     .unwrap()
+    ///     .with_retry(RetryPolicy::new(3));
+    /// ```
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Gzip the body of solve requests
+    ///
+    /// Large constraint matrices compress well, which helps on slow links.
+    /// The server decompresses bodies sent with `Content-Encoding: gzip`.
+    pub fn with_request_compression(mut self, enabled: bool) -> Self {
+        self.compress_requests = enabled;
+        self
+    }
+
+    /// Run `interceptor` on every request and response
+    ///
+    /// Interceptors run in the order they were added.
+    pub fn with_interceptor(mut self, interceptor: impl Interceptor + 'static) -> Self {
+        self.interceptors.push(interceptor);
+        self
+    }
+
+    /// Call `callback` with the duration, body size and status of every
+    /// call the client makes, once its retries are done
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use glpk_api_sdk::GlpkClient;
+    ///
+    /// let client = GlpkClient::new("http://localhost:9000")
+    ///     .unwrap()
+    ///     .with_metrics(|metrics| {
+    ///         println!(
+    ///             "{} took {:?} ({} bytes, {:?})",
+    ///             metrics.path, metrics.duration, metrics.request_bytes, metrics.status
+    ///         );
+    ///     });
+    /// ```
+    pub fn with_metrics(
+        mut self,
+        callback: impl Fn(&RequestMetrics) + Send + Sync + 'static,
+    ) -> Self {
+        self.metrics = Some(MetricsHook::new(callback));
+        self
+    }
+
+    /// Send the request made by `build` with `request_id` in its
+    /// `x-request-id` header, again after a delay for as long as the retry
+    /// policy allows
+    async fn send(&self, request_id: &str, build: impl Fn() -> RequestBuilder) -> Result<Response> {
+        let mut attempt = 1;
+        let started = Instant::now();
+        let mut path = String::new();
+        let mut request_bytes = 0;
+        loop {
+            let result = match build().header(X_REQUEST_ID, request_id).build() {
+                Ok(mut request) => {
+                    self.interceptors.on_request(&mut request);
+                    path = request.url().path().to_string();
+                    request_bytes = request
+                        .body()
+                        .and_then(|body| body.as_bytes())
+                        .map_or(0, |body| body.len());
+                    self.client.execute(request).await
+                }
+                Err(e) => Err(e),
+            };
+            if let Ok(response) = &result {
+                self.interceptors.on_response(response);
+            }
+            let delay = match &result {
+                Ok(response) => {
+                    self.retry
+                        .response_delay(response.status(), response.headers(), attempt)
+                }
+                Err(e) => self.retry.retries_error(e).then(|| self.retry.delay(attempt)),
+            };
+            match delay {
+                Some(delay) if attempt < self.retry.max_attempts => {
+                    crate::retry::sleep(delay).await;
+                    attempt += 1;
+                }
+                _ => {
+                    if let Some(metrics) = &self.metrics {
+                        metrics.record(&RequestMetrics {
+                            path,
+                            request_id: request_id.to_string(),
+                            duration: started.elapsed(),
+                            request_bytes,
+                            status: result.as_ref().ok().map(|response| response.status()),
+                            attempts: attempt,
+                        });
+                    }
+                    return Ok(result?);
+                }
+            }
+        }
+    }
+
     /// Check the health of the API server
     ///
     /// # Example
@@ -84,7 +301,9 @@ impl GlpkClient {
         let url = self.base_url.join("/health")
             .map_err(|e| GlpkError::InvalidUrl(e.to_string()))?;
 
-        let response = self.client.get(url).send().await?;
+        let response = self
+            .send(&new_request_id(), || self.client.get(url.clone()))
+            .await?;
         Ok(response.status().is_success())
     }
 
@@ -124,20 +343,139 @@ impl GlpkClient {
     /// # }
     /// ```
     pub async fn solve(&self, request: SolveRequest) -> Result<SolveResponse> {
+        self.solve_with_request_id(request, new_request_id()).await
+    }
+
+    /// Solve a request with a single objective and return its solution
+    ///
+    /// Fails with [`GlpkError::InvalidRequest`] before sending if the
+    /// request doesn't have exactly one objective, and with
+    /// [`GlpkError::ParseError`] if the server answers with a different
+    /// number of solutions.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use glpk_api_sdk::{GlpkClient, SolveRequest};
+    /// # async fn example(request: SolveRequest) -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = GlpkClient::new("http://localhost:9000")?;
+    /// let solution = client.solve_one(request).await?;
+    /// println!("{}: {}", solution.status, solution.objective);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn solve_one(&self, request: SolveRequest) -> Result<Solution> {
+        if request.objectives.len() != 1 {
+            return Err(GlpkError::InvalidRequest(format!(
+                "solve_one needs exactly one objective, the request has {}",
+                request.objectives.len()
+            )));
+        }
+        let mut solutions = self.solve(request).await?.solutions;
+        if solutions.len() != 1 {
+            return Err(GlpkError::ParseError(format!(
+                "Expected one solution, the server returned {}",
+                solutions.len()
+            )));
+        }
+        Ok(solutions.remove(0))
+    }
+
+    /// Solve a problem, sending `request_id` as its `x-request-id` instead
+    /// of a generated one
+    ///
+    /// [`solve`](Self::solve) puts the id it generated in
+    /// [`SolveResponse::request_id`], which is only there on success.
+    /// Picking the id up front lets it be logged for failed calls too.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use glpk_api_sdk::{GlpkClient, SolveRequest};
+    /// # async fn example(request: SolveRequest) -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = GlpkClient::new("http://localhost:9000")?;
+    /// let request_id = "order-4711-plan";
+    /// if let Err(e) = client.solve_with_request_id(request, request_id).await {
+    ///     eprintln!("solve {} failed: {}", request_id, e);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn solve_with_request_id(
+        &self,
+        request: SolveRequest,
+        request_id: impl Into<String>,
+    ) -> Result<SolveResponse> {
+        // Serialized once, not again for every retry
+        let body = serde_json::to_vec(&request)
+            .map_err(|e| GlpkError::InvalidRequest(e.to_string()))?;
+        let body = if self.compress_requests { gzip(&body)? } else { body };
+
+        self.post_solve(request_id.into(), || body.clone().into()).await
+    }
+
+    /// Solve a very large problem without building its JSON in memory
+    ///
+    /// The request is serialized on a separate thread while it is sent, in
+    /// chunks of 64 KiB with chunked transfer encoding, and the server
+    /// parses it as it arrives. Worth it once the JSON runs into hundreds
+    /// of megabytes; below that [`solve`](Self::solve) is simpler and
+    /// faster. Request compression and retries apply as usual, each retry
+    /// serializing the request again. Not available in WebAssembly.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use glpk_api_sdk::{GlpkClient, SolveRequest};
+    /// # async fn example(huge: SolveRequest) -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = GlpkClient::new("http://localhost:9000")?.with_request_compression(true);
+    /// let response = client.solve_streamed(huge).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn solve_streamed(&self, request: SolveRequest) -> Result<SolveResponse> {
+        let request = std::sync::Arc::new(request);
+        self.post_solve(new_request_id(), || {
+            streamed_body(request.clone(), self.compress_requests)
+        })
+        .await
+    }
+
+    /// Post the body made by `body` to `/solve` and read the answer
+    async fn post_solve(
+        &self,
+        request_id: String,
+        body: impl Fn() -> reqwest::Body,
+    ) -> Result<SolveResponse> {
         let url = self.base_url.join("/solve")
             .map_err(|e| GlpkError::InvalidUrl(e.to_string()))?;
 
-        let mut req_builder = self.client.post(url).json(&request);
-
-        // Add API key header if set
-        if let Some(ref api_key) = self.api_key {
-            req_builder = req_builder.header("X-API-Key", api_key);
-        }
+        let response = self
+            .send(&request_id, || {
+                let mut req_builder = self
+                    .client
+                    .post(url.clone())
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(body());
+                if self.compress_requests {
+                    req_builder = req_builder.header(CONTENT_ENCODING, "gzip");
+                }
 
-        let response = req_builder.send().await?;
+                // Add API key header if set
+                if let Some(ref api_key) = self.api_key {
+                    req_builder = req_builder.header("X-API-Key", api_key);
+                }
+                if let Some(timeout) = self.solve_timeout {
+                    req_builder = req_builder.timeout(timeout);
+                }
+                req_builder
+            })
+            .await?;
 
         if !response.status().is_success() {
             let status = response.status();
+            let retry_after = crate::retry::retry_after(response.headers());
             let error_text = response
                 .text()
                 .await
@@ -145,22 +483,509 @@ impl GlpkClient {
 
             return Err(match status.as_u16() {
                 401 | 403 => GlpkError::AuthenticationFailed,
+                422 => validation_error(error_text),
+                429 => GlpkError::RateLimited { retry_after },
                 _ => GlpkError::ApiError(error_text),
             });
         }
 
-        let solve_response: SolveResponse = response
+        let mut solve_response: SolveResponse = response
             .json()
             .await
             .map_err(|e| GlpkError::ParseError(e.to_string()))?;
+        solve_response.request_id = Some(request_id);
 
         Ok(solve_response)
     }
+
+    /// Solve a problem, giving up as soon as `cancel` is cancelled
+    ///
+    /// Cancelling closes the connection, which makes the server stop
+    /// solving, and returns [`GlpkError::Cancelled`]. Retries and the
+    /// delays between them are cut short too.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use glpk_api_sdk::{CancellationToken, GlpkClient, SolveRequest};
+    /// # async fn example(request: SolveRequest) -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = GlpkClient::new("http://localhost:9000")?;
+    /// let cancel = CancellationToken::new();
+    ///
+    /// // Hand a clone to whatever decides the result is no longer needed
+    /// let on_close = cancel.clone();
+    /// tokio::spawn(async move {
+    ///     tokio::time::sleep(std::time::Duration::from_secs(10)).await;
+    ///     on_close.cancel();
+    /// });
+    ///
+    /// let response = client.solve_with_cancel(request, &cancel).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn solve_with_cancel(
+        &self,
+        request: SolveRequest,
+        cancel: &CancellationToken,
+    ) -> Result<SolveResponse> {
+        tokio::select! {
+            biased;
+            _ = cancel.cancelled() => Err(GlpkError::Cancelled),
+            result = self.solve(request) => result,
+        }
+    }
+
+    /// Solve several requests, at most `max_concurrency` at a time, with
+    /// one result per request in the order they were given
+    ///
+    /// A failed request doesn't stop the others. Keep `max_concurrency` at
+    /// or below the number of solves the server runs in parallel; it is
+    /// raised to 1 if 0.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use glpk_api_sdk::{GlpkClient, SolveRequest};
+    /// # async fn example(requests: Vec<SolveRequest>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = GlpkClient::new("http://localhost:9000")?;
+    /// for (i, result) in client.solve_many(requests, 4).await.into_iter().enumerate() {
+    ///     match result {
+    ///         Ok(response) => println!("request {}: {:?}", i, response.best()),
+    ///         Err(e) => eprintln!("request {} failed: {}", i, e),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn solve_many(
+        &self,
+        requests: impl IntoIterator<Item = SolveRequest>,
+        max_concurrency: usize,
+    ) -> Vec<Result<SolveResponse>> {
+        stream::iter(requests)
+            .map(|request| self.solve(request))
+            .buffered(max_concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    /// Solve a problem on the server, or in-process with
+    /// [`local::solve`](crate::local::solve) when the server can't be reached
+    ///
+    /// Only connection failures fall back; errors the server answers with
+    /// are returned as they are. Needs the `local` feature.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use glpk_api_sdk::{GlpkClient, SolveRequest};
+    /// # async fn example(request: SolveRequest) -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = GlpkClient::new("http://localhost:9000")?;
+    /// let response = client.solve_or_local(request).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "local")]
+    pub async fn solve_or_local(&self, request: SolveRequest) -> Result<SolveResponse> {
+        match self.solve(request.clone()).await {
+            Err(GlpkError::Request(e)) if unreachable(&e) => crate::local::solve(&request),
+            result => result,
+        }
+    }
+}
+
+/// Whether a request failed because the server couldn't be reached
+#[cfg(all(feature = "local", not(target_arch = "wasm32")))]
+fn unreachable(error: &reqwest::Error) -> bool {
+    error.is_connect()
+}
+
+/// Whether a request failed because the server couldn't be reached; the
+/// browser reports every failed fetch the same way
+#[cfg(all(feature = "local", target_arch = "wasm32"))]
+fn unreachable(error: &reqwest::Error) -> bool {
+    error.is_request()
+}
+
+/// Header identifying a call in the server's logs
+const X_REQUEST_ID: &str = "x-request-id";
+
+/// A random version 4 UUID, to identify a call
+fn new_request_id() -> String {
+    let (a, b) = (random_u64(), random_u64());
+    format!(
+        "{:08x}-{:04x}-4{:03x}-{:04x}-{:012x}",
+        a >> 32,
+        (a >> 16) & 0xffff,
+        a & 0x0fff,
+        (b >> 48) & 0x3fff | 0x8000,
+        b & 0xffff_ffff_ffff
+    )
+}
+
+/// Size of the chunks [`GlpkClient::solve_streamed`] sends
+#[cfg(not(target_arch = "wasm32"))]
+const STREAM_CHUNK_BYTES: usize = 64 * 1024;
+
+/// Body that serializes `request` on its own thread as it is sent
+#[cfg(not(target_arch = "wasm32"))]
+fn streamed_body(request: std::sync::Arc<SolveRequest>, compress: bool) -> reqwest::Body {
+    let (sender, receiver) = tokio::sync::mpsc::channel(4);
+    std::thread::spawn(move || {
+        let writer = std::io::BufWriter::with_capacity(STREAM_CHUNK_BYTES, ChannelWriter(sender));
+        // Fails only once the request is dropped, when nobody reads the rest
+        let _ = if compress {
+            let mut encoder = GzEncoder::new(writer, Compression::default());
+            serde_json::to_writer(&mut encoder, &*request)
+                .map_err(std::io::Error::from)
+                .and_then(|_| encoder.finish()?.flush())
+        } else {
+            let mut writer = writer;
+            serde_json::to_writer(&mut writer, &*request)
+                .map_err(std::io::Error::from)
+                .and_then(|_| writer.flush())
+        };
+    });
+    reqwest::Body::wrap_stream(stream::unfold(receiver, |mut receiver| async move {
+        let chunk = receiver.recv().await?;
+        Some((Ok::<_, std::io::Error>(chunk), receiver))
+    }))
+}
+
+/// Writer handing each write to the body stream of [`streamed_body`]
+#[cfg(not(target_arch = "wasm32"))]
+struct ChannelWriter(tokio::sync::mpsc::Sender<Vec<u8>>);
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Write for ChannelWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.blocking_send(buf.to_vec()).map_err(|_| {
+            std::io::Error::new(std::io::ErrorKind::BrokenPipe, "request was dropped")
+        })?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Gzip a request body
+fn gzip(body: &[u8]) -> Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(body)
+        .and_then(|_| encoder.finish())
+        .map_err(|e| GlpkError::InvalidRequest(format!("Failed to compress request: {}", e)))
+}
+
+/// Body of a 422 response
+#[derive(Deserialize)]
+struct ValidationBody {
+    error: String,
+    code: Option<String>,
+    field: Option<String>,
+}
+
+/// Turn a 422 body into [`GlpkError::Validation`], keeping bodies that
+/// aren't the server's JSON error as [`GlpkError::ApiError`]
+fn validation_error(body: String) -> GlpkError {
+    match serde_json::from_str::<ValidationBody>(&body) {
+        Ok(parsed) => GlpkError::Validation {
+            code: parsed.code,
+            field: parsed.field,
+            message: parsed.error,
+        },
+        Err(_) => GlpkError::ApiError(body),
+    }
+}
+
+/// Builder for a [`GlpkClient`], created with [`GlpkClient::builder`]
+#[derive(Debug, Default)]
+pub struct GlpkClientBuilder {
+    base_url: Option<String>,
+    api_key: Option<String>,
+    solve_timeout: Option<Duration>,
+    #[cfg(not(target_arch = "wasm32"))]
+    connection: ConnectionSettings,
+    retry: RetryPolicy,
+    compress_requests: bool,
+    interceptors: Interceptors,
+    metrics: Option<MetricsHook>,
+}
+
+impl GlpkClientBuilder {
+    /// Set the base URL of the GLPK API (e.g., "http://localhost:9000")
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Set the API key for authentication
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Limit how long a `solve` call may take, in place of `timeout`
+    ///
+    /// Solves can take far longer than other requests, so this can be set
+    /// higher than `timeout` to keep health checks quick.
+    pub fn solve_timeout(mut self, timeout: Duration) -> Self {
+        self.solve_timeout = Some(timeout);
+        self
+    }
+
+    /// Retry requests that fail to connect or get a 5xx response
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Gzip the body of solve requests
+    pub fn request_compression(mut self, enabled: bool) -> Self {
+        self.compress_requests = enabled;
+        self
+    }
+
+    /// Run `interceptor` on every request and response
+    pub fn interceptor(mut self, interceptor: impl Interceptor + 'static) -> Self {
+        self.interceptors.push(interceptor);
+        self
+    }
+
+    /// Call `callback` with the duration, body size and status of every call
+    pub fn metrics(mut self, callback: impl Fn(&RequestMetrics) + Send + Sync + 'static) -> Self {
+        self.metrics = Some(MetricsHook::new(callback));
+        self
+    }
+
+    /// Build the client
+    pub fn build(self) -> Result<GlpkClient> {
+        let base_url = self
+            .base_url
+            .ok_or_else(|| GlpkError::InvalidUrl("Base URL must be set".to_string()))?;
+        let base_url =
+            Url::parse(&base_url).map_err(|e| GlpkError::InvalidUrl(e.to_string()))?;
+
+        #[cfg(not(target_arch = "wasm32"))]
+        let client = self.connection.apply(Client::builder())?;
+        // The browser's fetch manages connections, TLS and proxies itself
+        #[cfg(target_arch = "wasm32")]
+        let client = Client::builder();
+
+        Ok(GlpkClient {
+            client: client.build()?,
+            base_url,
+            api_key: self.api_key,
+            retry: self.retry,
+            solve_timeout: self.solve_timeout,
+            compress_requests: self.compress_requests,
+            interceptors: self.interceptors,
+            metrics: self.metrics,
+        })
+    }
+}
+
+/// Connection settings, not available in WebAssembly where the browser
+/// manages connections
+#[cfg(not(target_arch = "wasm32"))]
+impl GlpkClientBuilder {
+    /// Limit how long any request may take, from connecting until the
+    /// response body has been read
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.connection.timeout = Some(timeout);
+        self
+    }
+
+    /// Limit how long connecting to the server may take
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connection.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Keep at most `max` idle connections per host for reuse
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.connection.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Close connections that have been idle for `timeout`
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.connection.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Send TCP keep-alive probes on idle connections every `interval`
+    pub fn tcp_keepalive(mut self, interval: Duration) -> Self {
+        self.connection.tcp_keepalive = Some(interval);
+        self
+    }
+
+    /// Talk HTTP/2 without negotiating it first, for servers or proxies
+    /// known to accept it over plain TCP
+    pub fn http2_prior_knowledge(mut self) -> Self {
+        self.connection.http2_prior_knowledge = true;
+        self
+    }
+
+    /// Ping HTTP/2 connections every `interval` to keep them open
+    pub fn http2_keep_alive_interval(mut self, interval: Duration) -> Self {
+        self.connection.http2_keep_alive_interval = Some(interval);
+        self
+    }
+
+    /// Send all requests through the proxy at `url`, e.g.
+    /// `http://proxy.internal:3128`
+    ///
+    /// Without this, proxies from the `HTTP_PROXY`, `HTTPS_PROXY` and
+    /// `NO_PROXY` environment variables are used.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use glpk_api_sdk::GlpkClient;
+    ///
+    /// let client = GlpkClient::builder()
+    ///     .base_url("https://solver.example.com")
+    ///     .proxy("http://proxy.internal:3128")
+    ///     .proxy_basic_auth("svc-solver", "secret")
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn proxy(mut self, url: impl Into<String>) -> Self {
+        self.connection.proxy = Some(url.into());
+        self
+    }
+
+    /// Authenticate to the proxy set with [`proxy`](Self::proxy) with a
+    /// username and password
+    pub fn proxy_basic_auth(
+        mut self,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Self {
+        self.connection.proxy_auth = Some((username.into(), password.into()));
+        self
+    }
+
+    /// Connect directly, ignoring the proxy environment variables
+    pub fn no_proxy(mut self) -> Self {
+        self.connection.no_proxy = true;
+        self
+    }
+
+    /// Trust `certificate` in addition to the system's root certificates,
+    /// for servers behind a private CA
+    ///
+    /// Needs the `native-tls` or `rustls` feature.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use glpk_api_sdk::{Certificate, GlpkClient};
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let pem = std::fs::read("internal-ca.pem")?;
+    /// let client = GlpkClient::builder()
+    ///     .base_url("https://solver.internal")
+    ///     .root_certificate(Certificate::from_pem(&pem)?)
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(any(feature = "native-tls", feature = "rustls"))]
+    pub fn root_certificate(mut self, certificate: reqwest::Certificate) -> Self {
+        self.connection.root_certificates.push(certificate);
+        self
+    }
+
+    /// Accept any server certificate, even expired or self-signed ones
+    ///
+    /// This makes the connection open to interception; only use it against
+    /// test servers. Needs the `native-tls` or `rustls` feature.
+    #[cfg(any(feature = "native-tls", feature = "rustls"))]
+    pub fn danger_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.connection.accept_invalid_certs = accept;
+        self
+    }
+}
+
+/// Settings of the underlying HTTP client that browsers don't let a page
+/// control
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Default)]
+struct ConnectionSettings {
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    pool_max_idle_per_host: Option<usize>,
+    pool_idle_timeout: Option<Duration>,
+    tcp_keepalive: Option<Duration>,
+    http2_prior_knowledge: bool,
+    http2_keep_alive_interval: Option<Duration>,
+    proxy: Option<String>,
+    proxy_auth: Option<(String, String)>,
+    no_proxy: bool,
+    #[cfg(any(feature = "native-tls", feature = "rustls"))]
+    root_certificates: Vec<reqwest::Certificate>,
+    #[cfg(any(feature = "native-tls", feature = "rustls"))]
+    accept_invalid_certs: bool,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ConnectionSettings {
+    fn apply(self, mut client: reqwest::ClientBuilder) -> Result<reqwest::ClientBuilder> {
+        if let Some(timeout) = self.timeout {
+            client = client.timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            client = client.connect_timeout(timeout);
+        }
+        if let Some(max) = self.pool_max_idle_per_host {
+            client = client.pool_max_idle_per_host(max);
+        }
+        if let Some(timeout) = self.pool_idle_timeout {
+            client = client.pool_idle_timeout(timeout);
+        }
+        if self.tcp_keepalive.is_some() {
+            client = client.tcp_keepalive(self.tcp_keepalive);
+        }
+        if self.http2_prior_knowledge {
+            client = client.http2_prior_knowledge();
+        }
+        if let Some(interval) = self.http2_keep_alive_interval {
+            client = client
+                .http2_keep_alive_interval(interval)
+                .http2_keep_alive_while_idle(true);
+        }
+        if self.no_proxy {
+            client = client.no_proxy();
+        }
+        if let Some(url) = self.proxy {
+            let mut proxy = reqwest::Proxy::all(&url)
+                .map_err(|e| GlpkError::InvalidUrl(format!("Invalid proxy {}: {}", url, e)))?;
+            if let Some((username, password)) = &self.proxy_auth {
+                proxy = proxy.basic_auth(username, password);
+            }
+            client = client.proxy(proxy);
+        }
+        #[cfg(any(feature = "native-tls", feature = "rustls"))]
+        {
+            for certificate in self.root_certificates {
+                client = client.add_root_certificate(certificate);
+            }
+            client = client.danger_accept_invalid_certs(self.accept_invalid_certs);
+        }
+        Ok(client)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{SolveRequestBuilder, SolverDirection, Variable};
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
 
     #[test]
     fn test_client_creation() {
@@ -181,4 +1006,561 @@ mod tests {
         let client = GlpkClient::new("not a valid url");
         assert!(client.is_err());
     }
+
+    /// Serve one canned HTTP response per connection, in order, returning
+    /// the server's URL
+    fn serve(responses: Vec<&'static str>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = [0u8; 4096];
+                let _ = stream.read(&mut request);
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        url
+    }
+
+    const UNAVAILABLE: &str =
+        "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 4\r\nConnection: close\r\n\r\nbusy";
+    const OK: &str = "HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok";
+    const SOLVED: &str = "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 16\r\nConnection: close\r\n\r\n{\"solutions\":[]}";
+
+    #[test]
+    fn test_builder_requires_base_url() {
+        let client = GlpkClient::builder().timeout(Duration::from_secs(1)).build();
+        assert!(matches!(client, Err(GlpkError::InvalidUrl(_))));
+    }
+
+    #[tokio::test]
+    async fn test_solve_timeout() {
+        // Accepts the connection but never answers
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let (_stream, _) = listener.accept().unwrap();
+            std::thread::sleep(Duration::from_secs(5));
+        });
+        let client = GlpkClient::builder()
+            .base_url(url)
+            .solve_timeout(Duration::from_millis(50))
+            .build()
+            .unwrap();
+        let request = SolveRequestBuilder::new()
+            .add_variable(Variable::new("x", 0, 1))
+            .add_objective([("x".to_string(), 1.0)].into())
+            .direction(SolverDirection::Maximize)
+            .build()
+            .unwrap();
+
+        let error = client.solve(request).await.unwrap_err();
+
+        assert!(matches!(error, GlpkError::Request(e) if e.is_timeout()));
+    }
+
+    #[tokio::test]
+    async fn test_retries_server_errors() {
+        let url = serve(vec![UNAVAILABLE, UNAVAILABLE, OK]);
+        let client = GlpkClient::new(url).unwrap().with_retry(
+            RetryPolicy::new(3).with_backoff(Duration::from_millis(1), Duration::from_millis(1)),
+        );
+
+        assert!(client.health_check().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_attempts() {
+        let url = serve(vec![UNAVAILABLE, UNAVAILABLE, OK]);
+        let client = GlpkClient::new(url).unwrap().with_retry(
+            RetryPolicy::new(2).with_backoff(Duration::from_millis(1), Duration::from_millis(1)),
+        );
+
+        assert!(!client.health_check().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_sends_once_by_default() {
+        let url = serve(vec![UNAVAILABLE, OK]);
+        let client = GlpkClient::new(url).unwrap();
+
+        assert!(!client.health_check().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_closes_connection() {
+        // Accepts the connection, never answers, and reports when the
+        // client hangs up
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (closed_tx, closed_rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buffer = [0u8; 4096];
+            while !matches!(stream.read(&mut buffer), Ok(0) | Err(_)) {}
+            closed_tx.send(()).unwrap();
+        });
+        let client = GlpkClient::new(url).unwrap();
+        let request = SolveRequestBuilder::new()
+            .add_variable(Variable::new("x", 0, 1))
+            .add_objective([("x".to_string(), 1.0)].into())
+            .direction(SolverDirection::Maximize)
+            .build()
+            .unwrap();
+        let cancel = CancellationToken::new();
+        let trigger = cancel.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            trigger.cancel();
+        });
+
+        let error = client.solve_with_cancel(request, &cancel).await.unwrap_err();
+
+        assert!(matches!(error, GlpkError::Cancelled));
+        let closed = tokio::task::spawn_blocking(move || {
+            closed_rx.recv_timeout(Duration::from_secs(5))
+        });
+        assert!(closed.await.unwrap().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_interceptors_run_on_each_request() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        struct Traced(Arc<AtomicUsize>);
+
+        impl Interceptor for Traced {
+            fn on_request(&self, request: &mut reqwest::Request) {
+                request
+                    .headers_mut()
+                    .insert("X-Trace-Id", "abc".parse().unwrap());
+            }
+
+            fn on_response(&self, _response: &Response) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        // Answers 503 unless the trace header was sent
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for _ in 0..2 {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = [0u8; 4096];
+                let read = stream.read(&mut request).unwrap();
+                let traced = String::from_utf8_lossy(&request[..read])
+                    .to_lowercase()
+                    .contains("x-trace-id: abc");
+                let response = if traced { OK } else { UNAVAILABLE };
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        let responses = Arc::new(AtomicUsize::new(0));
+        let client = GlpkClient::builder()
+            .base_url(url)
+            .retry(RetryPolicy::new(2).with_backoff(Duration::from_millis(1), Duration::from_millis(1)))
+            .interceptor(Traced(responses.clone()))
+            .build()
+            .unwrap();
+
+        assert!(client.health_check().await.unwrap());
+        assert!(client.health_check().await.unwrap());
+        assert_eq!(responses.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_parses_validation_errors() {
+        let error = validation_error(
+            r#"{"error": "b has 2 values for 3 rows", "code": "shape_mismatch", "field": "polyhedron.b"}"#
+                .to_string(),
+        );
+        assert!(matches!(
+            error,
+            GlpkError::Validation { code: Some(code), field: Some(field), message }
+                if code == "shape_mismatch" && field == "polyhedron.b"
+                    && message == "b has 2 values for 3 rows"
+        ));
+
+        let error = validation_error(r#"{"error": "time_limit must be positive"}"#.to_string());
+        assert!(matches!(
+            error,
+            GlpkError::Validation { code: None, field: None, message }
+                if message == "time_limit must be positive"
+        ));
+
+        let error = validation_error("not json".to_string());
+        assert!(matches!(error, GlpkError::ApiError(text) if text == "not json"));
+    }
+
+    #[tokio::test]
+    async fn test_solve_reports_validation_errors() {
+        let url = serve(vec![
+            "HTTP/1.1 422 Unprocessable Entity\r\nContent-Type: application/json\r\nContent-Length: 27\r\nConnection: close\r\n\r\n{\"error\": \"b is too short\"}",
+        ]);
+        let client = GlpkClient::new(url).unwrap();
+        let request = SolveRequestBuilder::new()
+            .add_variable(Variable::new("x", 0, 1))
+            .add_objective([("x".to_string(), 1.0)].into())
+            .direction(SolverDirection::Maximize)
+            .build()
+            .unwrap();
+
+        let error = client.solve(request).await.unwrap_err();
+
+        assert!(matches!(
+            error,
+            GlpkError::Validation { message, .. } if message == "b is too short"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_request_compression() {
+        use flate2::read::GzDecoder;
+
+        // Answers with the decompressed request body's objective count
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut received = Vec::new();
+            let mut buffer = [0u8; 4096];
+            let (head, body) = loop {
+                let read = stream.read(&mut buffer).unwrap();
+                received.extend_from_slice(&buffer[..read]);
+                let text = String::from_utf8_lossy(&received).to_lowercase();
+                if let Some(end) = text.find("\r\n\r\n") {
+                    let length: usize = text
+                        .lines()
+                        .find_map(|line| line.strip_prefix("content-length: "))
+                        .unwrap()
+                        .trim()
+                        .parse()
+                        .unwrap();
+                    if received.len() >= end + 4 + length {
+                        break (text[..end].to_string(), received[end + 4..].to_vec());
+                    }
+                }
+            };
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 16\r\nConnection: close\r\n\r\n{\"solutions\":[]}")
+                .unwrap();
+            let mut json = String::new();
+            GzDecoder::new(&body[..]).read_to_string(&mut json).unwrap();
+            (head, json)
+        });
+        let client = GlpkClient::builder()
+            .base_url(url)
+            .request_compression(true)
+            .build()
+            .unwrap();
+        let request = SolveRequestBuilder::new()
+            .add_variable(Variable::new("x", 0, 1))
+            .add_objective([("x".to_string(), 1.0)].into())
+            .direction(SolverDirection::Maximize)
+            .build()
+            .unwrap();
+
+        assert!(client.solve(request).await.is_ok());
+
+        let (head, json) = server.join().unwrap();
+        assert!(head.contains("content-encoding: gzip"));
+        assert!(head.contains("content-type: application/json"));
+        let sent: SolveRequest = serde_json::from_str(&json).unwrap();
+        assert_eq!(sent.polyhedron.variables[0].id, "x");
+    }
+
+    const RATE_LIMITED: &str = "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 0\r\nContent-Length: 4\r\nConnection: close\r\n\r\nslow";
+
+    #[tokio::test]
+    async fn test_retries_rate_limited_requests() {
+        let url = serve(vec![RATE_LIMITED, RATE_LIMITED, OK]);
+        let client = GlpkClient::new(url).unwrap().with_retry(RetryPolicy::new(3));
+
+        assert!(client.health_check().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_reports_rate_limit_once_retries_run_out() {
+        let url = serve(vec![RATE_LIMITED]);
+        let client = GlpkClient::new(url).unwrap();
+        let request = SolveRequestBuilder::new()
+            .add_variable(Variable::new("x", 0, 1))
+            .add_objective([("x".to_string(), 1.0)].into())
+            .direction(SolverDirection::Maximize)
+            .build()
+            .unwrap();
+
+        let error = client.solve(request).await.unwrap_err();
+
+        assert!(matches!(
+            error,
+            GlpkError::RateLimited { retry_after: Some(wait) } if wait == Duration::ZERO
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_records_metrics_once_per_call() {
+        use std::sync::{Arc, Mutex};
+
+        let url = serve(vec![UNAVAILABLE, OK]);
+        let recorded = Arc::new(Mutex::new(Vec::new()));
+        let sink = recorded.clone();
+        let client = GlpkClient::new(url)
+            .unwrap()
+            .with_retry(RetryPolicy::new(2).with_backoff(Duration::from_millis(1), Duration::from_millis(1)))
+            .with_metrics(move |metrics| sink.lock().unwrap().push(metrics.clone()));
+
+        assert!(client.health_check().await.unwrap());
+
+        let recorded = recorded.lock().unwrap();
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded[0].path, "/health");
+        assert_eq!(recorded[0].status, Some(reqwest::StatusCode::OK));
+        assert_eq!(recorded[0].attempts, 2);
+        assert_eq!(recorded[0].request_bytes, 0);
+    }
+
+    #[tokio::test]
+    async fn test_builder_connection_settings() {
+        let url = serve(vec![OK, OK]);
+        let client = GlpkClient::builder()
+            .base_url(url)
+            .pool_max_idle_per_host(4)
+            .pool_idle_timeout(Duration::from_secs(30))
+            .tcp_keepalive(Duration::from_secs(15))
+            .http2_keep_alive_interval(Duration::from_secs(10))
+            .build()
+            .unwrap();
+
+        assert!(client.health_check().await.unwrap());
+        assert!(client.health_check().await.unwrap());
+    }
+
+    #[cfg(any(feature = "native-tls", feature = "rustls"))]
+    #[test]
+    fn test_builder_tls_settings() {
+        // Self-signed certificate for localhost
+        const PEM: &str = "\
+-----BEGIN CERTIFICATE-----\n\
+MIIBgDCCASWgAwIBAgIUfbYWb71XXtOy4jdBbxBiiChRmzcwCgYIKoZIzj0EAwIw\n\
+FDESMBAGA1UEAwwJbG9jYWxob3N0MCAXDTI2MTAxNzA2Mjk1NVoYDzIxMjYwOTIz\n\
+MDYyOTU1WjAUMRIwEAYDVQQDDAlsb2NhbGhvc3QwWTATBgcqhkjOPQIBBggqhkjO\n\
+PQMBBwNCAAT22jUAmO4WhVLfcry2YZsjwTjqELw0OP6+R9f+ssds1bIfg6gKVyR2\n\
+m1Lh8J/fHVNYpgpvzVOPA6G8A0KO2qDWo1MwUTAdBgNVHQ4EFgQUUm9e8K+6LLEW\n\
+VpxQyEqMRU98Wb8wHwYDVR0jBBgwFoAUUm9e8K+6LLEWVpxQyEqMRU98Wb8wDwYD\n\
+VR0TAQH/BAUwAwEB/zAKBggqhkjOPQQDAgNJADBGAiEAjgQBR89jArnGoi1R6xnY\n\
+oSnLUQ3sexRn/R0Vb6T+oroCIQDYJ35X72EiAXjWODBmqwrD/o1d8+2gVclhPU7g\n\
+FbkSbA==\n\
+-----END CERTIFICATE-----\n";
+
+        let client = GlpkClient::builder()
+            .base_url("https://localhost:9443")
+            .root_certificate(reqwest::Certificate::from_pem(PEM.as_bytes()).unwrap())
+            .danger_accept_invalid_certs(false)
+            .build();
+
+        assert!(client.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_sends_through_proxy() {
+        // Plays the proxy, answering in place of the server
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let proxy = format!("http://{}", listener.local_addr().unwrap());
+        let received = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0u8; 4096];
+            let read = stream.read(&mut request).unwrap();
+            stream.write_all(OK.as_bytes()).unwrap();
+            String::from_utf8_lossy(&request[..read]).to_string()
+        });
+        let client = GlpkClient::builder()
+            .base_url("http://solver.invalid:9000")
+            .proxy(proxy)
+            .proxy_basic_auth("user", "pass")
+            .build()
+            .unwrap();
+
+        assert!(client.health_check().await.unwrap());
+
+        let request = received.join().unwrap();
+        assert!(request.starts_with("GET http://solver.invalid:9000/health"));
+        // base64 of "user:pass"
+        assert!(request
+            .to_lowercase()
+            .contains("proxy-authorization: basic dxnlcjpwyxnz"));
+    }
+
+    #[test]
+    fn test_rejects_invalid_proxy() {
+        let client = GlpkClient::builder()
+            .base_url("http://localhost:9000")
+            .proxy("not a url")
+            .build();
+        assert!(matches!(client, Err(GlpkError::InvalidUrl(_))));
+    }
+
+    #[cfg(feature = "local")]
+    #[tokio::test]
+    async fn test_solves_locally_when_unreachable() {
+        // Nothing listens on the port once the listener is dropped
+        let url = {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            format!("http://{}", listener.local_addr().unwrap())
+        };
+        let request = SolveRequestBuilder::new()
+            .add_variable(Variable::new("x", 0, 4))
+            .add_objective([("x".to_string(), 1.0)].into())
+            .direction(SolverDirection::Maximize)
+            .build()
+            .unwrap();
+
+        let client = GlpkClient::new(url).unwrap();
+        let response = client.solve_or_local(request.clone()).await.unwrap();
+        assert_eq!(response.solutions[0].value("x"), Some(4));
+
+        // A server that answers isn't fallen back from
+        let client = GlpkClient::new(serve(vec![UNAVAILABLE])).unwrap();
+        assert!(matches!(
+            client.solve_or_local(request).await,
+            Err(GlpkError::ApiError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_solve_many_keeps_order() {
+        // Serves one connection at a time, so at most one request is in flight
+        let url = serve(vec![SOLVED, UNAVAILABLE, SOLVED]);
+        let client = GlpkClient::new(url).unwrap();
+        let request = SolveRequestBuilder::new()
+            .add_variable(Variable::new("x", 0, 1))
+            .add_objective([("x".to_string(), 1.0)].into())
+            .direction(SolverDirection::Maximize)
+            .build()
+            .unwrap();
+
+        let results = client.solve_many(vec![request; 3], 1).await;
+
+        assert!(results[0].is_ok());
+        assert!(matches!(results[1], Err(GlpkError::ApiError(_))));
+        assert!(results[2].is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_sends_request_id_on_every_attempt() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let received = std::thread::spawn(move || {
+            let mut heads = Vec::new();
+            for response in [UNAVAILABLE, SOLVED] {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = [0u8; 4096];
+                let read = stream.read(&mut request).unwrap();
+                stream.write_all(response.as_bytes()).unwrap();
+                heads.push(String::from_utf8_lossy(&request[..read]).to_lowercase());
+            }
+            heads
+        });
+        let client = GlpkClient::new(url).unwrap().with_retry(
+            RetryPolicy::new(2).with_backoff(Duration::from_millis(1), Duration::from_millis(1)),
+        );
+        let request = SolveRequestBuilder::new()
+            .add_variable(Variable::new("x", 0, 1))
+            .add_objective([("x".to_string(), 1.0)].into())
+            .direction(SolverDirection::Maximize)
+            .build()
+            .unwrap();
+
+        let response = client.solve_with_request_id(request, "support-42").await.unwrap();
+
+        assert_eq!(response.request_id.as_deref(), Some("support-42"));
+        for head in received.join().unwrap() {
+            assert!(head.contains("x-request-id: support-42"));
+        }
+    }
+
+    #[test]
+    fn test_generates_uuid_request_ids() {
+        let id = new_request_id();
+        assert_eq!(id.len(), 36);
+        assert_eq!(id.as_bytes()[14], b'4');
+        assert!(matches!(id.as_bytes()[19], b'8' | b'9' | b'a' | b'b'));
+        assert_ne!(id, new_request_id());
+    }
+
+    #[tokio::test]
+    async fn test_streams_large_requests_in_chunks() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut received = Vec::new();
+            let mut buffer = [0u8; 65536];
+            while !received.ends_with(b"\r\n0\r\n\r\n") {
+                let read = stream.read(&mut buffer).unwrap();
+                received.extend_from_slice(&buffer[..read]);
+            }
+            stream.write_all(SOLVED.as_bytes()).unwrap();
+
+            let end = received.windows(4).position(|w| w == b"\r\n\r\n").unwrap();
+            let head = String::from_utf8_lossy(&received[..end]).to_lowercase();
+            // Undo the chunked transfer encoding
+            let (mut rest, mut body, mut chunks) = (&received[end + 4..], Vec::new(), 0);
+            loop {
+                let line = rest.windows(2).position(|w| w == b"\r\n").unwrap();
+                let size = usize::from_str_radix(std::str::from_utf8(&rest[..line]).unwrap(), 16).unwrap();
+                if size == 0 {
+                    break;
+                }
+                body.extend_from_slice(&rest[line + 2..line + 2 + size]);
+                rest = &rest[line + 4 + size..];
+                chunks += 1;
+            }
+            (head, body, chunks)
+        });
+        let client = GlpkClient::new(url).unwrap();
+        let request = SolveRequestBuilder::new()
+            .add_variables_with_bounds("x", 20_000, 0, 1)
+            .add_objective([("x1".to_string(), 1.0)].into())
+            .direction(SolverDirection::Maximize)
+            .build()
+            .unwrap();
+
+        assert!(client.solve_streamed(request).await.is_ok());
+
+        let (head, body, chunks) = server.join().unwrap();
+        assert!(head.contains("transfer-encoding: chunked"));
+        assert!(chunks > 1);
+        let sent: SolveRequest = serde_json::from_slice(&body).unwrap();
+        assert_eq!(sent.polyhedron.variables.len(), 20_000);
+    }
+
+    #[tokio::test]
+    async fn test_solve_one_needs_one_solution() {
+        const ONE: &str = "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 71\r\nConnection: close\r\n\r\n{\"solutions\":[{\"status\":\"Optimal\",\"objective\":1.0,\"solution\":{\"x\":1}}]}";
+        let client = GlpkClient::new(serve(vec![ONE, SOLVED])).unwrap();
+        let request = SolveRequestBuilder::new()
+            .add_variable(Variable::new("x", 0, 1))
+            .add_objective([("x".to_string(), 1.0)].into())
+            .direction(SolverDirection::Maximize)
+            .build()
+            .unwrap();
+
+        let solution = client.solve_one(request.clone()).await.unwrap();
+        assert_eq!(solution.value("x"), Some(1));
+        assert!(matches!(
+            client.solve_one(request.clone()).await,
+            Err(GlpkError::ParseError(_))
+        ));
+
+        let mut two = request;
+        two.objectives.push(two.objectives[0].clone());
+        assert!(matches!(
+            client.solve_one(two).await,
+            Err(GlpkError::InvalidRequest(_))
+        ));
+    }
 }
@@ -18,6 +18,27 @@ pub enum GlpkError {
     #[error("API error: {0}")]
     ApiError(String),
 
+    /// The server rejected the request as invalid (HTTP 422)
+    ///
+    /// `code` and `field` are filled in when the server sends them.
+    #[error("Validation failed: {message}")]
+    Validation {
+        /// Machine-readable reason, if sent
+        code: Option<String>,
+        /// Request field the problem is in, if sent
+        field: Option<String>,
+        /// What is wrong with the request
+        message: String,
+    },
+
+    /// The server is rate limiting this client (HTTP 429), and retries, if
+    /// any, ran out
+    #[error("Rate limited by the server")]
+    RateLimited {
+        /// How long the server asked to wait, from `Retry-After`
+        retry_after: Option<std::time::Duration>,
+    },
+
     /// Failed to parse response
     #[error("Failed to parse response: {0}")]
     ParseError(String),
@@ -26,6 +47,18 @@ pub enum GlpkError {
     #[error("Invalid request: {0}")]
     InvalidRequest(String),
 
+    /// A model file could not be read
+    #[error("Invalid model file: {0}")]
+    ModelFormat(String),
+
+    /// A file could not be read or written
+    #[error("File error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The request was cancelled before the server answered
+    #[error("Request cancelled")]
+    Cancelled,
+
     /// Authentication failed
     #[error("Authentication failed")]
     AuthenticationFailed,
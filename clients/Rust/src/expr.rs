@@ -0,0 +1,278 @@
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// Handle to a variable of a [`SolveRequestBuilder`](crate::SolveRequestBuilder),
+/// from [`SolveRequestBuilder::var`](crate::SolveRequestBuilder::var)
+///
+/// Handles combine into [`LinExpr`]s with `+`, `-` and `*`:
+///
+/// ```
+/// use glpk_api_sdk::{SolveRequestBuilder, Variable};
+///
+/// let builder = SolveRequestBuilder::new()
+///     .add_variable(Variable::new("x", 0, 10))
+///     .add_variable(Variable::new("y", 0, 10));
+/// let (x, y) = (builder.var("x").unwrap(), builder.var("y").unwrap());
+///
+/// let expr = 2 * x + 3 * y - 1;
+/// assert_eq!(expr.constant(), -1);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Var {
+    pub(crate) col: usize,
+}
+
+impl Var {
+    /// Column of the variable in the constraint matrix
+    pub fn col(&self) -> usize {
+        self.col
+    }
+}
+
+/// Variable added with [`SolveRequestBuilder::add_var`](crate::SolveRequestBuilder::add_var):
+/// its column in the constraint matrix together with its id
+///
+/// References combine into [`LinExpr`]s like [`Var`]s do, and name the
+/// variable in [`add_objective_terms`](crate::SolveRequestBuilder::add_objective_terms).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VarRef {
+    pub(crate) var: Var,
+    pub(crate) id: String,
+}
+
+impl VarRef {
+    /// Copyable handle to the variable
+    pub fn var(&self) -> Var {
+        self.var
+    }
+
+    /// Column of the variable in the constraint matrix
+    pub fn col(&self) -> usize {
+        self.var.col
+    }
+
+    /// Id of the variable
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+/// How a constraint's expression relates to its right-hand side
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sense {
+    /// `expr ≤ rhs`
+    Le,
+    /// `expr ≥ rhs`, sent as `-expr ≤ -rhs`
+    Ge,
+    /// `expr = rhs`, sent as both `expr ≤ rhs` and `-expr ≤ -rhs`
+    Eq,
+}
+
+/// Linear expression with integer coefficients, plus a constant
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LinExpr {
+    terms: Vec<(usize, i32)>,
+    constant: i32,
+}
+
+impl LinExpr {
+    /// The empty expression, `0`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Coefficient per column, in order of first use, with repeated
+    /// columns summed and zero coefficients dropped
+    pub fn terms(&self) -> Vec<(usize, i32)> {
+        let mut merged: Vec<(usize, i32)> = Vec::with_capacity(self.terms.len());
+        for &(col, coeff) in &self.terms {
+            match merged.iter_mut().find(|(c, _)| *c == col) {
+                Some((_, total)) => *total += coeff,
+                None => merged.push((col, coeff)),
+            }
+        }
+        merged.retain(|&(_, coeff)| coeff != 0);
+        merged
+    }
+
+    /// Constant part of the expression
+    pub fn constant(&self) -> i32 {
+        self.constant
+    }
+
+    fn scaled(mut self, factor: i32) -> Self {
+        for (_, coeff) in &mut self.terms {
+            *coeff *= factor;
+        }
+        self.constant *= factor;
+        self
+    }
+}
+
+impl From<Var> for LinExpr {
+    fn from(var: Var) -> Self {
+        LinExpr {
+            terms: vec![(var.col, 1)],
+            constant: 0,
+        }
+    }
+}
+
+impl From<&VarRef> for LinExpr {
+    fn from(var: &VarRef) -> Self {
+        var.var.into()
+    }
+}
+
+impl From<i32> for LinExpr {
+    fn from(constant: i32) -> Self {
+        LinExpr {
+            terms: Vec::new(),
+            constant,
+        }
+    }
+}
+
+impl<T: Into<LinExpr>> Add<T> for LinExpr {
+    type Output = LinExpr;
+
+    fn add(mut self, rhs: T) -> LinExpr {
+        let rhs = rhs.into();
+        self.terms.extend(rhs.terms);
+        self.constant += rhs.constant;
+        self
+    }
+}
+
+impl<T: Into<LinExpr>> Sub<T> for LinExpr {
+    type Output = LinExpr;
+
+    fn sub(self, rhs: T) -> LinExpr {
+        self + rhs.into().scaled(-1)
+    }
+}
+
+impl Neg for LinExpr {
+    type Output = LinExpr;
+
+    fn neg(self) -> LinExpr {
+        self.scaled(-1)
+    }
+}
+
+impl Mul<i32> for LinExpr {
+    type Output = LinExpr;
+
+    fn mul(self, factor: i32) -> LinExpr {
+        self.scaled(factor)
+    }
+}
+
+impl Mul<LinExpr> for i32 {
+    type Output = LinExpr;
+
+    fn mul(self, expr: LinExpr) -> LinExpr {
+        expr.scaled(self)
+    }
+}
+
+impl<T: Into<LinExpr>> Add<T> for Var {
+    type Output = LinExpr;
+
+    fn add(self, rhs: T) -> LinExpr {
+        LinExpr::from(self) + rhs
+    }
+}
+
+impl<T: Into<LinExpr>> Sub<T> for Var {
+    type Output = LinExpr;
+
+    fn sub(self, rhs: T) -> LinExpr {
+        LinExpr::from(self) - rhs
+    }
+}
+
+impl Neg for Var {
+    type Output = LinExpr;
+
+    fn neg(self) -> LinExpr {
+        -LinExpr::from(self)
+    }
+}
+
+impl Mul<i32> for Var {
+    type Output = LinExpr;
+
+    fn mul(self, factor: i32) -> LinExpr {
+        LinExpr::from(self) * factor
+    }
+}
+
+impl Mul<Var> for i32 {
+    type Output = LinExpr;
+
+    fn mul(self, var: Var) -> LinExpr {
+        LinExpr::from(var) * self
+    }
+}
+
+impl<T: Into<LinExpr>> Add<T> for &VarRef {
+    type Output = LinExpr;
+
+    fn add(self, rhs: T) -> LinExpr {
+        self.var + rhs
+    }
+}
+
+impl<T: Into<LinExpr>> Sub<T> for &VarRef {
+    type Output = LinExpr;
+
+    fn sub(self, rhs: T) -> LinExpr {
+        self.var - rhs
+    }
+}
+
+impl Neg for &VarRef {
+    type Output = LinExpr;
+
+    fn neg(self) -> LinExpr {
+        -self.var
+    }
+}
+
+impl Mul<i32> for &VarRef {
+    type Output = LinExpr;
+
+    fn mul(self, factor: i32) -> LinExpr {
+        self.var * factor
+    }
+}
+
+impl Mul<&VarRef> for i32 {
+    type Output = LinExpr;
+
+    fn mul(self, var: &VarRef) -> LinExpr {
+        self * var.var
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const X: Var = Var { col: 0 };
+    const Y: Var = Var { col: 1 };
+
+    #[test]
+    fn test_combines_terms() {
+        let expr = 2 * X + 3 * Y - X + 4;
+        assert_eq!(expr.terms(), vec![(0, 1), (1, 3)]);
+        assert_eq!(expr.constant(), 4);
+    }
+
+    #[test]
+    fn test_drops_cancelled_terms() {
+        let expr = X + Y - (Y * 1) - 2 * (X - 5);
+        assert_eq!(expr.terms(), vec![(0, -1)]);
+        assert_eq!(expr.constant(), 10);
+    }
+}
@@ -0,0 +1,147 @@
+//! Reading and writing LP and MPS files, behind the `formats` feature
+//!
+//! The parsers are shared with the server through the `glpk-api-formats`
+//! crate; this module converts its models to and from [`SolveRequest`].
+//! The API only solves integer problems with `Ax ≤ b` rows, so every column
+//! must be declared integer and `≥`/`=` constraints are rewritten as `≤` rows.
+
+use crate::error::{GlpkError, Result};
+use crate::types::{
+    IntegerSparseMatrix, Shape, SolveRequest, SolverDirection, SparseLEIntegerPolyhedron,
+    Variable,
+};
+use glpk_api_formats::{Direction, FormatError, ImportedModel, Model, Polyhedron, SparseMatrix};
+use std::collections::HashMap;
+
+impl From<FormatError> for GlpkError {
+    fn from(error: FormatError) -> Self {
+        GlpkError::ModelFormat(error.details)
+    }
+}
+
+pub(crate) fn read_lp(input: &str) -> Result<SolveRequest> {
+    to_request(glpk_api_formats::read_lp(input)?)
+}
+
+pub(crate) fn read_mps(input: &str) -> Result<SolveRequest> {
+    to_request(glpk_api_formats::read_mps(input)?)
+}
+
+pub(crate) fn write_lp(request: &SolveRequest) -> Result<String> {
+    Ok(glpk_api_formats::write_lp(&to_model(request))?)
+}
+
+pub(crate) fn write_mps(request: &SolveRequest) -> Result<String> {
+    Ok(glpk_api_formats::write_mps(&to_model(request))?)
+}
+
+/// Convert a model read from a file to a request
+///
+/// Fails on continuous columns, which the API would solve as integers.
+fn to_request(imported: ImportedModel) -> Result<SolveRequest> {
+    if !imported.continuous.is_empty() {
+        return Err(GlpkError::ModelFormat(format!(
+            "continuous variables {} can't be solved, the API only solves integer problems; \
+             declare them integer",
+            imported.continuous.join(", ")
+        )));
+    }
+
+    let model = imported.model;
+    let polyhedron = model.polyhedron;
+    Ok(SolveRequest {
+        polyhedron: SparseLEIntegerPolyhedron {
+            a: IntegerSparseMatrix {
+                rows: polyhedron.a.rows,
+                cols: polyhedron.a.cols,
+                vals: polyhedron.a.vals,
+                shape: Shape {
+                    nrows: polyhedron.a.shape.nrows,
+                    ncols: polyhedron.a.shape.ncols,
+                },
+            },
+            b: polyhedron.b,
+            variables: polyhedron
+                .variables
+                .into_iter()
+                .map(|v| Variable::new(v.id, v.bound.0, v.bound.1))
+                .collect(),
+        },
+        objectives: model.objectives,
+        direction: match model.direction {
+            Direction::Maximize => SolverDirection::Maximize,
+            Direction::Minimize => SolverDirection::Minimize,
+        },
+        solver: None,
+        time_limit_seconds: None,
+        mip_rel_gap: None,
+        solver_options: HashMap::new(),
+    })
+}
+
+/// The parts of a request that LP and MPS files hold
+fn to_model(request: &SolveRequest) -> Model {
+    let polyhedron = &request.polyhedron;
+    Model {
+        polyhedron: Polyhedron {
+            a: SparseMatrix {
+                rows: polyhedron.a.rows.clone(),
+                cols: polyhedron.a.cols.clone(),
+                vals: polyhedron.a.vals.clone(),
+                shape: glpk_api_formats::Shape {
+                    nrows: polyhedron.a.shape.nrows,
+                    ncols: polyhedron.a.shape.ncols,
+                },
+            },
+            b: polyhedron.b.clone(),
+            variables: polyhedron
+                .variables
+                .iter()
+                .map(|v| glpk_api_formats::Variable {
+                    id: v.id.clone(),
+                    bound: v.bound,
+                })
+                .collect(),
+        },
+        objectives: request.objectives.clone(),
+        direction: match request.direction {
+            SolverDirection::Maximize => Direction::Maximize,
+            SolverDirection::Minimize => Direction::Minimize,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_lp_rejects_continuous_variables() {
+        let error = read_lp("Minimize\n obj: - x - y\nSubject To\n x + y <= 5\nGeneral\n y\nEnd\n")
+            .unwrap_err();
+        assert!(matches!(error, GlpkError::ModelFormat(details)
+            if details.starts_with("continuous variables x can't be solved")));
+    }
+
+    #[test]
+    fn test_read_mps_rejects_continuous_variables() {
+        let input = "ROWS\n N obj\nCOLUMNS\n x obj 1\n y obj 1\nBOUNDS\n BV BND y\nENDATA\n";
+        let error = read_mps(input).unwrap_err();
+        assert!(matches!(error, GlpkError::ModelFormat(details)
+            if details.starts_with("continuous variables x can't be solved")));
+    }
+
+    #[test]
+    fn test_write_lp_round_trips_requests() {
+        let input = "Maximize\n obj: x + 2 y\nSubject To\n c1: x + y <= 4\n c2: x - y >= -2\n\
+                     Bounds\n x <= 3\nGeneral\n x y\nEnd\n";
+        let request = read_lp(input).unwrap();
+        let read_back = read_lp(&write_lp(&request).unwrap()).unwrap();
+
+        assert_eq!(read_back.direction, SolverDirection::Maximize);
+        assert_eq!(read_back.polyhedron.b, vec![4, 2]);
+        assert_eq!(read_back.polyhedron.a.vals, request.polyhedron.a.vals);
+        assert_eq!(read_back.polyhedron.variables[0].bound, (0, 3));
+        assert_eq!(read_back.objectives, request.objectives);
+    }
+}
@@ -0,0 +1,72 @@
+use reqwest::{Request, Response};
+use std::fmt;
+use std::sync::Arc;
+
+/// Hook into every request a [`GlpkClient`](crate::GlpkClient) sends
+///
+/// `on_request` sees each attempt just before it is sent and can change it,
+/// e.g. to add tracing or auth headers. `on_response` sees every response,
+/// including ones that are retried. Both do nothing by default.
+///
+/// The hooks are synchronous: a token that needs refreshing should be
+/// refreshed elsewhere and read from shared state here.
+///
+/// # Example
+///
+/// ```no_run
+/// use glpk_api_sdk::{GlpkClient, Interceptor};
+/// use reqwest::{Request, Response};
+///
+/// struct RequestId;
+///
+/// impl Interceptor for RequestId {
+///     fn on_request(&self, request: &mut Request) {
+///         request
+///             .headers_mut()
+///             .insert("X-Request-Id", "3f2a".parse().unwrap());
+///     }
+///
+///     fn on_response(&self, response: &Response) {
+///         println!("{} {}", response.status(), response.url());
+///     }
+/// }
+///
+/// let client = GlpkClient::new("http://localhost:9000")
+///     .unwrap()
+///     .with_interceptor(RequestId);
+/// ```
+pub trait Interceptor: Send + Sync {
+    /// Called with each request just before it is sent
+    fn on_request(&self, _request: &mut Request) {}
+
+    /// Called with each response as soon as its headers arrive
+    fn on_response(&self, _response: &Response) {}
+}
+
+/// Interceptors registered on a client, in the order they run
+#[derive(Clone, Default)]
+pub(crate) struct Interceptors(Vec<Arc<dyn Interceptor>>);
+
+impl Interceptors {
+    pub(crate) fn push(&mut self, interceptor: impl Interceptor + 'static) {
+        self.0.push(Arc::new(interceptor));
+    }
+
+    pub(crate) fn on_request(&self, request: &mut Request) {
+        for interceptor in &self.0 {
+            interceptor.on_request(request);
+        }
+    }
+
+    pub(crate) fn on_response(&self, response: &Response) {
+        for interceptor in &self.0 {
+            interceptor.on_response(response);
+        }
+    }
+}
+
+impl fmt::Debug for Interceptors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Interceptors({})", self.0.len())
+    }
+}
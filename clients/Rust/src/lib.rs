@@ -32,11 +32,37 @@ pub mod types;
 pub mod client;
 pub mod builder;
 pub mod error;
+pub mod expr;
+pub mod interceptor;
+pub mod metrics;
+pub mod model;
+#[cfg(feature = "formats")]
+mod formats;
+#[cfg(feature = "local")]
+pub mod local;
+#[cfg(feature = "mock")]
+pub mod mock;
+pub mod retry;
 
-pub use client::GlpkClient;
+pub use client::{GlpkApi, GlpkClient, GlpkClientBuilder, MaybeSend};
 pub use types::{
     SolveRequest, SolveResponse, Variable, IntegerSparseMatrix, Shape,
-    SparseLEIntegerPolyhedron, SolverDirection, Solution, Status,
+    SparseLEIntegerPolyhedron, SolverBackend, SolverDirection, SolverOption, Solution, Status,
 };
 pub use builder::SolveRequestBuilder;
+pub use interceptor::Interceptor;
+pub use metrics::RequestMetrics;
+pub use model::SolveModel;
+#[cfg(feature = "derive")]
+pub use glpk_api_sdk_derive::SolveModel;
+pub use expr::{LinExpr, Sense, Var, VarRef};
 pub use error::{GlpkError, Result};
+#[cfg(feature = "mock")]
+pub use mock::MockGlpkClient;
+pub use retry::RetryPolicy;
+pub use tokio_util::sync::CancellationToken;
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    any(feature = "native-tls", feature = "rustls")
+))]
+pub use reqwest::Certificate;
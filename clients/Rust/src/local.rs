@@ -0,0 +1,187 @@
+use crate::error::{GlpkError, Result};
+use crate::types::{SolveRequest, SolveResponse, Solution, SolverDirection, Status};
+use microlp::{ComparisonOp, OptimizationDirection, Problem};
+use std::collections::HashMap;
+
+/// Solve a request in-process with the pure Rust microlp solver, the way the
+/// server's `microlp` backend does
+///
+/// Meant for small problems during offline development and in tests: it is
+/// much slower than the server's native backends, and ignores the time
+/// limit, gap and backend settings of the request. Needs the `local`
+/// feature.
+///
+/// # Example
+///
+/// ```
+/// use glpk_api_sdk::{local, SolveRequestBuilder, SolverDirection, Status, Variable};
+///
+/// // x + y <= 3, maximize 2x + y
+/// let request = SolveRequestBuilder::new()
+///     .add_variable(Variable::new("x", 0, 2))
+///     .add_variable(Variable::new("y", 0, 2))
+///     .add_constraint(vec![0, 0], vec![0, 1], vec![1, 1], 3)
+///     .add_objective([("x".to_string(), 2.0), ("y".to_string(), 1.0)].into())
+///     .direction(SolverDirection::Maximize)
+///     .build()
+///     .unwrap();
+///
+/// let response = local::solve(&request).unwrap();
+/// assert_eq!(response.solutions[0].status, Status::Optimal);
+/// assert_eq!(response.solutions[0].objective, 5.0);
+/// ```
+pub fn solve(request: &SolveRequest) -> Result<SolveResponse> {
+    let polyhedron = &request.polyhedron;
+    let variables = &polyhedron.variables;
+
+    for objective in &request.objectives {
+        if let Some(missing) = objective
+            .keys()
+            .find(|id| !variables.iter().any(|v| &v.id == *id))
+        {
+            return Err(GlpkError::InvalidRequest(format!(
+                "Objective contains missing variable {}",
+                missing
+            )));
+        }
+    }
+
+    // Group the sparse matrix by row once, shared by all objectives
+    let mut rows: Vec<Vec<(usize, f64)>> = vec![Vec::new(); polyhedron.b.len()];
+    for ((&row, &col), &val) in polyhedron
+        .a
+        .rows
+        .iter()
+        .zip(&polyhedron.a.cols)
+        .zip(&polyhedron.a.vals)
+    {
+        if row < 0 || row as usize >= rows.len() || col < 0 || col as usize >= variables.len() {
+            return Err(GlpkError::InvalidRequest(format!(
+                "Matrix entry ({}, {}) is outside the {}x{} constraints",
+                row,
+                col,
+                rows.len(),
+                variables.len()
+            )));
+        }
+        rows[row as usize].push((col as usize, val as f64));
+    }
+
+    let direction = match request.direction {
+        SolverDirection::Maximize => OptimizationDirection::Maximize,
+        SolverDirection::Minimize => OptimizationDirection::Minimize,
+    };
+
+    let solutions = request
+        .objectives
+        .iter()
+        .map(|objective| solve_objective(request, &rows, objective, direction))
+        .collect();
+    Ok(SolveResponse {
+        solutions,
+        request_id: None,
+    })
+}
+
+fn solve_objective(
+    request: &SolveRequest,
+    rows: &[Vec<(usize, f64)>],
+    objective: &HashMap<String, f64>,
+    direction: OptimizationDirection,
+) -> Solution {
+    let polyhedron = &request.polyhedron;
+    let mut problem = Problem::new(direction);
+    let columns: Vec<_> = polyhedron
+        .variables
+        .iter()
+        .map(|var| {
+            let obj_coeff = objective.get(&var.id).copied().unwrap_or(0.0);
+            problem.add_integer_var(obj_coeff, var.bound)
+        })
+        .collect();
+
+    for (row, &rhs) in rows.iter().zip(&polyhedron.b) {
+        let terms: Vec<_> = row
+            .iter()
+            .map(|&(col, coeff)| (columns[col], coeff))
+            .collect();
+        problem.add_constraint(terms, ComparisonOp::Le, rhs as f64);
+    }
+
+    let solution = match problem.solve() {
+        Ok(solution) => solution,
+        Err(microlp::Error::Infeasible) => return empty_solution(Status::Infeasible, None),
+        Err(microlp::Error::Unbounded) => return empty_solution(Status::Unbounded, None),
+        Err(microlp::Error::InternalError(e)) => {
+            return empty_solution(
+                Status::Undefined,
+                Some(format!("microlp solve failed: {}", e)),
+            )
+        }
+    };
+
+    Solution {
+        status: Status::Optimal,
+        objective: solution.objective(),
+        solution: polyhedron
+            .variables
+            .iter()
+            .zip(&columns)
+            .map(|(var, &column)| (var.id.clone(), solution.var_value(column).round() as i64))
+            .collect(),
+        error: None,
+    }
+}
+
+fn empty_solution(status: Status, error: Option<String>) -> Solution {
+    Solution {
+        status,
+        objective: 0.0,
+        solution: HashMap::new(),
+        error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SolveRequestBuilder, Variable};
+
+    fn request(b: i32) -> SolveRequest {
+        // x + 2y <= b, y <= 5
+        SolveRequestBuilder::new()
+            .add_variable(Variable::new("x", 0, 10))
+            .add_variable(Variable::new("y", 0, 10))
+            .add_constraint(vec![0, 0], vec![0, 1], vec![1, 2], b)
+            .add_constraint(vec![1], vec![1], vec![1], 5)
+            .add_objective([("x".to_string(), 1.0), ("y".to_string(), 3.0)].into())
+            .add_objective([("x".to_string(), 1.0)].into())
+            .direction(SolverDirection::Maximize)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_solves_each_objective() {
+        let response = solve(&request(10)).unwrap();
+
+        // x + 3y is best at x=0, y=5, x alone at x=10, y=0
+        assert_eq!(response.solutions[0].objective, 15.0);
+        assert_eq!(response.solutions[0].value("y"), Some(5));
+        assert_eq!(response.solutions[1].objective, 10.0);
+        assert_eq!(response.solutions[1].value("x"), Some(10));
+    }
+
+    #[test]
+    fn test_reports_infeasible_problems() {
+        let response = solve(&request(-1)).unwrap();
+        assert_eq!(response.solutions[0].status, Status::Infeasible);
+    }
+
+    #[test]
+    fn test_rejects_unknown_objective_variables() {
+        let mut request = request(10);
+        request.objectives[0].insert("z".to_string(), 1.0);
+        assert!(matches!(solve(&request), Err(GlpkError::InvalidRequest(_))));
+    }
+}
@@ -0,0 +1,44 @@
+use reqwest::StatusCode;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Measurements of one client call, passed to the callback set with
+/// [`GlpkClient::with_metrics`](crate::GlpkClient::with_metrics)
+#[derive(Debug, Clone)]
+pub struct RequestMetrics {
+    /// Path of the endpoint, e.g. `/solve`
+    pub path: String,
+    /// Id sent in the `x-request-id` header, the same for every attempt
+    pub request_id: String,
+    /// Time from sending the first attempt until the last response's
+    /// headers arrived or the last attempt failed, retry delays included
+    pub duration: Duration,
+    /// Size of the request body as sent, after any compression; 0 for
+    /// bodies streamed by [`solve_streamed`](crate::GlpkClient::solve_streamed)
+    pub request_bytes: usize,
+    /// Status of the last response, `None` if no response arrived
+    pub status: Option<StatusCode>,
+    /// Attempts made, more than one when requests were retried
+    pub attempts: u32,
+}
+
+/// Callback receiving [`RequestMetrics`]
+#[derive(Clone)]
+pub(crate) struct MetricsHook(Arc<dyn Fn(&RequestMetrics) + Send + Sync>);
+
+impl MetricsHook {
+    pub(crate) fn new(callback: impl Fn(&RequestMetrics) + Send + Sync + 'static) -> Self {
+        MetricsHook(Arc::new(callback))
+    }
+
+    pub(crate) fn record(&self, metrics: &RequestMetrics) {
+        (self.0)(metrics)
+    }
+}
+
+impl fmt::Debug for MetricsHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("MetricsHook")
+    }
+}
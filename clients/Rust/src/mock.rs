@@ -0,0 +1,136 @@
+use crate::client::{GlpkApi, MaybeSend};
+use crate::error::{GlpkError, Result};
+use crate::types::{SolveRequest, SolveResponse};
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::Mutex;
+
+/// Stand-in for [`GlpkClient`](crate::GlpkClient) that answers with canned
+/// responses, for testing code written against [`GlpkApi`]
+///
+/// Each `solve` call takes the next queued response or error, in order, and
+/// records the request it was given. Needs the `mock` feature.
+///
+/// # Example
+///
+/// ```
+/// use glpk_api_sdk::{GlpkApi, MockGlpkClient, SolveResponse, SolveRequestBuilder, SolverDirection, Variable};
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let mock = MockGlpkClient::new().respond_with(SolveResponse {
+///     solutions: vec![],
+///     request_id: None,
+/// });
+/// let request = SolveRequestBuilder::new()
+///     .add_variable(Variable::new("x", 0, 1))
+///     .add_objective([("x".to_string(), 1.0)].into())
+///     .direction(SolverDirection::Maximize)
+///     .build()
+///     .unwrap();
+///
+/// let response = mock.solve(request).await.unwrap();
+/// assert!(response.solutions.is_empty());
+/// assert_eq!(mock.requests().len(), 1);
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct MockGlpkClient {
+    responses: Mutex<VecDeque<Result<SolveResponse>>>,
+    requests: Mutex<Vec<SolveRequest>>,
+    healthy: bool,
+}
+
+impl MockGlpkClient {
+    /// A healthy mock with no responses queued
+    pub fn new() -> Self {
+        Self {
+            healthy: true,
+            ..Self::default()
+        }
+    }
+
+    /// Queue a response for a later `solve` call
+    pub fn respond_with(self, response: SolveResponse) -> Self {
+        self.responses.lock().unwrap().push_back(Ok(response));
+        self
+    }
+
+    /// Queue an error for a later `solve` call
+    pub fn fail_with(self, error: GlpkError) -> Self {
+        self.responses.lock().unwrap().push_back(Err(error));
+        self
+    }
+
+    /// Set what `health_check` reports
+    pub fn healthy(mut self, healthy: bool) -> Self {
+        self.healthy = healthy;
+        self
+    }
+
+    /// Requests passed to `solve` so far, in order
+    pub fn requests(&self) -> Vec<SolveRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl GlpkApi for MockGlpkClient {
+    fn health_check(&self) -> impl Future<Output = Result<bool>> + MaybeSend {
+        std::future::ready(Ok(self.healthy))
+    }
+
+    fn solve(
+        &self,
+        request: SolveRequest,
+    ) -> impl Future<Output = Result<SolveResponse>> + MaybeSend {
+        self.requests.lock().unwrap().push(request);
+        let response = self.responses.lock().unwrap().pop_front().unwrap_or_else(|| {
+            Err(GlpkError::ApiError(
+                "MockGlpkClient has no response queued".to_string(),
+            ))
+        });
+        std::future::ready(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SolveRequestBuilder, SolverDirection, Variable};
+
+    fn request() -> SolveRequest {
+        SolveRequestBuilder::new()
+            .add_variable(Variable::new("x", 0, 1))
+            .add_objective([("x".to_string(), 1.0)].into())
+            .direction(SolverDirection::Maximize)
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_answers_in_order() {
+        let mock = MockGlpkClient::new()
+            .respond_with(SolveResponse {
+                solutions: vec![],
+                request_id: None,
+            })
+            .fail_with(GlpkError::AuthenticationFailed);
+
+        assert!(mock.solve(request()).await.is_ok());
+        assert!(matches!(
+            mock.solve(request()).await,
+            Err(GlpkError::AuthenticationFailed)
+        ));
+        assert!(matches!(
+            mock.solve(request()).await,
+            Err(GlpkError::ApiError(_))
+        ));
+        assert_eq!(mock.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_health_check() {
+        assert!(MockGlpkClient::new().health_check().await.unwrap());
+        assert!(!MockGlpkClient::new().healthy(false).health_check().await.unwrap());
+    }
+}
@@ -0,0 +1,84 @@
+use crate::builder::SolveRequestBuilder;
+use crate::types::{Solution, Variable};
+
+/// A domain type whose fields are the variables of a problem, usually
+/// implemented with `#[derive(SolveModel)]` from the `derive` feature
+///
+/// Each field becomes a variable named after it, with the bounds given in
+/// its `#[var(...)]` attribute:
+///
+/// - `#[var(lower = 0, upper = 10)]` sets the bounds
+/// - `#[var(binary)]` is short for `lower = 0, upper = 1`
+/// - `#[var(id = "name")]` sends the variable under another id
+///
+/// Constraints come from the functions named in
+/// `#[solve_model(constraints = ...)]` on the struct. Each takes the
+/// generated `<Name>Vars` struct of variable handles and the builder, and
+/// returns the builder with its constraints added. They are applied in the
+/// order listed.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # {
+/// use glpk_api_sdk::{Sense::Le, SolveModel, SolveRequestBuilder, SolverDirection};
+///
+/// #[derive(SolveModel)]
+/// #[solve_model(constraints = Production::capacity)]
+/// struct Production {
+///     #[var(lower = 0, upper = 10)]
+///     chairs: i32,
+///     #[var(lower = 0, upper = 4)]
+///     tables: i32,
+///     #[var(binary)]
+///     overtime: i32,
+/// }
+///
+/// impl Production {
+///     fn capacity(v: &ProductionVars, builder: SolveRequestBuilder) -> SolveRequestBuilder {
+///         builder.constrain(v.chairs + 3 * v.tables - 5 * v.overtime, Le, 12)
+///     }
+/// }
+///
+/// let request = Production::builder()
+///     .add_objective([("chairs".to_string(), 1.0), ("tables".to_string(), 4.0)].into())
+///     .direction(SolverDirection::Maximize)
+///     .build()
+///     .unwrap();
+/// assert_eq!(request.polyhedron.variables.len(), 3);
+/// assert_eq!(request.polyhedron.b, vec![12]);
+///
+/// # use glpk_api_sdk::{Solution, Status};
+/// # let solution = Solution {
+/// #     status: Status::Optimal,
+/// #     objective: 18.0,
+/// #     solution: [("chairs".to_string(), 2), ("tables".to_string(), 4), ("overtime".to_string(), 0)].into(),
+/// #     error: None,
+/// # };
+/// let plan = Production::from_solution(&solution).unwrap();
+/// assert_eq!((plan.chairs, plan.tables, plan.overtime), (2, 4, 0));
+/// # }
+/// ```
+///
+/// Once solved, [`from_solution`](SolveModel::from_solution) reads the
+/// values back into the struct.
+pub trait SolveModel: Sized {
+    /// Handle to each variable of the model, for writing constraints
+    type Vars;
+
+    /// The model's variables, in field order
+    fn variables() -> Vec<Variable>;
+
+    /// Handles to the variables of a builder from [`builder`](SolveModel::builder)
+    fn vars() -> Self::Vars;
+
+    /// A builder with the model's variables and constraints added, ready
+    /// for objectives and a direction
+    fn builder() -> SolveRequestBuilder;
+
+    /// The model with each field set from its variable's value, or `None`
+    /// if a variable is missing from the solution or its value doesn't fit
+    /// the field's type
+    fn from_solution(solution: &Solution) -> Option<Self>;
+}
@@ -0,0 +1,282 @@
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::StatusCode;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// When and how often the client retries a failed request
+///
+/// Retries are off by default. A request is retried when it could not
+/// connect to the server, or when the server answered with a 5xx status or
+/// 429 Too Many Requests, waiting an exponentially growing delay between
+/// attempts. A `Retry-After` header on the response takes the place of
+/// that delay.
+///
+/// # Example
+///
+/// ```no_run
+/// use glpk_api_sdk::{GlpkClient, RetryPolicy};
+/// use std::time::Duration;
+///
+/// let policy = RetryPolicy::new(4)
+///     .with_backoff(Duration::from_millis(200), Duration::from_secs(5));
+/// let client = GlpkClient::new("http://localhost:9000")
+///     .unwrap()
+///     .with_retry(policy);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first
+    pub max_attempts: u32,
+    /// Delay before the first retry
+    pub initial_backoff: Duration,
+    /// Upper bound on the delay between attempts
+    pub max_backoff: Duration,
+    /// Factor the delay grows by after each retry
+    pub multiplier: f64,
+    /// Wait a random share of each delay instead of all of it, so that
+    /// clients failing together don't retry together
+    pub jitter: bool,
+    /// Retry when the connection to the server could not be made
+    pub retry_on_connect: bool,
+    /// Retry when the server answers with a 5xx status
+    pub retry_on_server_error: bool,
+    /// Retry when the server answers with 429 Too Many Requests
+    pub retry_on_rate_limit: bool,
+    /// Longest `Retry-After` to wait for; asked to wait longer, the client
+    /// returns the response instead
+    pub max_retry_after: Duration,
+}
+
+impl RetryPolicy {
+    /// Retry up to `max_attempts` attempts in total, on connection errors,
+    /// 5xx and 429 responses, backing off from 100ms up to 10s with jitter
+    /// and waiting up to a minute when the server sends `Retry-After`
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+            multiplier: 2.0,
+            jitter: true,
+            retry_on_connect: true,
+            retry_on_server_error: true,
+            retry_on_rate_limit: true,
+            max_retry_after: Duration::from_secs(60),
+        }
+    }
+
+    /// Send each request once
+    pub fn none() -> Self {
+        Self::new(1)
+    }
+
+    /// Set the first delay and the largest delay between attempts
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Set the factor the delay grows by after each retry
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Enable or disable jitter
+    pub fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Enable or disable retrying connection errors
+    pub fn retry_on_connect(mut self, retry: bool) -> Self {
+        self.retry_on_connect = retry;
+        self
+    }
+
+    /// Enable or disable retrying 5xx responses
+    pub fn retry_on_server_error(mut self, retry: bool) -> Self {
+        self.retry_on_server_error = retry;
+        self
+    }
+
+    /// Enable or disable retrying 429 responses
+    pub fn retry_on_rate_limit(mut self, retry: bool) -> Self {
+        self.retry_on_rate_limit = retry;
+        self
+    }
+
+    /// Set the longest `Retry-After` to wait for
+    pub fn with_max_retry_after(mut self, max: Duration) -> Self {
+        self.max_retry_after = max;
+        self
+    }
+
+    /// Delay before retry number `retry`, counting from 1, without jitter
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = self.multiplier.max(1.0).powi(retry.saturating_sub(1) as i32);
+        let delay = self.initial_backoff.as_secs_f64() * factor;
+        Duration::from_secs_f64(delay.min(self.max_backoff.as_secs_f64()))
+    }
+
+    /// Delay to wait before retry number `retry`, with jitter if enabled
+    pub(crate) fn delay(&self, retry: u32) -> Duration {
+        let backoff = self.backoff(retry);
+        if !self.jitter {
+            return backoff;
+        }
+        // "Full jitter": anywhere between no wait and the whole backoff
+        backoff.mul_f64(random_fraction())
+    }
+
+    /// Whether a request that failed to send should be tried again
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn retries_error(&self, error: &reqwest::Error) -> bool {
+        self.retry_on_connect && error.is_connect()
+    }
+
+    /// Whether a request that failed to send should be tried again
+    ///
+    /// The browser doesn't tell connection errors apart from other failed
+    /// fetches, so any of them is retried.
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn retries_error(&self, error: &reqwest::Error) -> bool {
+        self.retry_on_connect && error.is_request()
+    }
+
+    /// Whether a request answered with `status` should be tried again
+    pub(crate) fn retries_status(&self, status: StatusCode) -> bool {
+        (self.retry_on_server_error && status.is_server_error())
+            || (self.retry_on_rate_limit && status == StatusCode::TOO_MANY_REQUESTS)
+    }
+
+    /// Delay to wait before retry number `retry` of a request answered with
+    /// `status`, or `None` if it shouldn't be tried again
+    pub(crate) fn response_delay(
+        &self,
+        status: StatusCode,
+        headers: &HeaderMap,
+        retry: u32,
+    ) -> Option<Duration> {
+        if !self.retries_status(status) {
+            return None;
+        }
+        match retry_after(headers) {
+            Some(wait) if wait > self.max_retry_after => None,
+            Some(wait) => Some(wait),
+            None => Some(self.delay(retry)),
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+/// Wait asked for by a `Retry-After` header
+///
+/// Only the delay in seconds is understood; a header holding an HTTP date
+/// is ignored and the policy's own backoff is used.
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let seconds = headers.get(RETRY_AFTER)?.to_str().ok()?.trim().parse().ok()?;
+    Some(Duration::from_secs(seconds))
+}
+
+/// Wait `delay` before the next attempt
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) async fn sleep(delay: Duration) {
+    tokio::time::sleep(delay).await
+}
+
+/// Wait `delay` before the next attempt, on a browser timer
+#[cfg(target_arch = "wasm32")]
+pub(crate) async fn sleep(delay: Duration) {
+    gloo_timers::future::sleep(delay).await
+}
+
+/// Random number in `[0, 1)`
+fn random_fraction() -> f64 {
+    (random_u64() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Random bits from the standard library's randomly seeded hasher, to
+/// avoid depending on a random number crate
+pub(crate) fn random_u64() -> u64 {
+    RandomState::new().build_hasher().finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_grows_up_to_max() {
+        let policy = RetryPolicy::new(5)
+            .with_backoff(Duration::from_millis(100), Duration::from_millis(350))
+            .with_jitter(false);
+
+        assert_eq!(policy.delay(1), Duration::from_millis(100));
+        assert_eq!(policy.delay(2), Duration::from_millis(200));
+        assert_eq!(policy.delay(3), Duration::from_millis(350));
+        assert_eq!(policy.delay(10), Duration::from_millis(350));
+    }
+
+    #[test]
+    fn test_jitter_stays_within_backoff() {
+        let policy = RetryPolicy::new(3);
+        for retry in 1..=3 {
+            assert!(policy.delay(retry) <= policy.backoff(retry));
+        }
+    }
+
+    #[test]
+    fn test_default_sends_once() {
+        assert_eq!(RetryPolicy::default().max_attempts, 1);
+        assert_eq!(RetryPolicy::new(0).max_attempts, 1);
+    }
+
+    #[test]
+    fn test_retries_server_errors_only() {
+        let policy = RetryPolicy::new(3);
+        assert!(policy.retries_status(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(!policy.retries_status(StatusCode::BAD_REQUEST));
+        assert!(!policy
+            .retry_on_server_error(false)
+            .retries_status(StatusCode::BAD_GATEWAY));
+    }
+
+    #[test]
+    fn test_honors_retry_after() {
+        let policy = RetryPolicy::new(3).with_max_retry_after(Duration::from_secs(30));
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, "7".parse().unwrap());
+
+        assert_eq!(
+            policy.response_delay(StatusCode::TOO_MANY_REQUESTS, &headers, 1),
+            Some(Duration::from_secs(7))
+        );
+
+        headers.insert(RETRY_AFTER, "120".parse().unwrap());
+        assert_eq!(
+            policy.response_delay(StatusCode::TOO_MANY_REQUESTS, &headers, 1),
+            None
+        );
+
+        headers.insert(RETRY_AFTER, "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap());
+        assert!(policy
+            .response_delay(StatusCode::TOO_MANY_REQUESTS, &headers, 1)
+            .unwrap()
+            <= policy.backoff(1));
+
+        assert_eq!(
+            policy
+                .retry_on_rate_limit(false)
+                .response_delay(StatusCode::TOO_MANY_REQUESTS, &HeaderMap::new(), 1),
+            None
+        );
+    }
+}
@@ -1,5 +1,9 @@
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
 
 /// Variable bounds (lower_bound, upper_bound)
 pub type Bound = (i32, i32);
@@ -82,6 +86,68 @@ pub enum SolverDirection {
 /// Objective function as a mapping from variable names to coefficients
 pub type Objective = HashMap<String, f64>;
 
+/// Solver backend the server should use for a request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SolverBackend {
+    /// GLPK
+    Glpk,
+    /// The pure Rust microlp solver
+    Microlp,
+    /// HiGHS
+    Highs,
+    /// Gurobi
+    Gurobi,
+    /// lp_solve
+    LpSolve,
+    /// IBM CPLEX
+    Cplex,
+    /// Race the backends configured on the server and keep the first answer
+    Race,
+}
+
+/// Value of a backend parameter, e.g. `1` for Gurobi's `MIPFocus`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SolverOption {
+    /// Integer parameter
+    Int(i64),
+    /// Floating point parameter
+    Float(f64),
+    /// String parameter
+    Text(String),
+}
+
+impl From<i64> for SolverOption {
+    fn from(value: i64) -> Self {
+        SolverOption::Int(value)
+    }
+}
+
+impl From<i32> for SolverOption {
+    fn from(value: i32) -> Self {
+        SolverOption::Int(value.into())
+    }
+}
+
+impl From<f64> for SolverOption {
+    fn from(value: f64) -> Self {
+        SolverOption::Float(value)
+    }
+}
+
+impl From<&str> for SolverOption {
+    fn from(value: &str) -> Self {
+        SolverOption::Text(value.to_string())
+    }
+}
+
+impl From<String> for SolverOption {
+    fn from(value: String) -> Self {
+        SolverOption::Text(value)
+    }
+}
+
 /// Request to solve one or more linear programming problems
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SolveRequest {
@@ -91,6 +157,92 @@ pub struct SolveRequest {
     pub objectives: Vec<Objective>,
     /// Whether to maximize or minimize
     pub direction: SolverDirection,
+    /// Backend to solve with, the server's default when absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub solver: Option<SolverBackend>,
+    /// Stop after this many seconds and return the best solution found so far
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_limit_seconds: Option<f64>,
+    /// Stop once the relative gap between the best solution and the best
+    /// bound is at most this
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mip_rel_gap: Option<f64>,
+    /// Backend parameters by name
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub solver_options: HashMap<String, SolverOption>,
+}
+
+impl SolveRequest {
+    /// Save the request as JSON, the way it is sent to the server
+    ///
+    /// Useful for keeping a model that reproduces a problem, e.g. to
+    /// attach to a bug report.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use glpk_api_sdk::SolveRequest;
+    /// # fn example(request: SolveRequest) -> glpk_api_sdk::Result<()> {
+    /// request.to_json_file_pretty("reproducer.json")?;
+    /// let reloaded = SolveRequest::from_json_file("reproducer.json")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_json_file(&self, path: impl AsRef<Path>) -> crate::Result<()> {
+        let json = serde_json::to_vec(self)
+            .map_err(|e| crate::GlpkError::InvalidRequest(e.to_string()))?;
+        Ok(fs::write(path, json)?)
+    }
+
+    /// Save the request as indented JSON, for reading and diffing
+    pub fn to_json_file_pretty(&self, path: impl AsRef<Path>) -> crate::Result<()> {
+        let json = serde_json::to_vec_pretty(self)
+            .map_err(|e| crate::GlpkError::InvalidRequest(e.to_string()))?;
+        Ok(fs::write(path, json)?)
+    }
+
+    /// Load a request saved with [`to_json_file`](Self::to_json_file), or
+    /// any file holding a `/solve` request body
+    pub fn from_json_file(path: impl AsRef<Path>) -> crate::Result<Self> {
+        let json = fs::read(path)?;
+        serde_json::from_slice(&json).map_err(|e| crate::GlpkError::ParseError(e.to_string()))
+    }
+}
+
+#[cfg(feature = "formats")]
+impl SolveRequest {
+    /// The request in CPLEX LP format, for opening it in other solvers
+    ///
+    /// Fails when the request has more than one objective or a variable
+    /// name that can't be written unquoted. Needs the `formats` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use glpk_api_sdk::{Sense, SolveRequestBuilder, SolverDirection, Variable};
+    ///
+    /// let builder = SolveRequestBuilder::new().add_variable(Variable::new("x", 0, 3));
+    /// let x = builder.var("x").unwrap();
+    /// let request = builder
+    ///     .constrain(x, Sense::Le, 2)
+    ///     .add_objective([("x".to_string(), 1.0)].into_iter().collect())
+    ///     .direction(SolverDirection::Maximize)
+    ///     .build()
+    ///     .unwrap();
+    /// let lp = request.to_lp_string().unwrap();
+    /// assert!(lp.starts_with("Maximize"));
+    /// ```
+    pub fn to_lp_string(&self) -> crate::Result<String> {
+        crate::formats::write_lp(self)
+    }
+
+    /// The request in free MPS format, for opening it in other solvers
+    ///
+    /// Fails when the request has more than one objective or a variable
+    /// name that can't be written unquoted. Needs the `formats` feature.
+    pub fn to_mps_string(&self) -> crate::Result<String> {
+        crate::formats::write_mps(self)
+    }
 }
 
 /// Solution status codes
@@ -114,6 +266,46 @@ pub enum Status {
     MIPFailed = 8,
     /// Search space is empty
     EmptySpace = 9,
+    /// Stopped at the time limit
+    TimeLimit = 10,
+}
+
+impl Status {
+    /// Whether the solver proved the solution optimal
+    pub fn is_success(&self) -> bool {
+        *self == Status::Optimal
+    }
+
+    /// Whether the solution satisfies all constraints, optimal or not
+    ///
+    /// A solve stopped at the time limit may still hold a feasible
+    /// solution; that status doesn't say, so it isn't counted here.
+    pub fn is_feasible(&self) -> bool {
+        matches!(self, Status::Optimal | Status::Feasible)
+    }
+
+    /// Whether the solver failed instead of answering, as opposed to
+    /// proving the problem infeasible or unbounded
+    pub fn is_error(&self) -> bool {
+        matches!(self, Status::Undefined | Status::SimplexFailed | Status::MIPFailed)
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Status::Undefined => "undefined",
+            Status::Feasible => "feasible",
+            Status::Infeasible => "infeasible",
+            Status::NoFeasible => "no feasible solution",
+            Status::Optimal => "optimal",
+            Status::Unbounded => "unbounded",
+            Status::SimplexFailed => "simplex failed",
+            Status::MIPFailed => "MIP failed",
+            Status::EmptySpace => "empty search space",
+            Status::TimeLimit => "time limit reached",
+        })
+    }
 }
 
 /// A single solution for one objective function
@@ -122,16 +314,220 @@ pub struct Solution {
     /// Solution status
     pub status: Status,
     /// Objective value achieved
-    pub objective: i32,
+    pub objective: f64,
     /// Variable assignments
     pub solution: HashMap<String, i64>,
     /// Error message, if any
     pub error: Option<String>,
 }
 
+impl Solution {
+    /// Whether the solver proved the solution optimal
+    pub fn is_optimal(&self) -> bool {
+        self.status.is_success()
+    }
+
+    /// Whether the solution satisfies all constraints, optimal or not
+    pub fn is_feasible(&self) -> bool {
+        self.status.is_feasible()
+    }
+
+    /// Value of the variable with the given id, if it is in the solution
+    pub fn value(&self, id: &str) -> Option<i64> {
+        self.solution.get(id).copied()
+    }
+
+    /// Variables with a non-zero value, sorted by id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use glpk_api_sdk::{Solution, Status};
+    ///
+    /// let solution = Solution {
+    ///     status: Status::Optimal,
+    ///     objective: 2.0,
+    ///     solution: [("y".to_string(), 1), ("x".to_string(), 0), ("z".to_string(), 1)].into(),
+    ///     error: None,
+    /// };
+    /// assert_eq!(solution.nonzero(), vec![("y", 1), ("z", 1)]);
+    /// ```
+    pub fn nonzero(&self) -> Vec<(&str, i64)> {
+        let mut values: Vec<(&str, i64)> = self
+            .solution
+            .iter()
+            .filter(|(_, value)| **value != 0)
+            .map(|(id, value)| (id.as_str(), *value))
+            .collect();
+        values.sort_unstable();
+        values
+    }
+
+    /// The variable values as a `T`, deserialized from an object mapping
+    /// each id to its value
+    ///
+    /// Fields pick up the variable with the same id; `#[serde(rename)]`
+    /// maps other ids, and `#[serde(default)]` covers variables that may
+    /// be missing, e.g. from an infeasible solution.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use glpk_api_sdk::{Solution, Status};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Order {
+    ///     #[serde(rename = "qty_small")]
+    ///     small: u32,
+    ///     #[serde(rename = "qty_large")]
+    ///     large: u32,
+    /// }
+    ///
+    /// let solution = Solution {
+    ///     status: Status::Optimal,
+    ///     objective: 7.0,
+    ///     solution: [("qty_small".to_string(), 3), ("qty_large".to_string(), 2)].into(),
+    ///     error: None,
+    /// };
+    /// let order: Order = solution.parse_into().unwrap();
+    /// assert_eq!((order.small, order.large), (3, 2));
+    /// ```
+    pub fn parse_into<T: DeserializeOwned>(&self) -> crate::Result<T> {
+        let values = self
+            .solution
+            .iter()
+            .map(|(id, value)| (id.clone(), serde_json::Value::from(*value)))
+            .collect();
+        serde_json::from_value(serde_json::Value::Object(values))
+            .map_err(|e| crate::GlpkError::ParseError(e.to_string()))
+    }
+}
+
 /// Response from the solve endpoint
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SolveResponse {
     /// One solution per objective function
     pub solutions: Vec<Solution>,
+    /// Id sent in the `x-request-id` header of the call that got this
+    /// response, for finding the call in the server's logs
+    #[serde(skip)]
+    pub request_id: Option<String>,
+}
+
+impl SolveResponse {
+    /// The first optimal solution, or failing that the first feasible one
+    pub fn best(&self) -> Option<&Solution> {
+        self.solutions
+            .iter()
+            .find(|solution| solution.is_optimal())
+            .or_else(|| self.solutions.iter().find(|solution| solution.is_feasible()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solution(status: Status, objective: f64) -> Solution {
+        Solution {
+            status,
+            objective,
+            solution: [("x".to_string(), 3), ("y".to_string(), 0)].into(),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_solution_accessors() {
+        let solution = solution(Status::Feasible, 3.0);
+        assert!(!solution.is_optimal());
+        assert!(solution.is_feasible());
+        assert_eq!(solution.value("x"), Some(3));
+        assert_eq!(solution.value("missing"), None);
+        assert_eq!(solution.nonzero(), vec![("x", 3)]);
+    }
+
+    #[test]
+    fn test_json_file_round_trip() {
+        let request = crate::SolveRequestBuilder::new()
+            .add_variable(crate::Variable::new("x", 0, 3))
+            .add_constraint(vec![0], vec![0], vec![1], 2)
+            .add_objective([("x".to_string(), 1.0)].into())
+            .direction(SolverDirection::Maximize)
+            .build()
+            .unwrap();
+        let path = std::env::temp_dir().join(format!("glpk-sdk-{}.json", std::process::id()));
+
+        request.to_json_file_pretty(&path).unwrap();
+        let reloaded = SolveRequest::from_json_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(reloaded.polyhedron.b, vec![2]);
+        assert_eq!(reloaded.polyhedron.variables[0].id, "x");
+        assert_eq!(reloaded.direction, SolverDirection::Maximize);
+        assert!(matches!(
+            SolveRequest::from_json_file(&path),
+            Err(crate::GlpkError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_into() {
+        #[derive(Deserialize)]
+        struct Values {
+            x: u8,
+            #[serde(default)]
+            missing: i64,
+        }
+
+        let values: Values = solution(Status::Optimal, 3.0).parse_into().unwrap();
+        assert_eq!((values.x, values.missing), (3, 0));
+
+        let mut negative = solution(Status::Optimal, 3.0);
+        negative.solution.insert("x".to_string(), -1);
+        assert!(matches!(
+            negative.parse_into::<Values>(),
+            Err(crate::GlpkError::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_status_helpers() {
+        assert!(Status::Optimal.is_success());
+        assert!(!Status::Feasible.is_success());
+        assert!(Status::Feasible.is_feasible());
+        assert!(!Status::TimeLimit.is_feasible());
+        assert!(Status::MIPFailed.is_error());
+        assert!(!Status::Infeasible.is_error());
+        assert_eq!(Status::NoFeasible.to_string(), "no feasible solution");
+    }
+
+    #[test]
+    fn test_best_prefers_optimal_solutions() {
+        let response = SolveResponse {
+            solutions: vec![
+                solution(Status::Infeasible, 0.0),
+                solution(Status::Feasible, 1.0),
+                solution(Status::Optimal, 2.0),
+            ],
+            request_id: None,
+        };
+        assert_eq!(response.best().unwrap().objective, 2.0);
+
+        let response = SolveResponse {
+            solutions: vec![
+                solution(Status::Infeasible, 0.0),
+                solution(Status::Feasible, 1.0),
+            ],
+            request_id: None,
+        };
+        assert_eq!(response.best().unwrap().objective, 1.0);
+
+        let response = SolveResponse {
+            solutions: vec![solution(Status::Infeasible, 0.0)],
+            request_id: None,
+        };
+        assert!(response.best().is_none());
+    }
 }
@@ -0,0 +1,301 @@
+//! Reading and writing LP and MPS files for the GLPK REST API
+//!
+//! Shared by the server's CLI and the client SDK. Models are read into
+//! [`Model`], which mirrors the `/solve` request schema: integer `Ax <= b`
+//! rows, so `>=` and `=` constraints are rewritten as one or two `<=` rows.
+//! Each side converts [`Model`] to and from its own request type.
+
+mod lp;
+mod mps;
+
+pub use lp::{read_lp, write_lp};
+pub use mps::{read_mps, write_mps};
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+#[derive(Debug)]
+pub struct FormatError {
+    pub details: String,
+}
+
+impl FormatError {
+    pub fn new(details: impl Into<String>) -> Self {
+        FormatError {
+            details: details.into(),
+        }
+    }
+
+    fn at_line(line: usize, details: impl fmt::Display) -> Self {
+        FormatError {
+            details: format!("line {}: {}", line, details),
+        }
+    }
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.details)
+    }
+}
+
+impl std::error::Error for FormatError {}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Direction {
+    Maximize,
+    Minimize,
+}
+
+/// `A` in coordinate form
+#[derive(Clone, Debug, PartialEq)]
+pub struct SparseMatrix {
+    pub rows: Vec<i32>,
+    pub cols: Vec<i32>,
+    pub vals: Vec<i32>,
+    pub shape: Shape,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Shape {
+    pub nrows: usize,
+    pub ncols: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Variable {
+    pub id: String,
+    pub bound: (i32, i32),
+}
+
+/// The constraints `Ax <= b` over `variables`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Polyhedron {
+    pub a: SparseMatrix,
+    pub b: Vec<i32>,
+    pub variables: Vec<Variable>,
+}
+
+/// A model in the shape of a `/solve` request
+#[derive(Clone, Debug, PartialEq)]
+pub struct Model {
+    pub polyhedron: Polyhedron,
+    pub objectives: Vec<HashMap<String, f64>>,
+    pub direction: Direction,
+}
+
+/// A model read from a solver file
+#[derive(Debug)]
+pub struct ImportedModel {
+    pub model: Model,
+    /// Columns the file declares continuous, which the API can only solve
+    /// as integers
+    pub continuous: Vec<String>,
+}
+
+// ---------- Shared reader model ----------
+
+#[derive(Clone, Copy, PartialEq)]
+enum Sense {
+    Le,
+    Ge,
+    Eq,
+}
+
+struct Constraint {
+    sense: Sense,
+    terms: Vec<(usize, f64)>,
+    rhs: f64,
+}
+
+struct Column {
+    id: String,
+    integer: bool,
+    lower: f64,
+    upper: f64,
+}
+
+impl Column {
+    /// A column with the default bounds of both formats, `[0, +inf)`
+    fn new(id: &str, integer: bool) -> Self {
+        Column {
+            id: id.to_string(),
+            integer,
+            lower: 0.0,
+            upper: f64::INFINITY,
+        }
+    }
+}
+
+/// Build a model from a parsed file, rewriting every constraint as one or
+/// two `<=` rows
+fn build_model(
+    constraints: &[Constraint],
+    columns: &[Column],
+    objective: &[(usize, f64)],
+    direction: Direction,
+) -> Result<ImportedModel, FormatError> {
+    let mut a_rows = Vec::new();
+    let mut a_cols = Vec::new();
+    let mut a_vals = Vec::new();
+    let mut b = Vec::new();
+
+    for constraint in constraints {
+        let signs: &[i32] = match constraint.sense {
+            Sense::Le => &[1],
+            Sense::Ge => &[-1],
+            Sense::Eq => &[1, -1],
+        };
+        let rhs = to_integer(constraint.rhs, "right-hand side")?;
+        for &sign in signs {
+            let row_number = b.len() as i32;
+            for &(column, value) in &constraint.terms {
+                a_rows.push(row_number);
+                a_cols.push(column as i32);
+                a_vals.push(sign * to_integer(value, "coefficient")?);
+            }
+            b.push(sign * rhs);
+        }
+    }
+
+    let mut objective_map = HashMap::new();
+    for &(column, value) in objective {
+        *objective_map
+            .entry(columns[column].id.clone())
+            .or_insert(0.0) += value;
+    }
+    objective_map.retain(|_, coeff| *coeff != 0.0);
+
+    let variables = columns
+        .iter()
+        .map(|column| {
+            Ok(Variable {
+                id: column.id.clone(),
+                bound: (
+                    to_integer_bound(column.lower, "lower bound")?,
+                    to_integer_bound(column.upper, "upper bound")?,
+                ),
+            })
+        })
+        .collect::<Result<Vec<_>, FormatError>>()?;
+
+    let continuous = columns
+        .iter()
+        .filter(|column| !column.integer)
+        .map(|column| column.id.clone())
+        .collect();
+
+    Ok(ImportedModel {
+        model: Model {
+            polyhedron: Polyhedron {
+                a: SparseMatrix {
+                    rows: a_rows,
+                    cols: a_cols,
+                    vals: a_vals,
+                    shape: Shape {
+                        nrows: b.len(),
+                        ncols: variables.len(),
+                    },
+                },
+                b,
+                variables,
+            },
+            objectives: vec![objective_map],
+            direction,
+        },
+        continuous,
+    })
+}
+
+/// Convert a coefficient or bound from a file to the API's integer type
+fn to_integer(value: f64, what: &str) -> Result<i32, FormatError> {
+    if value.fract() != 0.0 || value < i32::MIN as f64 || value > i32::MAX as f64 {
+        return Err(FormatError::new(format!(
+            "{} {} is not an integer in the supported range",
+            what, value
+        )));
+    }
+    Ok(value as i32)
+}
+
+/// Convert a variable bound, mapping infinities to the widest integer bound
+fn to_integer_bound(value: f64, what: &str) -> Result<i32, FormatError> {
+    if value == f64::INFINITY {
+        Ok(i32::MAX)
+    } else if value == f64::NEG_INFINITY {
+        Ok(i32::MIN)
+    } else {
+        to_integer(value, what)
+    }
+}
+
+// ---------- Shared writer helpers ----------
+
+/// Rows of `A` with duplicate entries summed, in column order
+fn merged_rows(polyhedron: &Polyhedron) -> Vec<BTreeMap<usize, i64>> {
+    let mut rows = vec![BTreeMap::new(); polyhedron.a.shape.nrows];
+    for ((&row, &col), &val) in polyhedron
+        .a
+        .rows
+        .iter()
+        .zip(&polyhedron.a.cols)
+        .zip(&polyhedron.a.vals)
+    {
+        *rows[row as usize].entry(col as usize).or_insert(0) += val as i64;
+    }
+    rows
+}
+
+/// The model's objective as `(column, coefficient)` pairs in column order.
+///
+/// LP and MPS files hold exactly one objective.
+fn single_objective(model: &Model) -> Result<Vec<(usize, f64)>, FormatError> {
+    let objective = match model.objectives.as_slice() {
+        [] => return Ok(Vec::new()),
+        [objective] => objective,
+        _ => {
+            return Err(FormatError::new(format!(
+                "LP and MPS files hold a single objective, the request has {}",
+                model.objectives.len()
+            )))
+        }
+    };
+
+    let variables = &model.polyhedron.variables;
+    if let Some(missing) = objective
+        .keys()
+        .find(|id| !variables.iter().any(|v| &v.id == *id))
+    {
+        return Err(FormatError::new(format!(
+            "Objective contains missing variable {}",
+            missing
+        )));
+    }
+
+    Ok(variables
+        .iter()
+        .enumerate()
+        .filter_map(|(index, variable)| {
+            objective
+                .get(&variable.id)
+                .filter(|coeff| **coeff != 0.0)
+                .map(|coeff| (index, *coeff))
+        })
+        .collect())
+}
+
+/// Check that a variable name can be written without quoting
+fn check_name(name: &str, format: &str) -> Result<(), FormatError> {
+    let valid = !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit() || c == '.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "!\"#$%&()/,.;?@_`'{}|~".contains(c));
+    if !valid {
+        return Err(FormatError::new(format!(
+            "variable name {:?} can't be written in {} format",
+            name, format
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,637 @@
+use super::{
+    build_model, check_name, merged_rows, single_objective, Column, Constraint, FormatError,
+    ImportedModel, Sense,
+};
+use crate::{Direction, Model};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Terms written per line, keeping lines well below the 560 character limit
+/// some readers impose
+const TERMS_PER_LINE: usize = 8;
+
+/// Bounds at or beyond this magnitude are treated as infinite, as in CPLEX
+const INFINITE_BOUND: f64 = 1e30;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Section {
+    Objective,
+    Constraints,
+    Bounds,
+    General,
+    Binary,
+}
+
+#[derive(Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Name(String),
+    Plus,
+    Minus,
+    Colon,
+    Relation(Sense),
+}
+
+struct Lexed {
+    token: Token,
+    line: usize,
+}
+
+/// Read a model in CPLEX LP format.
+///
+/// Section keywords (`Maximize`, `Subject To`, `Bounds`, `General`,
+/// `Binary`, `End`) must be on lines of their own. As with MPS, constraints
+/// are rewritten as `<=` rows and must have integer coefficients.
+/// Semi-continuous variables, SOS and quadratic terms are not supported.
+pub fn read_lp(input: &str) -> Result<ImportedModel, FormatError> {
+    let mut sections: Vec<(Section, Vec<Lexed>)> = Vec::new();
+    let mut direction = None;
+
+    for (number, line) in input.lines().enumerate() {
+        let number = number + 1;
+        let line = line.split('\\').next().unwrap_or_default();
+        let keyword = line
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase();
+
+        let section = match keyword.as_str() {
+            "" => continue,
+            "maximize" | "maximise" | "maximum" | "max" => {
+                direction = Some(Direction::Maximize);
+                Some(Section::Objective)
+            }
+            "minimize" | "minimise" | "minimum" | "min" => {
+                direction = Some(Direction::Minimize);
+                Some(Section::Objective)
+            }
+            "subject to" | "such that" | "st" | "s.t." | "st." => Some(Section::Constraints),
+            "bounds" | "bound" => Some(Section::Bounds),
+            "general" | "generals" | "gen" | "integer" | "integers" => Some(Section::General),
+            "binary" | "binaries" | "bin" => Some(Section::Binary),
+            "semi-continuous" | "semis" | "semi" | "sos" => {
+                return Err(FormatError::at_line(
+                    number,
+                    format!("{} section is not supported", keyword),
+                ))
+            }
+            "end" => break,
+            _ => None,
+        };
+
+        match (section, sections.last_mut()) {
+            (Some(section), _) => sections.push((section, Vec::new())),
+            (None, Some((_, tokens))) => tokens.extend(tokenize(line, number)?),
+            (None, None) => {
+                return Err(FormatError::at_line(
+                    number,
+                    "expected Maximize or Minimize",
+                ))
+            }
+        }
+    }
+
+    let Some(direction) = direction else {
+        return Err(FormatError::new("missing Maximize or Minimize section"));
+    };
+
+    let mut parser = Parser::default();
+    for (section, tokens) in sections {
+        let mut tokens = Tokens {
+            tokens: &tokens,
+            pos: 0,
+        };
+        match section {
+            Section::Objective => parser.objective(&mut tokens)?,
+            Section::Constraints => parser.constraints(&mut tokens)?,
+            Section::Bounds => parser.bounds(&mut tokens)?,
+            Section::General | Section::Binary => {
+                parser.integers(&mut tokens, section == Section::Binary)?
+            }
+        }
+    }
+
+    build_model(
+        &parser.constraints,
+        &parser.columns,
+        &parser.objective,
+        direction,
+    )
+}
+
+/// Write a model with a single objective in CPLEX LP format.
+///
+/// Rows are named `c0`, `c1`, ... and every variable is listed as `General`,
+/// with `i32::MIN`/`i32::MAX` bounds written as infinite.
+pub fn write_lp(model: &Model) -> Result<String, FormatError> {
+    let polyhedron = &model.polyhedron;
+    let variables = &polyhedron.variables;
+    for variable in variables {
+        check_name(&variable.id, "LP")?;
+    }
+    let objective = single_objective(model)?;
+    let rows = merged_rows(polyhedron);
+
+    let mut out = String::new();
+    out.push_str(match model.direction {
+        Direction::Maximize => "Maximize\n",
+        Direction::Minimize => "Minimize\n",
+    });
+    // Every variable is listed, so that reading the file back declares them
+    // in the original order
+    let objective: HashMap<usize, f64> = objective.into_iter().collect();
+    out.push_str(" obj:");
+    write_terms(
+        &mut out,
+        variables.iter().enumerate().map(|(col, variable)| {
+            (
+                objective.get(&col).copied().unwrap_or(0.0),
+                variable.id.as_str(),
+            )
+        }),
+    );
+    out.push('\n');
+
+    out.push_str("Subject To\n");
+    for (row_index, row) in rows.iter().enumerate() {
+        let _ = write!(out, " c{}:", row_index);
+        if row.is_empty() {
+            // A constraint needs at least one term
+            let Some(first) = variables.first() else {
+                return Err(FormatError::new(
+                    "cannot write constraints for a model without variables",
+                ));
+            };
+            let _ = write!(out, " 0 {}", first.id);
+        } else {
+            write_terms(
+                &mut out,
+                row.iter()
+                    .map(|(&col, &val)| (val as f64, variables[col].id.as_str())),
+            );
+        }
+        let _ = writeln!(out, " <= {}", polyhedron.b[row_index]);
+    }
+
+    out.push_str("Bounds\n");
+    for variable in variables {
+        let id = &variable.id;
+        let _ = match variable.bound {
+            (i32::MIN, i32::MAX) => writeln!(out, " {} free", id),
+            (lower, upper) if lower == upper => writeln!(out, " {} = {}", id, lower),
+            (i32::MIN, upper) => writeln!(out, " -inf <= {} <= {}", id, upper),
+            (lower, i32::MAX) => writeln!(out, " {} >= {}", id, lower),
+            (lower, upper) => writeln!(out, " {} <= {} <= {}", lower, id, upper),
+        };
+    }
+
+    if !variables.is_empty() {
+        out.push_str("General\n");
+        for chunk in variables.chunks(TERMS_PER_LINE) {
+            for variable in chunk {
+                let _ = write!(out, " {}", variable.id);
+            }
+            out.push('\n');
+        }
+    }
+    out.push_str("End\n");
+
+    Ok(out)
+}
+
+fn write_terms<'a>(out: &mut String, terms: impl Iterator<Item = (f64, &'a str)>) {
+    for (index, (coeff, id)) in terms.enumerate() {
+        if index > 0 && index % TERMS_PER_LINE == 0 {
+            out.push_str("\n   ");
+        }
+        let sign = if coeff < 0.0 {
+            "-"
+        } else if index > 0 {
+            "+"
+        } else {
+            ""
+        };
+        if sign.is_empty() {
+            let _ = write!(out, " {} {}", coeff, id);
+        } else {
+            let _ = write!(out, " {} {} {}", sign, coeff.abs(), id);
+        }
+    }
+}
+
+fn tokenize(line: &str, number: usize) -> Result<Vec<Lexed>, FormatError> {
+    let mut tokens = Vec::new();
+    let chars: Vec<char> = line.chars().collect();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let starts_number =
+            c.is_ascii_digit() || (c == '.' && chars.get(i + 1).is_some_and(char::is_ascii_digit));
+
+        let token =
+            if c.is_whitespace() {
+                i += 1;
+                continue;
+            } else if starts_number {
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                // Exponent, only when followed by digits so names like `e1x` still lex
+                if i < chars.len() && matches!(chars[i], 'e' | 'E') {
+                    let mut j = i + 1;
+                    if j < chars.len() && matches!(chars[j], '+' | '-') {
+                        j += 1;
+                    }
+                    if j < chars.len() && chars[j].is_ascii_digit() {
+                        i = j;
+                        while i < chars.len() && chars[i].is_ascii_digit() {
+                            i += 1;
+                        }
+                    }
+                }
+                let text: String = chars[start..i].iter().collect();
+                Token::Number(text.parse().map_err(|_| {
+                    FormatError::at_line(number, format!("invalid number {}", text))
+                })?)
+            } else if matches!(c, '<' | '>' | '=') {
+                let start = i;
+                while i < chars.len() && matches!(chars[i], '<' | '>' | '=') {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                Token::Relation(match text.as_str() {
+                    "<" | "<=" | "=<" => Sense::Le,
+                    ">" | ">=" | "=>" => Sense::Ge,
+                    "=" => Sense::Eq,
+                    _ => {
+                        return Err(FormatError::at_line(
+                            number,
+                            format!("invalid operator {}", text),
+                        ))
+                    }
+                })
+            } else if matches!(c, '+' | '-' | ':') {
+                i += 1;
+                match c {
+                    '+' => Token::Plus,
+                    '-' => Token::Minus,
+                    _ => Token::Colon,
+                }
+            } else if matches!(c, '[' | ']' | '^' | '*' | '/') {
+                return Err(FormatError::at_line(
+                    number,
+                    "quadratic terms are not supported",
+                ));
+            } else {
+                let start = i;
+                while i < chars.len()
+                    && !chars[i].is_whitespace()
+                    && !matches!(chars[i], '+' | '-' | ':' | '<' | '>' | '=')
+                {
+                    i += 1;
+                }
+                Token::Name(chars[start..i].iter().collect())
+            };
+
+        tokens.push(Lexed {
+            token,
+            line: number,
+        });
+    }
+
+    Ok(tokens)
+}
+
+struct Tokens<'a> {
+    tokens: &'a [Lexed],
+    pos: usize,
+}
+
+impl Tokens<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.peek_at(0)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&Token> {
+        self.tokens.get(self.pos + offset).map(|lexed| &lexed.token)
+    }
+
+    fn next(&mut self) -> Option<&Token> {
+        let token = self.tokens.get(self.pos).map(|lexed| &lexed.token);
+        self.pos += 1;
+        token
+    }
+
+    fn is_done(&self) -> bool {
+        self.pos >= self.tokens.len()
+    }
+
+    fn error(&self, details: impl std::fmt::Display) -> FormatError {
+        match self.tokens.get(self.pos).or(self.tokens.last()) {
+            Some(lexed) => FormatError::at_line(lexed.line, details),
+            None => FormatError::new(details.to_string()),
+        }
+    }
+
+    /// Skip a `label:` prefix
+    fn skip_label(&mut self) {
+        if matches!(self.peek(), Some(Token::Name(_))) && self.peek_at(1) == Some(&Token::Colon) {
+            self.pos += 2;
+        }
+    }
+
+    /// Optional signs, returning `-1.0` for an odd number of minuses
+    fn signs(&mut self) -> f64 {
+        let mut sign = 1.0;
+        while let Some(token @ (Token::Plus | Token::Minus)) = self.peek() {
+            if *token == Token::Minus {
+                sign = -sign;
+            }
+            self.pos += 1;
+        }
+        sign
+    }
+
+    /// A signed number or infinity in a bound
+    fn bound_value(&mut self) -> Result<f64, FormatError> {
+        let sign = self.signs();
+        let value = match self.next() {
+            Some(Token::Number(value)) => *value,
+            Some(Token::Name(name)) if is_infinity(name) => f64::INFINITY,
+            _ => {
+                self.pos -= 1;
+                return Err(self.error("expected a bound value"));
+            }
+        };
+        let value = sign * value;
+        Ok(if value >= INFINITE_BOUND {
+            f64::INFINITY
+        } else if value <= -INFINITE_BOUND {
+            f64::NEG_INFINITY
+        } else {
+            value
+        })
+    }
+}
+
+fn is_infinity(name: &str) -> bool {
+    matches!(name.to_lowercase().as_str(), "inf" | "infinity")
+}
+
+#[derive(Default)]
+struct Parser {
+    columns: Vec<Column>,
+    column_index: HashMap<String, usize>,
+    objective: Vec<(usize, f64)>,
+    constraints: Vec<Constraint>,
+}
+
+impl Parser {
+    fn column(&mut self, name: &str) -> usize {
+        if let Some(&index) = self.column_index.get(name) {
+            return index;
+        }
+        self.columns.push(Column::new(name, false));
+        self.column_index
+            .insert(name.to_string(), self.columns.len() - 1);
+        self.columns.len() - 1
+    }
+
+    /// Parse `[+-] [coefficient] name` terms up to a relation or the end
+    fn expression(&mut self, tokens: &mut Tokens) -> Result<Vec<(usize, f64)>, FormatError> {
+        let mut terms = Vec::new();
+        loop {
+            let start = tokens.pos;
+            let sign = tokens.signs();
+            if !terms.is_empty() && tokens.pos == start {
+                // Terms after the first must be joined by an operator
+                break;
+            }
+            let coeff = match tokens.peek() {
+                Some(Token::Number(value)) => {
+                    let value = *value;
+                    tokens.pos += 1;
+                    value
+                }
+                _ => 1.0,
+            };
+            match tokens.peek() {
+                Some(Token::Name(name)) => {
+                    let name = name.clone();
+                    tokens.pos += 1;
+                    let column = self.column(&name);
+                    terms.push((column, sign * coeff));
+                }
+                _ if tokens.pos > start => {
+                    return Err(tokens.error("constant terms are not supported"));
+                }
+                _ => break,
+            }
+        }
+        Ok(terms)
+    }
+
+    fn objective(&mut self, tokens: &mut Tokens) -> Result<(), FormatError> {
+        tokens.skip_label();
+        self.objective = self.expression(tokens)?;
+        if !tokens.is_done() {
+            return Err(tokens.error("unexpected token in objective"));
+        }
+        Ok(())
+    }
+
+    fn constraints(&mut self, tokens: &mut Tokens) -> Result<(), FormatError> {
+        while !tokens.is_done() {
+            tokens.skip_label();
+            let terms = self.expression(tokens)?;
+            let Some(Token::Relation(sense)) = tokens.next().cloned() else {
+                tokens.pos -= 1;
+                return Err(tokens.error("expected <=, >= or ="));
+            };
+            let sign = tokens.signs();
+            let Some(Token::Number(rhs)) = tokens.next().cloned() else {
+                tokens.pos -= 1;
+                return Err(tokens.error("expected a number on the right-hand side"));
+            };
+            self.constraints.push(Constraint {
+                sense,
+                terms,
+                rhs: sign * rhs,
+            });
+        }
+        Ok(())
+    }
+
+    fn bounds(&mut self, tokens: &mut Tokens) -> Result<(), FormatError> {
+        while !tokens.is_done() {
+            let value_first = match (tokens.peek(), tokens.peek_at(1)) {
+                (Some(Token::Name(name)), Some(Token::Relation(_))) => is_infinity(name),
+                (Some(Token::Name(_)), _) => false,
+                _ => true,
+            };
+
+            if !value_first {
+                let Some(Token::Name(name)) = tokens.next().cloned() else {
+                    unreachable!("checked above");
+                };
+                let column = self.column(&name);
+                match tokens.next().cloned() {
+                    Some(Token::Name(word)) if word.eq_ignore_ascii_case("free") => {
+                        self.columns[column].lower = f64::NEG_INFINITY;
+                        self.columns[column].upper = f64::INFINITY;
+                    }
+                    Some(Token::Relation(sense)) => {
+                        let value = tokens.bound_value()?;
+                        self.apply_bound(column, sense, value);
+                    }
+                    _ => {
+                        tokens.pos -= 1;
+                        return Err(tokens.error("expected a bound"));
+                    }
+                }
+                continue;
+            }
+
+            // value <= name [<= value]
+            let value = tokens.bound_value()?;
+            let Some(Token::Relation(sense)) = tokens.next().cloned() else {
+                tokens.pos -= 1;
+                return Err(tokens.error("expected <=, >= or ="));
+            };
+            let Some(Token::Name(name)) = tokens.next().cloned() else {
+                tokens.pos -= 1;
+                return Err(tokens.error("expected a variable name"));
+            };
+            let column = self.column(&name);
+            let flipped = match sense {
+                Sense::Le => Sense::Ge,
+                Sense::Ge => Sense::Le,
+                Sense::Eq => Sense::Eq,
+            };
+            self.apply_bound(column, flipped, value);
+
+            if let Some(Token::Relation(sense)) = tokens.peek().cloned() {
+                tokens.pos += 1;
+                let value = tokens.bound_value()?;
+                self.apply_bound(column, sense, value);
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply `column <sense> value`
+    fn apply_bound(&mut self, column: usize, sense: Sense, value: f64) {
+        let column = &mut self.columns[column];
+        match sense {
+            Sense::Le => column.upper = value,
+            Sense::Ge => column.lower = value,
+            Sense::Eq => {
+                column.lower = value;
+                column.upper = value;
+            }
+        }
+    }
+
+    fn integers(&mut self, tokens: &mut Tokens, binary: bool) -> Result<(), FormatError> {
+        while let Some(token) = tokens.peek() {
+            let Token::Name(name) = token else {
+                return Err(tokens.error("expected a variable name"));
+            };
+            let name = name.clone();
+            tokens.pos += 1;
+            let column = self.column(&name);
+            let column = &mut self.columns[column];
+            column.integer = true;
+            if binary {
+                column.lower = 0.0;
+                column.upper = 1.0;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::merged_rows;
+
+    const EXAMPLE: &str = "\
+\\ Example model
+Maximize
+ obj: x + 2 y + z
+Subject To
+ c1: x + y <= 4
+ c2: x >= 1
+ c3: y
+     + z = 2
+Bounds
+ x <= 3
+ -inf <= z <= 10
+General
+ x z
+Binary
+ y
+End
+";
+
+    #[test]
+    fn test_read_lp_converts_rows_to_le() {
+        let imported = read_lp(EXAMPLE).unwrap();
+        let model = imported.model;
+        let polyhedron = &model.polyhedron;
+
+        assert_eq!(model.direction, Direction::Maximize);
+        assert_eq!(polyhedron.b, vec![4, -1, 2, -2]);
+        assert_eq!(polyhedron.a.rows, vec![0, 0, 1, 2, 2, 3, 3]);
+        assert_eq!(polyhedron.a.cols, vec![0, 1, 0, 1, 2, 1, 2]);
+        assert_eq!(polyhedron.a.vals, vec![1, 1, -1, 1, 1, -1, -1]);
+
+        let bounds: Vec<_> = polyhedron.variables.iter().map(|v| v.bound).collect();
+        assert_eq!(bounds, vec![(0, 3), (0, 1), (i32::MIN, 10)]);
+        assert_eq!(model.objectives[0]["y"], 2.0);
+        assert!(imported.continuous.is_empty());
+    }
+
+    #[test]
+    fn test_read_lp_reports_continuous_variables() {
+        let imported = read_lp("Minimize\n obj: - x\nSubject To\n x <= 5\nEnd\n").unwrap();
+        assert_eq!(imported.continuous, vec!["x".to_string()]);
+        assert_eq!(imported.model.objectives[0]["x"], -1.0);
+    }
+
+    #[test]
+    fn test_read_lp_rejects_constants_in_objective() {
+        let error = read_lp("Maximize\n obj: x + 3\nEnd\n").unwrap_err();
+        assert_eq!(error.details, "line 2: constant terms are not supported");
+    }
+
+    #[test]
+    fn test_write_lp_round_trips() {
+        let model = read_lp(EXAMPLE).unwrap().model;
+        let written = write_lp(&model).unwrap();
+        let read_back = read_lp(&written).unwrap();
+
+        assert!(read_back.continuous.is_empty());
+        assert_eq!(
+            merged_rows(&read_back.model.polyhedron),
+            merged_rows(&model.polyhedron)
+        );
+        assert_eq!(read_back.model.polyhedron.b, model.polyhedron.b);
+        assert_eq!(
+            read_back.model.polyhedron.variables,
+            model.polyhedron.variables
+        );
+        assert_eq!(read_back.model.objectives, model.objectives);
+    }
+
+    #[test]
+    fn test_write_lp_rejects_multiple_objectives() {
+        let mut model = read_lp(EXAMPLE).unwrap().model;
+        model.objectives.push(model.objectives[0].clone());
+        assert!(write_lp(&model).is_err());
+    }
+}
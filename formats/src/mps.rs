@@ -0,0 +1,442 @@
+use super::{
+    build_model, check_name, merged_rows, single_objective, Column, Constraint, FormatError,
+    ImportedModel, Sense,
+};
+use crate::{Direction, Model};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+#[derive(Clone, Copy, PartialEq)]
+enum RowType {
+    Objective,
+    Free,
+    Constraint(Sense),
+}
+
+struct Row {
+    row_type: RowType,
+    terms: Vec<(usize, f64)>,
+    rhs: f64,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Section {
+    None,
+    ObjSense,
+    Rows,
+    Columns,
+    Rhs,
+    Bounds,
+    End,
+}
+
+/// Read a model in (fixed or free) MPS format.
+///
+/// `E` and `G` rows are rewritten as `<=` rows, since the API only accepts
+/// `Ax <= b`. Coefficients and right-hand sides must be integers; infinite
+/// variable bounds become the widest `i32` bound. `RANGES` and objective
+/// constants are not supported.
+pub fn read_mps(input: &str) -> Result<ImportedModel, FormatError> {
+    let mut section = Section::None;
+    let mut direction = Direction::Minimize;
+    let mut rows: Vec<Row> = Vec::new();
+    let mut row_index: HashMap<String, usize> = HashMap::new();
+    let mut has_objective = false;
+    let mut columns: Vec<Column> = Vec::new();
+    let mut column_index: HashMap<String, usize> = HashMap::new();
+    let mut in_integer_block = false;
+
+    for (number, line) in input.lines().enumerate() {
+        let number = number + 1;
+        if line.trim().is_empty() || line.starts_with('*') {
+            continue;
+        }
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+
+        // Section headers start in the first column
+        if !line.starts_with(char::is_whitespace) {
+            section = match tokens[0] {
+                "NAME" => Section::None,
+                "OBJSENSE" => {
+                    if let Some(sense) = tokens.get(1) {
+                        direction = parse_sense(sense, number)?;
+                    }
+                    Section::ObjSense
+                }
+                "ROWS" => Section::Rows,
+                "COLUMNS" => Section::Columns,
+                "RHS" => Section::Rhs,
+                "BOUNDS" => Section::Bounds,
+                "ENDATA" => Section::End,
+                "RANGES" => {
+                    return Err(FormatError::at_line(
+                        number,
+                        "RANGES section is not supported",
+                    ))
+                }
+                other => {
+                    return Err(FormatError::at_line(
+                        number,
+                        format!("unknown section {}", other),
+                    ))
+                }
+            };
+            if section == Section::End {
+                break;
+            }
+            continue;
+        }
+
+        match section {
+            Section::ObjSense => direction = parse_sense(tokens[0], number)?,
+            Section::Rows => {
+                let [kind, name] = tokens[..] else {
+                    return Err(FormatError::at_line(number, "expected row type and name"));
+                };
+                let row_type = match kind {
+                    "N" if !has_objective => RowType::Objective,
+                    "N" => RowType::Free,
+                    "L" => RowType::Constraint(Sense::Le),
+                    "G" => RowType::Constraint(Sense::Ge),
+                    "E" => RowType::Constraint(Sense::Eq),
+                    other => {
+                        return Err(FormatError::at_line(
+                            number,
+                            format!("unknown row type {}", other),
+                        ))
+                    }
+                };
+                has_objective |= row_type == RowType::Objective;
+                if row_index.insert(name.to_string(), rows.len()).is_some() {
+                    return Err(FormatError::at_line(
+                        number,
+                        format!("duplicate row {}", name),
+                    ));
+                }
+                rows.push(Row {
+                    row_type,
+                    terms: Vec::new(),
+                    rhs: 0.0,
+                });
+            }
+            Section::Columns => {
+                if tokens.get(1).map(|t| t.trim_matches('\'')) == Some("MARKER") {
+                    match tokens.get(2).map(|t| t.trim_matches('\'')) {
+                        Some("INTORG") => in_integer_block = true,
+                        Some("INTEND") => in_integer_block = false,
+                        _ => return Err(FormatError::at_line(number, "unknown marker")),
+                    }
+                    continue;
+                }
+                if tokens.len() != 3 && tokens.len() != 5 {
+                    return Err(FormatError::at_line(
+                        number,
+                        "expected column name and one or two row/value pairs",
+                    ));
+                }
+                let column = *column_index
+                    .entry(tokens[0].to_string())
+                    .or_insert_with(|| {
+                        columns.push(Column::new(tokens[0], in_integer_block));
+                        columns.len() - 1
+                    });
+                for pair in tokens[1..].chunks(2) {
+                    let row = lookup(&row_index, pair[0], number)?;
+                    let value = parse_number(pair[1], number)?;
+                    rows[row].terms.push((column, value));
+                }
+            }
+            Section::Rhs => {
+                // The RHS set name is optional in free MPS
+                let pairs = if tokens.len() % 2 == 1 {
+                    &tokens[1..]
+                } else {
+                    &tokens[..]
+                };
+                for pair in pairs.chunks(2) {
+                    let [name, value] = pair else {
+                        return Err(FormatError::at_line(number, "expected row/value pairs"));
+                    };
+                    let row = lookup(&row_index, name, number)?;
+                    let value = parse_number(value, number)?;
+                    if rows[row].row_type == RowType::Objective && value != 0.0 {
+                        return Err(FormatError::at_line(
+                            number,
+                            "objective constants are not supported",
+                        ));
+                    }
+                    rows[row].rhs = value;
+                }
+            }
+            Section::Bounds => {
+                let kind = tokens[0];
+                let needs_value = matches!(kind, "UP" | "LO" | "FX" | "LI" | "UI");
+                let expected = if needs_value { 3 } else { 2 };
+                // The bound set name is optional in free MPS
+                let rest = match tokens.len() - 1 {
+                    n if n == expected => &tokens[2..],
+                    n if n == expected - 1 => &tokens[1..],
+                    _ => {
+                        return Err(FormatError::at_line(
+                            number,
+                            format!("malformed {} bound", kind),
+                        ))
+                    }
+                };
+                let column = *column_index.get(rest[0]).ok_or_else(|| {
+                    FormatError::at_line(number, format!("unknown column {}", rest[0]))
+                })?;
+                let column = &mut columns[column];
+                let value = match rest.get(1) {
+                    Some(value) => parse_number(value, number)?,
+                    None => 0.0,
+                };
+                match kind {
+                    "UP" => column.upper = value,
+                    "LO" => column.lower = value,
+                    "FX" => {
+                        column.lower = value;
+                        column.upper = value;
+                    }
+                    "LI" => {
+                        column.lower = value;
+                        column.integer = true;
+                    }
+                    "UI" => {
+                        column.upper = value;
+                        column.integer = true;
+                    }
+                    "FR" => {
+                        column.lower = f64::NEG_INFINITY;
+                        column.upper = f64::INFINITY;
+                    }
+                    "MI" => column.lower = f64::NEG_INFINITY,
+                    "PL" => column.upper = f64::INFINITY,
+                    "BV" => {
+                        column.lower = 0.0;
+                        column.upper = 1.0;
+                        column.integer = true;
+                    }
+                    other => {
+                        return Err(FormatError::at_line(
+                            number,
+                            format!("unknown bound type {}", other),
+                        ))
+                    }
+                }
+            }
+            Section::None | Section::End => {
+                return Err(FormatError::at_line(number, "data outside of a section"));
+            }
+        }
+    }
+
+    let mut objective = Vec::new();
+    let mut constraints = Vec::new();
+    for row in rows {
+        match row.row_type {
+            RowType::Objective => objective = row.terms,
+            RowType::Free => {}
+            RowType::Constraint(sense) => constraints.push(Constraint {
+                sense,
+                terms: row.terms,
+                rhs: row.rhs,
+            }),
+        }
+    }
+
+    build_model(&constraints, &columns, &objective, direction)
+}
+
+/// Write a model with a single objective in free MPS format.
+///
+/// Every row is an `L` row named `c0`, `c1`, ... and every column is
+/// integer, with `i32::MIN`/`i32::MAX` bounds written as infinite.
+pub fn write_mps(model: &Model) -> Result<String, FormatError> {
+    let polyhedron = &model.polyhedron;
+    for variable in &polyhedron.variables {
+        check_name(&variable.id, "MPS")?;
+    }
+    let objective = single_objective(model)?;
+    let rows = merged_rows(polyhedron);
+
+    // Column-major view of the rows for the COLUMNS section
+    let mut column_entries: Vec<Vec<(usize, i64)>> = vec![Vec::new(); polyhedron.variables.len()];
+    for (row_index, row) in rows.iter().enumerate() {
+        for (&col, &val) in row {
+            column_entries[col].push((row_index, val));
+        }
+    }
+    let objective: HashMap<usize, f64> = objective.into_iter().collect();
+
+    let mut out = String::new();
+    out.push_str("NAME          MODEL\n");
+    if model.direction == Direction::Maximize {
+        out.push_str("OBJSENSE\n    MAX\n");
+    }
+    out.push_str("ROWS\n N  obj\n");
+    for row_index in 0..rows.len() {
+        let _ = writeln!(out, " L  c{}", row_index);
+    }
+
+    out.push_str("COLUMNS\n    MARKER                 'MARKER'                 'INTORG'\n");
+    for (index, variable) in polyhedron.variables.iter().enumerate() {
+        let entries = &column_entries[index];
+        // Columns must appear at least once to be declared
+        if let Some(coeff) = objective.get(&index) {
+            let _ = writeln!(out, "    {}  obj  {}", variable.id, coeff);
+        } else if entries.is_empty() {
+            let _ = writeln!(out, "    {}  obj  0", variable.id);
+        }
+        for &(row_index, val) in entries {
+            let _ = writeln!(out, "    {}  c{}  {}", variable.id, row_index, val);
+        }
+    }
+    out.push_str("    MARKER                 'MARKER'                 'INTEND'\n");
+
+    out.push_str("RHS\n");
+    for (row_index, rhs) in polyhedron.b.iter().enumerate() {
+        if *rhs != 0 {
+            let _ = writeln!(out, "    RHS  c{}  {}", row_index, rhs);
+        }
+    }
+
+    out.push_str("BOUNDS\n");
+    for variable in &polyhedron.variables {
+        let id = &variable.id;
+        match variable.bound {
+            (i32::MIN, i32::MAX) => {
+                let _ = writeln!(out, " FR BND  {}", id);
+            }
+            (lower, upper) if lower == upper => {
+                let _ = writeln!(out, " FX BND  {}  {}", id, lower);
+            }
+            (lower, upper) => {
+                if lower == i32::MIN {
+                    let _ = writeln!(out, " MI BND  {}", id);
+                } else {
+                    let _ = writeln!(out, " LO BND  {}  {}", id, lower);
+                }
+                // Written even when infinite, since some readers default
+                // integer columns to an upper bound of 1
+                if upper == i32::MAX {
+                    let _ = writeln!(out, " PL BND  {}", id);
+                } else {
+                    let _ = writeln!(out, " UP BND  {}  {}", id, upper);
+                }
+            }
+        }
+    }
+    out.push_str("ENDATA\n");
+
+    Ok(out)
+}
+
+fn parse_sense(token: &str, line: usize) -> Result<Direction, FormatError> {
+    match token {
+        "MAX" | "MAXIMIZE" => Ok(Direction::Maximize),
+        "MIN" | "MINIMIZE" => Ok(Direction::Minimize),
+        other => Err(FormatError::at_line(
+            line,
+            format!("unknown objective sense {}", other),
+        )),
+    }
+}
+
+fn parse_number(token: &str, line: usize) -> Result<f64, FormatError> {
+    token
+        .parse::<f64>()
+        .map_err(|_| FormatError::at_line(line, format!("invalid number {}", token)))
+}
+
+fn lookup(rows: &HashMap<String, usize>, name: &str, line: usize) -> Result<usize, FormatError> {
+    rows.get(name)
+        .copied()
+        .ok_or_else(|| FormatError::at_line(line, format!("unknown row {}", name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+NAME          EXAMPLE
+OBJSENSE
+    MAX
+ROWS
+ N  obj
+ L  c1
+ G  c2
+ E  c3
+COLUMNS
+    MARKER                 'MARKER'                 'INTORG'
+    x         obj       1.0          c1        1.0
+    x         c2        1.0
+    y         obj       2.0          c1        1.0
+    y         c3        1.0
+    MARKER                 'MARKER'                 'INTEND'
+    z         obj       1.0          c3        1.0
+RHS
+    RHS       c1        4.0          c2        1.0
+    RHS       c3        2.0
+BOUNDS
+ UP BND       x         3.0
+ BV BND       y
+ENDATA
+";
+
+    #[test]
+    fn test_read_mps_converts_rows_to_le() {
+        let imported = read_mps(EXAMPLE).unwrap();
+        let model = imported.model;
+        let polyhedron = &model.polyhedron;
+
+        assert_eq!(model.direction, Direction::Maximize);
+        // c1, -c2, c3 and -c3
+        assert_eq!(polyhedron.b, vec![4, -1, 2, -2]);
+        assert_eq!(polyhedron.a.shape.nrows, 4);
+        assert_eq!(polyhedron.a.shape.ncols, 3);
+        assert_eq!(polyhedron.a.rows, vec![0, 0, 1, 2, 2, 3, 3]);
+        assert_eq!(polyhedron.a.cols, vec![0, 1, 0, 1, 2, 1, 2]);
+        assert_eq!(polyhedron.a.vals, vec![1, 1, -1, 1, 1, -1, -1]);
+
+        let bounds: Vec<_> = polyhedron.variables.iter().map(|v| v.bound).collect();
+        assert_eq!(bounds, vec![(0, 3), (0, 1), (0, i32::MAX)]);
+        assert_eq!(model.objectives[0]["y"], 2.0);
+        assert_eq!(imported.continuous, vec!["z".to_string()]);
+    }
+
+    #[test]
+    fn test_read_mps_rejects_fractional_coefficients() {
+        let input = "ROWS\n N obj\n L c1\nCOLUMNS\n x obj 1 c1 0.5\nRHS\n RHS c1 1\nENDATA\n";
+        let error = read_mps(input).unwrap_err();
+        assert!(error.details.contains("coefficient 0.5"));
+    }
+
+    #[test]
+    fn test_read_mps_reports_unknown_rows_with_line() {
+        let input = "ROWS\n N obj\nCOLUMNS\n x missing 1\nENDATA\n";
+        let error = read_mps(input).unwrap_err();
+        assert_eq!(error.details, "line 4: unknown row missing");
+    }
+
+    #[test]
+    fn test_write_mps_round_trips() {
+        let model = read_mps(EXAMPLE).unwrap().model;
+        let written = write_mps(&model).unwrap();
+        let read_back = read_mps(&written).unwrap();
+
+        assert!(read_back.continuous.is_empty());
+        assert_eq!(
+            merged_rows(&read_back.model.polyhedron),
+            merged_rows(&model.polyhedron)
+        );
+        assert_eq!(read_back.model.polyhedron.b, model.polyhedron.b);
+        assert_eq!(
+            read_back.model.polyhedron.variables,
+            model.polyhedron.variables
+        );
+        assert_eq!(read_back.model.objectives, model.objectives);
+        assert_eq!(read_back.model.direction, model.direction);
+    }
+}
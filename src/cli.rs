@@ -0,0 +1,410 @@
+//! Command-line mode: run the solve pipeline on a file without the HTTP server
+
+use crate::domain::goal;
+use crate::domain::solver::{HighsOptions, SolveOptions, Solver};
+use crate::domain::solver_factory::{create_solver_with_cache, SolverType};
+use crate::domain::solvers::ParallelObjectivesSolver;
+use crate::domain::validate::validate_solve_request;
+use crate::formats::{self, Format};
+use crate::models::{ApiSolveStats, SolveRequest, SolverDirection};
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const USAGE: &str = "\
+Usage:
+  rust-solver-api                      Start the HTTP server
+  rust-solver-api solve <FILE> [OPTIONS]
+                                       Solve a .json (API request), .lp or .mps
+                                       file and print the solutions as JSON
+  rust-solver-api convert [FILE] --to <FORMAT> [OPTIONS]
+                                       Convert a model between json, lp and mps
+
+Solve options:
+  --solver <NAME>       glpk, microlp, highs, gurobi, lpsolve or cplex
+                        (default: the file's \"solver\", $SOLVER, or glpk)
+  --direction <DIR>     maximize or minimize, overrides the file
+  --no-presolve         Disable presolve (default: $USE_PRESOLVE, or enabled)
+
+Convert options:
+  --from <FORMAT>       Input format (default: from the file extension)
+  --to <FORMAT>         Output format
+  -o, --output <FILE>   Write to FILE instead of stdout
+                        Without FILE, or with -, the model is read from stdin
+
+  -h, --help            Print this help
+";
+
+enum CliError {
+    /// Bad command line; print usage
+    Usage(String),
+    /// The command itself failed
+    Failed(String),
+}
+
+struct ConvertArgs {
+    input: Option<PathBuf>,
+    from: Format,
+    to: Format,
+    output: Option<PathBuf>,
+}
+
+struct SolveArgs {
+    file: PathBuf,
+    /// From `--solver`; the request's own `solver` applies otherwise
+    solver: Option<SolverType>,
+    direction: Option<SolverDirection>,
+    use_presolve: bool,
+}
+
+/// Run the subcommand in `args` (without the program name) and return the
+/// process exit code.
+pub fn run(args: &[String]) -> i32 {
+    let result = match args[0].as_str() {
+        "solve" => parse_solve_args(&args[1..]).and_then(|args| solve(&args)),
+        "convert" => parse_convert_args(&args[1..]).and_then(|args| convert(&args)),
+        "-h" | "--help" | "help" => {
+            print!("{}", USAGE);
+            return 0;
+        }
+        other => Err(CliError::Usage(format!("unknown command {}", other))),
+    };
+
+    match result {
+        Ok(()) => 0,
+        Err(CliError::Usage(message)) => {
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            2
+        }
+        Err(CliError::Failed(message)) => {
+            eprintln!("error: {}", message);
+            1
+        }
+    }
+}
+
+fn parse_solve_args(args: &[String]) -> Result<SolveArgs, CliError> {
+    let mut file = None;
+    let mut solver = None;
+    let mut direction = None;
+    let mut use_presolve = env::var("USE_PRESOLVE")
+        .ok()
+        .and_then(|s| s.parse::<bool>().ok())
+        .unwrap_or(true);
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--solver" => {
+                let name = option_value(&mut args, "--solver")?;
+                solver = Some(
+                    SolverType::try_from_name(name).map_err(|e| CliError::Usage(e.to_string()))?,
+                );
+            }
+            "--direction" => {
+                direction = Some(match option_value(&mut args, "--direction")? {
+                    "maximize" => SolverDirection::Maximize,
+                    "minimize" => SolverDirection::Minimize,
+                    other => return Err(CliError::Usage(format!("unknown direction {}", other))),
+                });
+            }
+            "--no-presolve" => use_presolve = false,
+            option if option.starts_with('-') => {
+                return Err(CliError::Usage(format!("unknown option {}", option)));
+            }
+            path if file.is_none() => file = Some(PathBuf::from(path)),
+            extra => return Err(CliError::Usage(format!("unexpected argument {}", extra))),
+        }
+    }
+
+    Ok(SolveArgs {
+        file: file.ok_or_else(|| CliError::Usage("missing input file".to_string()))?,
+        solver,
+        direction,
+        use_presolve,
+    })
+}
+
+fn parse_convert_args(args: &[String]) -> Result<ConvertArgs, CliError> {
+    let mut input = None;
+    let mut from = None;
+    let mut to = None;
+    let mut output = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--from" => from = Some(format_value(option_value(&mut args, "--from")?)?),
+            "--to" => to = Some(format_value(option_value(&mut args, "--to")?)?),
+            "-o" | "--output" => output = Some(PathBuf::from(option_value(&mut args, arg)?)),
+            "-" if input.is_none() => input = Some(None),
+            option if option.starts_with('-') => {
+                return Err(CliError::Usage(format!("unknown option {}", option)));
+            }
+            path if input.is_none() => input = Some(Some(PathBuf::from(path))),
+            extra => return Err(CliError::Usage(format!("unexpected argument {}", extra))),
+        }
+    }
+    let input = input.flatten();
+
+    let from = match (from, &input) {
+        (Some(from), _) => from,
+        (None, Some(path)) => Format::from_path(path).ok_or_else(|| {
+            CliError::Usage(format!(
+                "cannot tell the format of {}; use --from",
+                path.display()
+            ))
+        })?,
+        (None, None) => {
+            return Err(CliError::Usage(
+                "--from is required when reading from stdin".to_string(),
+            ))
+        }
+    };
+
+    Ok(ConvertArgs {
+        input,
+        from,
+        to: to.ok_or_else(|| CliError::Usage("missing --to".to_string()))?,
+        output,
+    })
+}
+
+fn format_value(name: &str) -> Result<Format, CliError> {
+    Format::from_name(name).ok_or_else(|| {
+        CliError::Usage(format!("unknown format {}; expected json, lp or mps", name))
+    })
+}
+
+fn option_value<'a>(
+    args: &mut impl Iterator<Item = &'a String>,
+    option: &str,
+) -> Result<&'a str, CliError> {
+    args.next()
+        .map(String::as_str)
+        .ok_or_else(|| CliError::Usage(format!("{} needs a value", option)))
+}
+
+/// Backend for a solve: `--solver`, else the request's `solver`, else
+/// `SOLVER`, else the default
+fn solver_type(args: &SolveArgs, request: &SolveRequest) -> Result<SolverType, CliError> {
+    if let Some(solver) = args.solver {
+        return Ok(solver);
+    }
+    let name = match &request.solver {
+        Some(name) if name == "race" => {
+            return Err(CliError::Usage(
+                "\"solver\": \"race\" needs the HTTP server; pick a backend with --solver"
+                    .to_string(),
+            ))
+        }
+        Some(name) => name.clone(),
+        None => match env::var("SOLVER") {
+            Ok(name) => name,
+            Err(_) => return Ok(SolverType::default()),
+        },
+    };
+    SolverType::try_from_name(&name).map_err(|e| CliError::Usage(e.to_string()))
+}
+
+fn solve(args: &SolveArgs) -> Result<(), CliError> {
+    let mut request = read_request(&args.file)?;
+    if let Some(direction) = args.direction {
+        request.direction = direction;
+    }
+
+    validate_solve_request(&request).map_err(|e| CliError::Failed(e.details))?;
+    let has_goal = goal::rewrite_request(&mut request).map_err(|e| CliError::Failed(e.details))?;
+
+    let mut solver: Arc<dyn Solver> =
+        Arc::from(create_solver_with_cache(solver_type(args, &request)?, None));
+    if let Some(threads) = request.objective_threads.filter(|&threads| threads > 1) {
+        solver = Arc::new(ParallelObjectivesSolver::new(solver, threads));
+    }
+    let options = SolveOptions::for_request(&request, args.use_presolve, &HighsOptions::default());
+    let mut outcome = solver
+        .solve(
+            request.polyhedron,
+            request.objectives,
+            request.direction,
+            &options,
+        )
+        .map_err(|e| CliError::Failed(e.details))?;
+    if has_goal {
+        goal::strip_deviations(&mut outcome.solutions);
+    }
+
+    let mut output = serde_json::json!({
+        "solutions": outcome.solutions,
+        "stats": ApiSolveStats::from(outcome.stats),
+    });
+    if let Some(log) = outcome.log {
+        output["log"] = log.into();
+    }
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&output).map_err(|e| CliError::Failed(e.to_string()))?
+    );
+    Ok(())
+}
+
+fn convert(args: &ConvertArgs) -> Result<(), CliError> {
+    let contents = match &args.input {
+        Some(path) => read_file(path)?,
+        None => {
+            let mut contents = String::new();
+            io::stdin()
+                .read_to_string(&mut contents)
+                .map_err(|e| CliError::Failed(format!("cannot read stdin: {}", e)))?;
+            contents
+        }
+    };
+    let source = match &args.input {
+        Some(path) => path.display().to_string(),
+        None => "stdin".to_string(),
+    };
+
+    let request = import(args.from, &contents, &source)?;
+    validate_solve_request(&request)
+        .map_err(|e| CliError::Failed(format!("{}: {}", source, e.details)))?;
+    let converted = formats::write(args.to, &request)
+        .map_err(|e| CliError::Failed(format!("{}: {}", source, e)))?;
+
+    match &args.output {
+        Some(path) => fs::write(path, converted)
+            .map_err(|e| CliError::Failed(format!("cannot write {}: {}", path.display(), e))),
+        None => {
+            print!("{}", converted);
+            Ok(())
+        }
+    }
+}
+
+fn read_request(path: &Path) -> Result<SolveRequest, CliError> {
+    let format = Format::from_path(path).ok_or_else(|| {
+        CliError::Usage(format!(
+            "cannot tell the format of {}; expected a .json, .lp or .mps file",
+            path.display()
+        ))
+    })?;
+    let contents = read_file(path)?;
+    import(format, &contents, &path.display().to_string())
+}
+
+fn read_file(path: &Path) -> Result<String, CliError> {
+    fs::read_to_string(path)
+        .map_err(|e| CliError::Failed(format!("cannot read {}: {}", path.display(), e)))
+}
+
+/// Read a model, warning about anything the API can't represent exactly
+fn import(format: Format, contents: &str, source: &str) -> Result<SolveRequest, CliError> {
+    let model = formats::read(format, contents)
+        .map_err(|e| CliError::Failed(format!("{}: {}", source, e)))?;
+    if !model.continuous.is_empty() {
+        eprintln!(
+            "warning: {} continuous column(s) will be treated as integers",
+            model.continuous.len()
+        );
+    }
+    Ok(model.request)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_solve_args() {
+        let parsed = parse_solve_args(&args(&[
+            "model.mps",
+            "--solver",
+            "microlp",
+            "--direction",
+            "maximize",
+            "--no-presolve",
+        ]))
+        .ok()
+        .unwrap();
+
+        assert_eq!(parsed.file, PathBuf::from("model.mps"));
+        assert_eq!(parsed.solver, Some(SolverType::Microlp));
+        assert!(parsed.direction == Some(SolverDirection::Maximize));
+        assert!(!parsed.use_presolve);
+    }
+
+    #[test]
+    fn test_parse_solve_args_requires_file() {
+        assert!(matches!(
+            parse_solve_args(&args(&["--solver", "glpk"])),
+            Err(CliError::Usage(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_convert_args_infers_input_format() {
+        let parsed = parse_convert_args(&args(&["model.mps", "--to", "lp", "-o", "model.lp"]))
+            .ok()
+            .unwrap();
+
+        assert_eq!(parsed.input, Some(PathBuf::from("model.mps")));
+        assert_eq!(parsed.from, Format::Mps);
+        assert_eq!(parsed.to, Format::Lp);
+        assert_eq!(parsed.output, Some(PathBuf::from("model.lp")));
+    }
+
+    #[test]
+    fn test_parse_convert_args_requires_from_for_stdin() {
+        assert!(matches!(
+            parse_convert_args(&args(&["--to", "json"])),
+            Err(CliError::Usage(_))
+        ));
+        assert!(parse_convert_args(&args(&["-", "--from", "lp", "--to", "json"])).is_ok());
+    }
+
+    #[test]
+    fn test_solver_type_prefers_flag_then_request() {
+        let mut request: SolveRequest = serde_json::from_value(serde_json::json!({
+            "polyhedron": {
+                "A": { "rows": [], "cols": [], "vals": [], "shape": { "nrows": 0, "ncols": 0 } },
+                "b": [],
+                "variables": []
+            },
+            "objectives": [],
+            "direction": "maximize",
+            "solver": "microlp"
+        }))
+        .unwrap();
+        let mut parsed = parse_solve_args(&args(&["model.json"])).ok().unwrap();
+        assert_eq!(
+            solver_type(&parsed, &request).ok(),
+            Some(SolverType::Microlp)
+        );
+
+        parsed.solver = Some(SolverType::default());
+        assert_eq!(
+            solver_type(&parsed, &request).ok(),
+            Some(SolverType::default())
+        );
+
+        parsed.solver = None;
+        request.solver = Some("race".to_string());
+        assert!(matches!(
+            solver_type(&parsed, &request),
+            Err(CliError::Usage(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_solve_args_rejects_unknown_solver() {
+        assert!(matches!(
+            parse_solve_args(&args(&["model.json", "--solver", "nosuch"])),
+            Err(CliError::Usage(_))
+        ));
+    }
+}
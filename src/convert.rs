@@ -1,18 +1,53 @@
-use crate::models::{
-    ApiIntegerSparseMatrix, ApiSolution, ObjectiveOwned, SparseLEIntegerPolyhedron, Status,
-};
+use crate::domain::solver::{self, PresolveReductions, SolveStats};
+use crate::models::{ApiPresolveReductions, ApiSolveStats, TerminationReason};
+
+#[cfg(feature = "glpk-solver")]
+use crate::models::{ApiIntegerSparseMatrix, ObjectiveOwned, SparseLEIntegerPolyhedron};
+#[cfg(feature = "glpk-solver")]
 use std::collections::HashMap;
 
+#[cfg(feature = "glpk-solver")]
 use glpk_rust::{
-    Bound, IntegerSparseMatrix as GlpkMatrix, Solution, SparseLEIntegerPolyhedron as GlpkPoly,
-    Status as GlpkStatus, Variable as GlpkVar,
+    Bound, IntegerSparseMatrix as GlpkMatrix, SparseLEIntegerPolyhedron as GlpkPoly,
+    Variable as GlpkVar,
 };
 
+impl From<solver::TerminationReason> for TerminationReason {
+    fn from(reason: solver::TerminationReason) -> Self {
+        match reason {
+            solver::TerminationReason::Completed => TerminationReason::Completed,
+            solver::TerminationReason::TimeLimit => TerminationReason::TimeLimit,
+            solver::TerminationReason::Failed => TerminationReason::Failed,
+        }
+    }
+}
+
+impl From<PresolveReductions> for ApiPresolveReductions {
+    fn from(r: PresolveReductions) -> Self {
+        ApiPresolveReductions {
+            rows_removed: r.rows_removed,
+            cols_removed: r.cols_removed,
+        }
+    }
+}
+
+impl From<SolveStats> for ApiSolveStats {
+    fn from(stats: SolveStats) -> Self {
+        ApiSolveStats {
+            total_seconds: stats.total_time.as_secs_f64(),
+            presolve_reductions: stats.presolve_reductions.map(Into::into),
+            termination: stats.termination.into(),
+        }
+    }
+}
+
+#[cfg(feature = "glpk-solver")]
 pub fn to_borrowed_objective(obj: &ObjectiveOwned) -> HashMap<&str, f64> {
     obj.iter().map(|(k, v)| (k.as_str(), *v)).collect()
 }
 
 /// Convert an API LE polyhedron to a GLPK LE polyhedron by building borrowed variables.
+#[cfg(feature = "glpk-solver")]
 pub fn to_glpk_polyhedron<'a>(le: &'a SparseLEIntegerPolyhedron) -> GlpkPoly<'a> {
     let a = to_glpk_matrix(&le.a);
     let b: Vec<Bound> = le.b.iter().map(|&v| (0, v)).collect();
@@ -34,6 +69,7 @@ pub fn to_glpk_polyhedron<'a>(le: &'a SparseLEIntegerPolyhedron) -> GlpkPoly<'a>
     }
 }
 
+#[cfg(feature = "glpk-solver")]
 fn to_glpk_matrix(m: &ApiIntegerSparseMatrix) -> GlpkMatrix {
     GlpkMatrix {
         rows: m.rows.clone(),
@@ -41,35 +77,3 @@ fn to_glpk_matrix(m: &ApiIntegerSparseMatrix) -> GlpkMatrix {
         vals: m.vals.clone(),
     }
 }
-
-impl From<GlpkStatus> for Status {
-    fn from(s: GlpkStatus) -> Self {
-        // Assumes your crate uses the same variant names
-        match s {
-            GlpkStatus::Undefined => Status::Undefined,
-            GlpkStatus::Feasible => Status::Feasible,
-            GlpkStatus::Infeasible => Status::Infeasible,
-            GlpkStatus::NoFeasible => Status::NoFeasible,
-            GlpkStatus::Optimal => Status::Optimal,
-            GlpkStatus::Unbounded => Status::Unbounded,
-            GlpkStatus::SimplexFailed => Status::SimplexFailed,
-            GlpkStatus::MIPFailed => Status::MIPFailed,
-            GlpkStatus::EmptySpace => Status::EmptySpace,
-        }
-    }
-}
-
-impl From<Solution> for ApiSolution {
-    fn from(s: Solution) -> Self {
-        ApiSolution {
-            status: s.status.into(),
-            objective: s.objective as i32, // Match current api contract
-            solution: s
-                .solution
-                .into_iter()
-                .map(|(k, v)| (k.to_string(), v))
-                .collect(),
-            error: s.error,
-        }
-    }
-}
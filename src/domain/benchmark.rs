@@ -0,0 +1,139 @@
+use crate::domain::solver::{SolveOptions, Solver};
+use crate::models::{
+    ApiBenchmarkResult, ApiBenchmarkSolution, SolverDirection, SparseLEIntegerPolyhedron,
+};
+use std::collections::HashMap;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Run the same problem on each solver in turn, recording timing and results.
+///
+/// Backends run one at a time so their timings don't compete for CPU. A
+/// backend still running after `time_limit` is reported as timed out and
+/// cancelled. Backends that only check for cancellation between objectives
+/// may finish the current one in the background while the next backend starts.
+pub fn run_benchmark(
+    solvers: &[Arc<dyn Solver>],
+    polyhedron: &SparseLEIntegerPolyhedron,
+    objectives: &[HashMap<String, f64>],
+    direction: SolverDirection,
+    options: &SolveOptions,
+    time_limit: Duration,
+) -> Vec<ApiBenchmarkResult> {
+    solvers
+        .iter()
+        .map(|solver| {
+            let (sender, receiver) = mpsc::channel();
+            let started = Instant::now();
+            let run_options = SolveOptions {
+                cancel: options.cancel.child(),
+                ..options.clone()
+            };
+            {
+                let solver = Arc::clone(solver);
+                let polyhedron = polyhedron.clone();
+                let objectives = objectives.to_vec();
+                let run_options = run_options.clone();
+                thread::spawn(move || {
+                    let result = solver.solve(polyhedron, objectives, direction, &run_options);
+                    let _ = sender.send(result);
+                });
+            }
+
+            let mut result = ApiBenchmarkResult {
+                solver: solver.name().to_string(),
+                seconds: 0.0,
+                timed_out: false,
+                solutions: Vec::new(),
+                error: None,
+            };
+            match receiver.recv_timeout(time_limit) {
+                Ok(Ok(outcome)) => {
+                    result.solutions = outcome
+                        .solutions
+                        .into_iter()
+                        .map(|solution| ApiBenchmarkSolution {
+                            status: solution.status,
+                            objective: solution.objective,
+                        })
+                        .collect();
+                }
+                Ok(Err(error)) => result.error = Some(error.details),
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    run_options.cancel.cancel();
+                    result.timed_out = true;
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => {
+                    result.error = Some(format!("{} crashed", solver.name()));
+                }
+            }
+            result.seconds = started.elapsed().as_secs_f64();
+            result
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::solver::SolveOutcome;
+    use crate::domain::solvers::MicrolpSolver;
+    use crate::domain::validate::SolveInputError;
+    use crate::models::{ApiIntegerSparseMatrix, ApiShape, ApiVariable, Status};
+
+    struct SlowSolver;
+
+    impl Solver for SlowSolver {
+        fn solve(
+            &self,
+            _polyhedron: SparseLEIntegerPolyhedron,
+            _objectives: Vec<HashMap<String, f64>>,
+            _direction: SolverDirection,
+            _options: &SolveOptions,
+        ) -> Result<SolveOutcome, SolveInputError> {
+            thread::sleep(Duration::from_secs(2));
+            Ok(SolveOutcome::finished(Vec::new(), Instant::now()))
+        }
+
+        fn name(&self) -> &str {
+            "slow"
+        }
+    }
+
+    #[test]
+    fn test_reports_results_and_timeouts() {
+        let polyhedron = SparseLEIntegerPolyhedron {
+            a: ApiIntegerSparseMatrix {
+                rows: vec![0],
+                cols: vec![0],
+                vals: vec![1],
+                shape: ApiShape { nrows: 1, ncols: 1 },
+            },
+            b: vec![3],
+            variables: vec![ApiVariable {
+                id: "x".to_string(),
+                bound: (0, 10),
+            }],
+        };
+        let solvers: Vec<Arc<dyn Solver>> = vec![Arc::new(MicrolpSolver), Arc::new(SlowSolver)];
+
+        let results = run_benchmark(
+            &solvers,
+            &polyhedron,
+            &[HashMap::from([("x".to_string(), 1.0)])],
+            SolverDirection::Maximize,
+            &SolveOptions::with_presolve(true),
+            Duration::from_millis(100),
+        );
+
+        assert_eq!(results[0].solver, "microlp");
+        assert!(!results[0].timed_out);
+        assert!(matches!(results[0].solutions[0].status, Status::Optimal));
+        assert_eq!(results[0].solutions[0].objective, 3.0);
+        assert_eq!(results[1].solver, "slow");
+        assert!(results[1].timed_out);
+        assert!(results[1].solutions.is_empty());
+    }
+}
@@ -0,0 +1,84 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Flag that asks a running solve to stop
+///
+/// Clones share the same flag. Backends poll `is_cancelled` between
+/// objectives and, where the native solver allows it, from inside its search,
+/// then return an error instead of finishing.
+#[derive(Clone)]
+pub struct CancellationToken {
+    // This token's own flag is the last; the rest belong to its parents
+    flags: Vec<Arc<AtomicBool>>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        CancellationToken {
+            flags: vec![Arc::new(AtomicBool::new(false))],
+        }
+    }
+
+    /// Create a token that is cancelled along with this one, but can also be
+    /// cancelled on its own without affecting this one
+    pub fn child(&self) -> Self {
+        let mut flags = self.flags.clone();
+        flags.push(Arc::new(AtomicBool::new(false)));
+        CancellationToken { flags }
+    }
+
+    pub fn cancel(&self) {
+        if let Some(flag) = self.flags.last() {
+            flag.store(true, Ordering::Relaxed);
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.flags.iter().any(|flag| flag.load(Ordering::Relaxed))
+    }
+
+    /// Cancel this token when the returned guard is dropped
+    ///
+    /// Held by request handlers: actix drops the handler future when the
+    /// client disconnects, which stops the solve that was running for it.
+    pub fn cancel_on_drop(&self) -> CancelOnDrop {
+        CancelOnDrop(self.clone())
+    }
+}
+
+impl Default for CancellationToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Guard returned by [`CancellationToken::cancel_on_drop`]
+pub struct CancelOnDrop(CancellationToken);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.cancel();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_child_follows_parent() {
+        let parent = CancellationToken::new();
+        let child = parent.child();
+
+        child.cancel();
+        assert!(child.is_cancelled());
+        assert!(!parent.is_cancelled());
+
+        let other_child = parent.child();
+        {
+            let _guard = parent.cancel_on_drop();
+        }
+        assert!(parent.is_cancelled());
+        assert!(other_child.is_cancelled());
+    }
+}
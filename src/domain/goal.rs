@@ -0,0 +1,252 @@
+use crate::domain::validate::SolveInputError;
+use crate::models::{
+    ApiShape, ApiSolution, ApiVariable, ObjectiveOwned, SolveRequest, SolverDirection,
+    SparseLEIntegerPolyhedron,
+};
+use std::collections::HashMap;
+
+/// Prefix of the deviation variables added by `rewrite_as_goals`
+const DEVIATION_PREFIX: &str = "__goal_deviation_";
+
+/// Terms, target and largest possible deviation of one goal
+type Goal = (Vec<(usize, i32)>, i32, i32);
+
+/// Rewrite a problem so that minimizing the returned objectives minimizes
+/// each original objective's distance to its target
+///
+/// Each objective `c·x` with target `t` gets a deviation variable `d` and the
+/// rows `c·x - d <= t` and `-c·x - d <= -t`, so that `d >= |c·x - t|`. The
+/// returned objectives each select one deviation. A deviation is bounded by
+/// the largest distance its objective can reach, so the rows of the other
+/// goals never cut off solutions.
+///
+/// The constraint matrix is integer, so objective coefficients and targets
+/// must be whole numbers. The polyhedron is left as is on error.
+pub fn rewrite_as_goals(
+    polyhedron: &mut SparseLEIntegerPolyhedron,
+    objectives: &[ObjectiveOwned],
+    targets: &[i32],
+) -> Result<Vec<ObjectiveOwned>, SolveInputError> {
+    if let Some(var) = polyhedron
+        .variables
+        .iter()
+        .find(|var| var.id.starts_with(DEVIATION_PREFIX))
+    {
+        return Err(SolveInputError {
+            details: format!(
+                "Variable '{}' clashes with the goal deviation variables",
+                var.id
+            ),
+        });
+    }
+
+    let goals = objectives
+        .iter()
+        .zip(targets)
+        .map(|(objective, &target)| to_goal(&polyhedron.variables, objective, target))
+        .collect::<Result<Vec<Goal>, _>>()?;
+
+    let mut goal_objectives = Vec::with_capacity(goals.len());
+    for (index, (terms, target, reach)) in goals.into_iter().enumerate() {
+        let deviation = polyhedron.variables.len() as i32;
+        let id = format!("{}{}", DEVIATION_PREFIX, index);
+        polyhedron.variables.push(ApiVariable {
+            id: id.clone(),
+            bound: (0, reach),
+        });
+
+        for sign in [1, -1] {
+            let row = polyhedron.b.len() as i32;
+            for &(col, coeff) in &terms {
+                polyhedron.a.rows.push(row);
+                polyhedron.a.cols.push(col as i32);
+                polyhedron.a.vals.push(sign * coeff);
+            }
+            polyhedron.a.rows.push(row);
+            polyhedron.a.cols.push(deviation);
+            polyhedron.a.vals.push(-1);
+            polyhedron.b.push(sign * target);
+        }
+
+        goal_objectives.push(HashMap::from([(id, 1.0)]));
+    }
+    polyhedron.a.shape = ApiShape {
+        nrows: polyhedron.b.len(),
+        ncols: polyhedron.variables.len(),
+    };
+
+    Ok(goal_objectives)
+}
+
+/// Integer terms of one objective, with its target and the largest
+/// deviation from the target any solution can have
+fn to_goal(
+    variables: &[ApiVariable],
+    objective: &ObjectiveOwned,
+    target: i32,
+) -> Result<Goal, SolveInputError> {
+    let columns: HashMap<&str, usize> = variables
+        .iter()
+        .enumerate()
+        .map(|(col, var)| (var.id.as_str(), col))
+        .collect();
+
+    // Largest |c·x - t| over the variable bounds
+    let mut reach = i64::from(target).abs();
+    let mut terms = Vec::with_capacity(objective.len());
+    for (id, &coeff) in objective {
+        let col = *columns.get(id.as_str()).ok_or_else(|| SolveInputError {
+            details: format!("Objective variable '{}' is not in the polyhedron", id),
+        })?;
+        let limit = i32::MAX as f64;
+        if coeff.fract() != 0.0 || !(-limit..=limit).contains(&coeff) {
+            return Err(SolveInputError {
+                details: format!(
+                    "Goal objectives need integer coefficients, got {} for '{}'",
+                    coeff, id
+                ),
+            });
+        }
+        let coeff = coeff as i32;
+        if coeff == 0 {
+            continue;
+        }
+        let (lower, upper) = variables[col].bound;
+        let largest = i64::from(lower).abs().max(i64::from(upper).abs());
+        reach = reach.saturating_add(i64::from(coeff).abs().saturating_mul(largest));
+        terms.push((col, coeff));
+    }
+
+    let reach = i32::try_from(reach).map_err(|_| SolveInputError {
+        details: "Goal deviation can exceed the integer range of the polyhedron".to_string(),
+    })?;
+    Ok((terms, target, reach))
+}
+
+/// Rewrite a request carrying a `goal` into the deviations to minimize
+///
+/// Returns whether the request had a goal, in which case its solutions need
+/// `strip_deviations`.
+pub fn rewrite_request(req: &mut SolveRequest) -> Result<bool, SolveInputError> {
+    let Some(targets) = req.goal.take() else {
+        return Ok(false);
+    };
+    req.objectives = rewrite_as_goals(&mut req.polyhedron, &req.objectives, &targets)?;
+    req.direction = SolverDirection::Minimize;
+    Ok(true)
+}
+
+/// Remove the deviation variables added by `rewrite_as_goals` from solutions
+///
+/// Each solution's objective value is then its distance to the target.
+pub fn strip_deviations(solutions: &mut [ApiSolution]) {
+    for solution in solutions {
+        solution
+            .solution
+            .retain(|id, _| !id.starts_with(DEVIATION_PREFIX));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::solver::{SolveOptions, Solver};
+    use crate::domain::solvers::MicrolpSolver;
+    use crate::models::{ApiIntegerSparseMatrix, SolverDirection, Status};
+
+    /// x + y <= 8 with x, y in [0, 5]
+    fn polyhedron() -> SparseLEIntegerPolyhedron {
+        SparseLEIntegerPolyhedron {
+            a: ApiIntegerSparseMatrix {
+                rows: vec![0, 0],
+                cols: vec![0, 1],
+                vals: vec![1, 1],
+                shape: ApiShape { nrows: 1, ncols: 2 },
+            },
+            b: vec![8],
+            variables: vec![
+                ApiVariable {
+                    id: "x".to_string(),
+                    bound: (0, 5),
+                },
+                ApiVariable {
+                    id: "y".to_string(),
+                    bound: (0, 5),
+                },
+            ],
+        }
+    }
+
+    fn objective(terms: &[(&str, f64)]) -> ObjectiveOwned {
+        terms
+            .iter()
+            .map(|(id, coeff)| (id.to_string(), *coeff))
+            .collect()
+    }
+
+    #[test]
+    fn test_adds_deviation_rows_per_goal() {
+        let mut goal = polyhedron();
+        let objectives = rewrite_as_goals(&mut goal, &[objective(&[("x", 2.0)])], &[3])
+            .ok()
+            .unwrap();
+
+        assert_eq!(goal.a.shape.nrows, 3);
+        assert_eq!(goal.a.shape.ncols, 3);
+        assert_eq!(goal.a.rows, vec![0, 0, 1, 1, 2, 2]);
+        assert_eq!(goal.a.cols, vec![0, 1, 0, 2, 0, 2]);
+        assert_eq!(goal.a.vals, vec![1, 1, 2, -1, -2, -1]);
+        assert_eq!(goal.b, vec![8, 3, -3]);
+        // |2x - 3| is at most 3 + 2 * 5
+        assert_eq!(goal.variables[2].bound, (0, 13));
+        assert_eq!(objectives, vec![objective(&[("__goal_deviation_0", 1.0)])]);
+    }
+
+    #[test]
+    fn test_rejects_fractional_coefficients() {
+        let mut goal = polyhedron();
+        let err = rewrite_as_goals(&mut goal, &[objective(&[("x", 0.5)])], &[3])
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.details,
+            "Goal objectives need integer coefficients, got 0.5 for 'x'"
+        );
+        assert!(goal == polyhedron());
+    }
+
+    #[test]
+    fn test_solves_closest_to_targets() {
+        let objectives = [
+            objective(&[("x", 1.0), ("y", 1.0)]),
+            objective(&[("x", 1.0), ("y", -1.0)]),
+        ];
+        // x + y can reach 7 exactly; x - y = 12 is out of reach, 5 is closest
+        let mut goal = polyhedron();
+        let goal_objectives = rewrite_as_goals(&mut goal, &objectives, &[7, 12])
+            .ok()
+            .unwrap();
+
+        let mut outcome = MicrolpSolver
+            .solve(
+                goal,
+                goal_objectives,
+                SolverDirection::Minimize,
+                &SolveOptions::with_presolve(false),
+            )
+            .ok()
+            .unwrap();
+        strip_deviations(&mut outcome.solutions);
+
+        let first = &outcome.solutions[0];
+        assert!(matches!(first.status, Status::Optimal));
+        assert_eq!(first.objective, 0.0);
+        assert_eq!(first.solution["x"] + first.solution["y"], 7);
+        assert_eq!(first.solution.len(), 2);
+
+        let second = &outcome.solutions[1];
+        assert_eq!(second.objective, 7.0);
+        assert_eq!(second.solution["x"], 5);
+        assert_eq!(second.solution["y"], 0);
+    }
+}
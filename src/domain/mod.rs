@@ -1,4 +1,12 @@
+pub mod benchmark;
+pub mod cancellation;
+pub mod goal;
+pub mod recent_solves;
+pub mod self_test;
+pub mod solve_executor;
 pub mod solver;
 pub mod solver_factory;
+pub mod solver_registry;
 pub mod solvers;
-mod validate;
+pub mod validate;
+pub mod worker_pool;
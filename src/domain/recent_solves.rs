@@ -0,0 +1,219 @@
+use crate::domain::solver::SolveOptions;
+use crate::models::{ObjectiveOwned, SolverDirection, SparseLEIntegerPolyhedron};
+use lru::LruCache;
+use parking_lot::Mutex;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::num::NonZeroUsize;
+use std::time::{Duration, Instant};
+
+/// Canonical fingerprint of a solve request, used as its `ETag`.
+///
+/// Objective keys are hashed in sorted order so that two requests that only
+/// differ in JSON key order get the same tag. The solver backend, presolve,
+/// exact LP, MIP gap, LP method and HiGHS settings are part of the
+/// fingerprint, since they can change the answer.
+/// Tags are only meaningful to the process that issued them.
+pub fn fingerprint(
+    polyhedron: &SparseLEIntegerPolyhedron,
+    objectives: &[ObjectiveOwned],
+    direction: SolverDirection,
+    solver_name: &str,
+    options: &SolveOptions,
+) -> String {
+    let mut hasher = DefaultHasher::new();
+    polyhedron.hash(&mut hasher);
+    direction.hash(&mut hasher);
+    solver_name.hash(&mut hasher);
+    options.use_presolve.hash(&mut hasher);
+    options.exact_lp.hash(&mut hasher);
+    options.mip_rel_gap.map(f64::to_bits).hash(&mut hasher);
+    options.method.hash(&mut hasher);
+    options.highs.hash(&mut hasher);
+
+    objectives.len().hash(&mut hasher);
+    for objective in objectives {
+        let mut terms: Vec<(&String, &f64)> = objective.iter().collect();
+        terms.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        terms.len().hash(&mut hasher);
+        for (id, coeff) in terms {
+            id.hash(&mut hasher);
+            coeff.to_bits().hash(&mut hasher);
+        }
+    }
+
+    format!("\"{:016x}\"", hasher.finish())
+}
+
+/// Fingerprints of requests that were solved successfully in the last `ttl`.
+///
+/// Lets `/solve` answer `If-None-Match` with 304 Not Modified instead of
+/// solving the same deterministic problem again.
+pub struct RecentSolves {
+    entries: Mutex<LruCache<String, Instant>>,
+    ttl: Duration,
+}
+
+impl RecentSolves {
+    /// Create a store remembering at most `capacity` fingerprints for `ttl`
+    pub fn new(capacity: NonZeroUsize, ttl: Duration) -> Self {
+        RecentSolves {
+            entries: Mutex::new(LruCache::new(capacity)),
+            ttl,
+        }
+    }
+
+    /// Record that the request with this fingerprint was just solved
+    pub fn insert(&self, tag: &str) {
+        self.entries.lock().put(tag.to_string(), Instant::now());
+    }
+
+    /// Whether the fingerprint was solved within the last `ttl`
+    pub fn contains(&self, tag: &str) -> bool {
+        let mut entries = self.entries.lock();
+        match entries.get(tag) {
+            Some(solved_at) if solved_at.elapsed() <= self.ttl => true,
+            Some(_) => {
+                entries.pop(tag);
+                false
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::solver::HighsOptions;
+    use crate::models::{ApiIntegerSparseMatrix, ApiShape, ApiVariable, HighsParallel, LpMethod};
+    use std::collections::HashMap;
+
+    fn polyhedron() -> SparseLEIntegerPolyhedron {
+        SparseLEIntegerPolyhedron {
+            a: ApiIntegerSparseMatrix {
+                rows: vec![0, 0],
+                cols: vec![0, 1],
+                vals: vec![1, 1],
+                shape: ApiShape { nrows: 1, ncols: 2 },
+            },
+            b: vec![1],
+            variables: vec![
+                ApiVariable {
+                    id: "x".into(),
+                    bound: (0, 1),
+                },
+                ApiVariable {
+                    id: "y".into(),
+                    bound: (0, 1),
+                },
+            ],
+        }
+    }
+
+    fn objective(terms: &[(&str, f64)]) -> ObjectiveOwned {
+        terms
+            .iter()
+            .map(|(id, coeff)| (id.to_string(), *coeff))
+            .collect::<HashMap<_, _>>()
+    }
+
+    fn tag(
+        objectives: &[ObjectiveOwned],
+        direction: SolverDirection,
+        options: &SolveOptions,
+    ) -> String {
+        fingerprint(&polyhedron(), objectives, direction, "GLPK", options)
+    }
+
+    #[test]
+    fn test_fingerprint_is_stable_across_key_order() {
+        let options = SolveOptions::with_presolve(true);
+        let a = tag(
+            &[objective(&[("x", 1.0), ("y", 2.0)])],
+            SolverDirection::Maximize,
+            &options,
+        );
+        let b = tag(
+            &[objective(&[("y", 2.0), ("x", 1.0)])],
+            SolverDirection::Maximize,
+            &options,
+        );
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_fingerprint_depends_on_problem_and_settings() {
+        let objectives = [objective(&[("x", 1.0)])];
+        let options = SolveOptions::with_presolve(true);
+        let base = tag(&objectives, SolverDirection::Maximize, &options);
+
+        let minimize = tag(&objectives, SolverDirection::Minimize, &options);
+        let no_presolve = tag(
+            &objectives,
+            SolverDirection::Maximize,
+            &SolveOptions::with_presolve(false),
+        );
+        let other_objective = tag(
+            &[objective(&[("x", 1.5)])],
+            SolverDirection::Maximize,
+            &options,
+        );
+        let exact = tag(
+            &objectives,
+            SolverDirection::Maximize,
+            &SolveOptions {
+                exact_lp: true,
+                ..options.clone()
+            },
+        );
+        let gap = tag(
+            &objectives,
+            SolverDirection::Maximize,
+            &SolveOptions {
+                mip_rel_gap: Some(0.01),
+                ..options.clone()
+            },
+        );
+        let method = tag(
+            &objectives,
+            SolverDirection::Maximize,
+            &SolveOptions {
+                method: Some(LpMethod::Barrier),
+                ..options.clone()
+            },
+        );
+        let highs = tag(
+            &objectives,
+            SolverDirection::Maximize,
+            &SolveOptions {
+                highs: HighsOptions {
+                    parallel: Some(HighsParallel::Off),
+                    ..HighsOptions::default()
+                },
+                ..options.clone()
+            },
+        );
+
+        assert_ne!(base, minimize);
+        assert_ne!(base, no_presolve);
+        assert_ne!(base, other_objective);
+        assert_ne!(base, exact);
+        assert_ne!(base, gap);
+        assert_ne!(base, method);
+        assert_ne!(base, highs);
+    }
+
+    #[test]
+    fn test_recent_solves_expire() {
+        let recent = RecentSolves::new(NonZeroUsize::new(4).unwrap(), Duration::ZERO);
+        recent.insert("\"tag\"");
+        std::thread::sleep(Duration::from_millis(2));
+        assert!(!recent.contains("\"tag\""));
+
+        let recent = RecentSolves::new(NonZeroUsize::new(4).unwrap(), Duration::from_secs(60));
+        recent.insert("\"tag\"");
+        assert!(recent.contains("\"tag\""));
+        assert!(!recent.contains("\"other\""));
+    }
+}
@@ -0,0 +1,155 @@
+use crate::domain::solver::{SolveOptions, Solver};
+use crate::domain::solver_factory::{create_solver_with_cache, SolverType};
+use crate::models::{
+    ApiIntegerSparseMatrix, ApiSelfTest, ApiShape, ApiVariable, SolverDirection,
+    SparseLEIntegerPolyhedron, Status,
+};
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+
+/// Results of the startup self-tests, `None` while they are still running
+#[derive(Default)]
+pub struct SelfTests {
+    results: RwLock<Option<Vec<ApiSelfTest>>>,
+}
+
+impl SelfTests {
+    pub fn record(&self, results: Vec<ApiSelfTest>) {
+        *self.results.write() = Some(results);
+    }
+
+    pub fn results(&self) -> Option<Vec<ApiSelfTest>> {
+        self.results.read().clone()
+    }
+}
+
+/// Solve the canned model on each backend
+///
+/// Catches what a user's first request would otherwise run into, such as a
+/// missing license or shared library. Backends needing a license contact
+/// the license server, so call this from a blocking context.
+pub fn run_self_tests(solver_types: &[SolverType]) -> Vec<ApiSelfTest> {
+    solver_types
+        .iter()
+        .map(|&solver_type| {
+            let solver = create_solver_with_cache(solver_type, None);
+            let result = self_test(solver.as_ref());
+            ApiSelfTest {
+                solver: solver.name().to_string(),
+                passed: result.is_ok(),
+                error: result.err(),
+            }
+        })
+        .collect()
+}
+
+/// Maximize x + 2y subject to x + y <= 1 with binary x and y, whose only
+/// optimum is x = 0, y = 1
+fn canned_model() -> SparseLEIntegerPolyhedron {
+    SparseLEIntegerPolyhedron {
+        a: ApiIntegerSparseMatrix {
+            rows: vec![0, 0],
+            cols: vec![0, 1],
+            vals: vec![1, 1],
+            shape: ApiShape { nrows: 1, ncols: 2 },
+        },
+        b: vec![1],
+        variables: vec![
+            ApiVariable {
+                id: "x".to_string(),
+                bound: (0, 1),
+            },
+            ApiVariable {
+                id: "y".to_string(),
+                bound: (0, 1),
+            },
+        ],
+    }
+}
+
+/// Solve the canned model with `solver` and check the answer
+pub fn self_test(solver: &dyn Solver) -> Result<(), String> {
+    let objective = HashMap::from([("x".to_string(), 1.0), ("y".to_string(), 2.0)]);
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+        solver.solve(
+            canned_model(),
+            vec![objective],
+            SolverDirection::Maximize,
+            &SolveOptions::with_presolve(true),
+        )
+    }))
+    .map_err(|_| "Solver panicked".to_string())?
+    .map_err(|e| e.details)?;
+
+    let solution = outcome
+        .solutions
+        .first()
+        .ok_or_else(|| "No solution returned".to_string())?;
+    if !matches!(solution.status, Status::Optimal) {
+        let status = serde_json::to_string(&solution.status).unwrap_or_default();
+        return Err(format!(
+            "Expected an optimal solution, got status {}",
+            status.trim_matches('"')
+        ));
+    }
+    let values = (solution.solution.get("x"), solution.solution.get("y"));
+    if values != (Some(&0), Some(&1)) || (solution.objective - 2.0).abs() > 1e-6 {
+        return Err(format!(
+            "Expected x = 0, y = 1 with objective 2, got {:?} with objective {}",
+            values, solution.objective
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::solver::SolveOutcome;
+    use crate::domain::validate::SolveInputError;
+    use crate::models::ApiSolution;
+    use std::time::Instant;
+
+    /// Answers every model with x = 1, y = 0
+    struct WrongSolver;
+
+    impl Solver for WrongSolver {
+        fn solve(
+            &self,
+            _polyhedron: SparseLEIntegerPolyhedron,
+            _objectives: Vec<HashMap<String, f64>>,
+            _direction: SolverDirection,
+            _options: &SolveOptions,
+        ) -> Result<SolveOutcome, SolveInputError> {
+            let solution = ApiSolution {
+                status: Status::Optimal,
+                objective: 1.0,
+                solution: HashMap::from([("x".to_string(), 1), ("y".to_string(), 0)]),
+                error: None,
+            };
+            Ok(SolveOutcome::finished(vec![solution], Instant::now()))
+        }
+
+        fn name(&self) -> &str {
+            "wrong"
+        }
+    }
+
+    #[test]
+    fn test_microlp_passes_self_test() {
+        let results = run_self_tests(&[SolverType::Microlp]);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].solver, "microlp");
+        assert!(results[0].passed, "{:?}", results[0].error);
+    }
+
+    #[test]
+    fn test_reports_wrong_answer() {
+        let error = self_test(&WrongSolver).unwrap_err();
+        assert_eq!(
+            error,
+            "Expected x = 0, y = 1 with objective 2, got (Some(1), Some(0)) with objective 1"
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use std::fmt;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+/// Why a job could not be run to completion
+#[derive(Debug)]
+pub enum ExecutorError {
+    /// The executor is shutting down and accepts no more work
+    Closed(String),
+    /// The job panicked or was aborted
+    Failed(String),
+}
+
+impl fmt::Display for ExecutorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExecutorError::Closed(reason) => write!(f, "Executor closed: {}", reason),
+            ExecutorError::Failed(reason) => write!(f, "Solve did not complete: {}", reason),
+        }
+    }
+}
+
+/// Runs solver calls on the blocking thread pool
+///
+/// Solvers are synchronous and can run for minutes, so calling them on the
+/// async runtime would stall every other request on that worker, including
+/// `/health`. All solves go through here instead, which moves them to
+/// dedicated threads and caps how many run at once.
+pub struct SolveExecutor {
+    permits: Arc<Semaphore>,
+}
+
+impl SolveExecutor {
+    /// Create an executor running at most `max_concurrent` jobs at a time
+    pub fn new(max_concurrent: usize) -> Self {
+        SolveExecutor {
+            permits: Arc::new(Semaphore::new(max_concurrent)),
+        }
+    }
+
+    /// Run `job` on a blocking thread once a slot is free
+    pub async fn run<T, F>(&self, job: F) -> Result<T, ExecutorError>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        // Wait for a slot without holding a thread
+        let permit = Arc::clone(&self.permits)
+            .acquire_owned()
+            .await
+            .map_err(|e| ExecutorError::Closed(e.to_string()))?;
+
+        tokio::task::spawn_blocking(move || {
+            // Held until the job returns, even if the caller stops waiting
+            let _permit = permit;
+            job()
+        })
+        .await
+        .map_err(|e| ExecutorError::Failed(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[actix_web::test]
+    async fn test_limits_concurrent_jobs() {
+        let executor = Arc::new(SolveExecutor::new(2));
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let jobs: Vec<_> = (0..6)
+            .map(|_| {
+                let executor = Arc::clone(&executor);
+                let running = Arc::clone(&running);
+                let peak = Arc::clone(&peak);
+                actix_web::rt::spawn(async move {
+                    executor
+                        .run(move || {
+                            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                            peak.fetch_max(now, Ordering::SeqCst);
+                            std::thread::sleep(Duration::from_millis(20));
+                            running.fetch_sub(1, Ordering::SeqCst);
+                        })
+                        .await
+                })
+            })
+            .collect();
+        for job in jobs {
+            job.await.unwrap().unwrap();
+        }
+
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[actix_web::test]
+    async fn test_reports_panicking_jobs() {
+        let executor = SolveExecutor::new(1);
+        let result = executor.run(|| panic!("solver crashed")).await;
+        assert!(matches!(result, Err(ExecutorError::Failed(_))));
+
+        // The slot is released again
+        assert_eq!(executor.run(|| 42).await.unwrap(), 42);
+    }
+}
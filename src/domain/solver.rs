@@ -1,6 +1,204 @@
+use crate::domain::cancellation::CancellationToken;
 use crate::domain::validate::SolveInputError;
-use crate::models::{ApiSolution, SolverDirection, SparseLEIntegerPolyhedron};
+use crate::models::{
+    ApiHighsOptions, ApiLicenseStatus, ApiSolution, ApiSolverOption, HighsParallel, LpMethod,
+    SolveRequest, SolverDefaults, SolverDirection, SparseLEIntegerPolyhedron, Status,
+};
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Per-call settings passed to every backend
+#[derive(Clone, Default)]
+pub struct SolveOptions {
+    /// Enable/disable presolve optimization
+    pub use_presolve: bool,
+    /// Stops the solve early when cancelled, e.g. because the client went away
+    pub cancel: CancellationToken,
+    /// Wall-clock budget for the whole call, across all objectives
+    pub time_limit: Option<Duration>,
+    /// Capture the backend's log into `SolveOutcome::log`
+    pub include_log: bool,
+    /// Solve the LP relaxation in exact arithmetic, for ill-conditioned models
+    pub exact_lp: bool,
+    /// Relative MIP gap at which the search may stop, `None` for the
+    /// backend's default
+    pub mip_rel_gap: Option<f64>,
+    /// Threading and simplex settings for the HiGHS backend
+    pub highs: HighsOptions,
+    /// Backend parameters by name; backends without passthrough ignore them
+    pub solver_options: HashMap<String, ApiSolverOption>,
+    /// LP algorithm, `None` for the backend's choice
+    pub method: Option<LpMethod>,
+}
+
+impl SolveOptions {
+    pub fn with_presolve(use_presolve: bool) -> Self {
+        SolveOptions {
+            use_presolve,
+            ..Default::default()
+        }
+    }
+
+    /// The options a request asks for, with its HiGHS settings over
+    /// `highs_defaults`
+    pub fn for_request(
+        req: &SolveRequest,
+        use_presolve: bool,
+        highs_defaults: &HighsOptions,
+    ) -> Self {
+        SolveOptions {
+            // Checked by validate_solve_request
+            time_limit: req.time_limit_seconds.map(Duration::from_secs_f64),
+            mip_rel_gap: req.mip_rel_gap,
+            include_log: req.include_log,
+            exact_lp: req.exact_lp,
+            highs: highs_defaults.overridden_by(req.highs),
+            solver_options: req.solver_options.clone(),
+            method: req.method,
+            ..SolveOptions::with_presolve(use_presolve)
+        }
+    }
+
+    /// These options with a backend's server-wide defaults filled in where
+    /// the request left them unset
+    pub fn with_defaults(mut self, defaults: &SolverDefaults) -> Self {
+        self.time_limit = self
+            .time_limit
+            .or(defaults.time_limit_seconds.map(Duration::from_secs_f64));
+        self.mip_rel_gap = self.mip_rel_gap.or(defaults.mip_rel_gap);
+        for (name, value) in &defaults.solver_options {
+            self.solver_options
+                .entry(name.clone())
+                .or_insert_with(|| value.clone());
+        }
+        self
+    }
+
+    /// Time left at `now` for a solve started at `started`
+    ///
+    /// `None` when there is no time limit.
+    pub fn remaining_time(&self, started: Instant) -> Option<Duration> {
+        self.time_limit
+            .map(|limit| limit.saturating_sub(started.elapsed()))
+    }
+
+    /// Fail with a "cancelled" error once the token has been cancelled
+    pub fn check_cancelled(&self) -> Result<(), SolveInputError> {
+        if self.cancel.is_cancelled() {
+            return Err(SolveInputError {
+                details: "Solve cancelled".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// HiGHS settings; `None` keeps HiGHS's own default
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct HighsOptions {
+    /// Size of HiGHS's global thread pool. Server-wide only, as HiGHS sizes
+    /// the pool on the first solve.
+    pub threads: Option<u32>,
+    pub parallel: Option<HighsParallel>,
+    pub simplex_strategy: Option<i32>,
+}
+
+impl HighsOptions {
+    /// These settings with a request's overrides applied
+    pub fn overridden_by(&self, request: Option<ApiHighsOptions>) -> Self {
+        let request = request.unwrap_or_default();
+        HighsOptions {
+            threads: self.threads,
+            parallel: request.parallel.or(self.parallel),
+            simplex_strategy: request.simplex_strategy.or(self.simplex_strategy),
+        }
+    }
+
+    /// The settings a request can carry, e.g. to forward them to a worker
+    pub fn request_overrides(&self) -> Option<ApiHighsOptions> {
+        let overrides = ApiHighsOptions {
+            parallel: self.parallel,
+            simplex_strategy: self.simplex_strategy,
+        };
+        (overrides != ApiHighsOptions::default()).then_some(overrides)
+    }
+}
+
+/// Why the solver stopped
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TerminationReason {
+    /// Every objective reached a final status
+    Completed,
+    /// The time limit stopped at least one objective early
+    TimeLimit,
+    /// The backend failed on at least one objective
+    Failed,
+}
+
+/// Size of the model removed by presolve
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PresolveReductions {
+    pub rows_removed: usize,
+    pub cols_removed: usize,
+}
+
+/// Solver-level statistics for one `solve` call
+#[derive(Clone, Debug)]
+pub struct SolveStats {
+    pub total_time: Duration,
+    /// `None` when presolve was off or the backend doesn't report it
+    pub presolve_reductions: Option<PresolveReductions>,
+    pub termination: TerminationReason,
+}
+
+/// Result of a `solve` call: one solution per objective plus statistics
+pub struct SolveOutcome {
+    pub solutions: Vec<ApiSolution>,
+    pub stats: SolveStats,
+    /// Solver log, when requested with `SolveOptions::include_log` and the
+    /// backend can capture it
+    pub log: Option<String>,
+}
+
+impl SolveOutcome {
+    /// Outcome of a solve started at `started`, ending now
+    pub fn finished(solutions: Vec<ApiSolution>, started: Instant) -> Self {
+        let failed = solutions.iter().any(|solution| {
+            matches!(
+                solution.status,
+                Status::Undefined | Status::SimplexFailed | Status::MIPFailed
+            )
+        });
+        let timed_out = solutions
+            .iter()
+            .any(|solution| matches!(solution.status, Status::TimeLimit));
+        SolveOutcome {
+            solutions,
+            stats: SolveStats {
+                total_time: started.elapsed(),
+                presolve_reductions: None,
+                termination: if failed {
+                    TerminationReason::Failed
+                } else if timed_out {
+                    TerminationReason::TimeLimit
+                } else {
+                    TerminationReason::Completed
+                },
+            },
+            log: None,
+        }
+    }
+
+    pub fn with_presolve_reductions(mut self, reductions: Option<PresolveReductions>) -> Self {
+        self.stats.presolve_reductions = reductions;
+        self
+    }
+
+    pub fn with_log(mut self, log: Option<String>) -> Self {
+        self.log = log;
+        self
+    }
+}
 
 /// Common interface for LP/ILP solvers
 pub trait Solver: Send + Sync {
@@ -10,18 +208,102 @@ pub trait Solver: Send + Sync {
     /// * `polyhedron` - The constraint polyhedron (Ax <= b with variable bounds)
     /// * `objectives` - List of objective functions to optimize
     /// * `direction` - Maximize or Minimize
-    /// * `use_presolve` - Enable/disable presolve optimization
+    /// * `options` - Presolve setting and cancellation token
     ///
     /// # Returns
-    /// A vector of solutions, one for each objective function
+    /// One solution for each objective function, plus solver statistics
     fn solve(
         &self,
         polyhedron: SparseLEIntegerPolyhedron,
         objectives: Vec<HashMap<String, f64>>,
         direction: SolverDirection,
-        use_presolve: bool,
-    ) -> Result<Vec<ApiSolution>, SolveInputError>;
+        options: &SolveOptions,
+    ) -> Result<SolveOutcome, SolveInputError>;
+
+    /// Solve like `solve`, also returning the name of the backend that answered
+    ///
+    /// Differs from `name()` for solvers that delegate, such as a fallback chain.
+    fn solve_attributed(
+        &self,
+        polyhedron: SparseLEIntegerPolyhedron,
+        objectives: Vec<HashMap<String, f64>>,
+        direction: SolverDirection,
+        options: &SolveOptions,
+    ) -> Result<(SolveOutcome, String), SolveInputError> {
+        let outcome = self.solve(polyhedron, objectives, direction, options)?;
+        Ok((outcome, self.name().to_string()))
+    }
 
     /// Get the solver name for logging/debugging
     fn name(&self) -> &str;
+
+    /// Probe the license of backends that need one
+    ///
+    /// Returns `None` for open-source backends. This may contact a license
+    /// server, so call it from a blocking context.
+    fn license_status(&self) -> Option<ApiLicenseStatus> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_overrides_highs_defaults() {
+        let defaults = HighsOptions {
+            threads: Some(8),
+            parallel: Some(HighsParallel::On),
+            simplex_strategy: Some(2),
+        };
+        let request = ApiHighsOptions {
+            parallel: None,
+            simplex_strategy: Some(4),
+        };
+
+        let merged = defaults.overridden_by(Some(request));
+        assert_eq!(merged.threads, Some(8));
+        assert_eq!(merged.parallel, Some(HighsParallel::On));
+        assert_eq!(merged.simplex_strategy, Some(4));
+        assert_eq!(defaults.overridden_by(None), defaults);
+
+        // Threads stay with the server
+        assert_eq!(
+            merged.request_overrides(),
+            Some(ApiHighsOptions {
+                parallel: Some(HighsParallel::On),
+                simplex_strategy: Some(4),
+            })
+        );
+        assert_eq!(HighsOptions::default().request_overrides(), None);
+    }
+
+    #[test]
+    fn test_request_options_override_solver_defaults() {
+        let defaults = SolverDefaults {
+            time_limit_seconds: Some(30.0),
+            mip_rel_gap: Some(0.001),
+            solver_options: HashMap::from([
+                ("MIPFocus".to_string(), ApiSolverOption::Int(1)),
+                ("Threads".to_string(), ApiSolverOption::Int(8)),
+            ]),
+        };
+        let request = SolveOptions {
+            mip_rel_gap: Some(0.05),
+            solver_options: HashMap::from([("MIPFocus".to_string(), ApiSolverOption::Int(2))]),
+            ..Default::default()
+        };
+
+        let merged = request.with_defaults(&defaults);
+        assert_eq!(merged.time_limit, Some(Duration::from_secs(30)));
+        assert_eq!(merged.mip_rel_gap, Some(0.05));
+        assert_eq!(
+            merged.solver_options,
+            HashMap::from([
+                ("MIPFocus".to_string(), ApiSolverOption::Int(2)),
+                ("Threads".to_string(), ApiSolverOption::Int(8)),
+            ])
+        );
+    }
 }
@@ -1,5 +1,9 @@
 use crate::domain::solver::Solver;
+use crate::domain::solvers::{FallbackSolver, MicrolpSolver};
+
+#[cfg(feature = "glpk-solver")]
 use crate::domain::solvers::GlpkSolver;
+use std::fmt;
 
 #[cfg(feature = "highs-solver")]
 use crate::domain::solvers::HighsSolver;
@@ -7,44 +11,169 @@ use crate::domain::solvers::HighsSolver;
 #[cfg(feature = "gurobi-solver")]
 use crate::domain::solvers::GurobiSolver;
 
+#[cfg(feature = "lpsolve-solver")]
+use crate::domain::solvers::LpSolveSolver;
+
+#[cfg(feature = "cplex-solver")]
+use crate::domain::solvers::CplexSolver;
+
 /// Available solver backends
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum SolverType {
+    #[cfg(feature = "glpk-solver")]
     Glpk,
+    Microlp,
     #[cfg(feature = "highs-solver")]
     Highs,
     #[cfg(feature = "gurobi-solver")]
     Gurobi,
+    #[cfg(feature = "lpsolve-solver")]
+    LpSolve,
+    #[cfg(feature = "cplex-solver")]
+    Cplex,
+}
+
+/// Reason a solver name could not be turned into a `SolverType`
+#[derive(Debug, PartialEq, Eq)]
+pub enum SolverTypeError {
+    /// The name does not match any known backend
+    Unknown(String),
+    /// The backend exists but this binary was built without its feature flag
+    NotCompiled { name: String, feature: &'static str },
+}
+
+impl fmt::Display for SolverTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolverTypeError::Unknown(name) => write!(f, "Unknown solver '{}'", name),
+            SolverTypeError::NotCompiled { name, feature } => write!(
+                f,
+                "Solver '{}' is not available in this build. Enable using `--features {}`",
+                name, feature
+            ),
+        }
+    }
 }
 
 impl SolverType {
     /// Parse solver type from string (case-insensitive)
     pub fn from_str(s: &str) -> Option<Self> {
-        match s.to_lowercase().as_str() {
-            "glpk" => Some(SolverType::Glpk),
+        match Self::try_from_name(s) {
+            Ok(solver_type) => Some(solver_type),
+            Err(SolverTypeError::NotCompiled { name, feature }) => panic!(
+                "{} solver specified in environment but feature flag not present. Enable using `--features {}`",
+                name, feature
+            ),
+            Err(SolverTypeError::Unknown(_)) => None,
+        }
+    }
+
+    /// Lowercase name accepted by `try_from_name`
+    pub fn name(&self) -> &'static str {
+        match self {
+            #[cfg(feature = "glpk-solver")]
+            SolverType::Glpk => "glpk",
+            SolverType::Microlp => "microlp",
+            #[cfg(feature = "highs-solver")]
+            SolverType::Highs => "highs",
+            #[cfg(feature = "gurobi-solver")]
+            SolverType::Gurobi => "gurobi",
+            #[cfg(feature = "lpsolve-solver")]
+            SolverType::LpSolve => "lpsolve",
+            #[cfg(feature = "cplex-solver")]
+            SolverType::Cplex => "cplex",
+        }
+    }
+
+    /// All backends compiled into this binary
+    pub fn all() -> Vec<SolverType> {
+        #[allow(unused_mut)]
+        let mut types = vec![
+            #[cfg(feature = "glpk-solver")]
+            SolverType::Glpk,
+            SolverType::Microlp,
+        ];
+        #[cfg(feature = "highs-solver")]
+        types.push(SolverType::Highs);
+        #[cfg(feature = "gurobi-solver")]
+        types.push(SolverType::Gurobi);
+        #[cfg(feature = "lpsolve-solver")]
+        types.push(SolverType::LpSolve);
+        #[cfg(feature = "cplex-solver")]
+        types.push(SolverType::Cplex);
+        types
+    }
+
+    /// Parse solver type from string (case-insensitive) without panicking on
+    /// backends that were not compiled in. Use this for runtime input.
+    pub fn try_from_name(s: &str) -> Result<Self, SolverTypeError> {
+        match s.trim().to_lowercase().as_str() {
+            #[cfg(feature = "glpk-solver")]
+            "glpk" => Ok(SolverType::Glpk),
+            #[cfg(not(feature = "glpk-solver"))]
+            "glpk" => Err(SolverTypeError::NotCompiled {
+                name: "GLPK".to_string(),
+                feature: "glpk-solver",
+            }),
+            "microlp" => Ok(SolverType::Microlp),
             #[cfg(feature = "highs-solver")]
-            "highs" => Some(SolverType::Highs),
+            "highs" => Ok(SolverType::Highs),
             #[cfg(not(feature = "highs-solver"))]
-            "highs" => panic!("Highs solver specified in environment but feature flag not present. Enable using `--features highs-solver`"),
+            "highs" => Err(SolverTypeError::NotCompiled {
+                name: "Highs".to_string(),
+                feature: "highs-solver",
+            }),
             #[cfg(feature = "gurobi-solver")]
-            "gurobi" => Some(SolverType::Gurobi),
+            "gurobi" => Ok(SolverType::Gurobi),
             #[cfg(not(feature = "gurobi-solver"))]
-            "gurobi" => panic!("Gurobi solver specified in environment but feature flag not present. Enable using `--features gurobi-solver`"),
-            _ => None,
+            "gurobi" => Err(SolverTypeError::NotCompiled {
+                name: "Gurobi".to_string(),
+                feature: "gurobi-solver",
+            }),
+            #[cfg(feature = "lpsolve-solver")]
+            "lpsolve" | "lp_solve" => Ok(SolverType::LpSolve),
+            #[cfg(not(feature = "lpsolve-solver"))]
+            "lpsolve" | "lp_solve" => Err(SolverTypeError::NotCompiled {
+                name: "lp_solve".to_string(),
+                feature: "lpsolve-solver",
+            }),
+            #[cfg(feature = "cplex-solver")]
+            "cplex" => Ok(SolverType::Cplex),
+            #[cfg(not(feature = "cplex-solver"))]
+            "cplex" => Err(SolverTypeError::NotCompiled {
+                name: "CPLEX".to_string(),
+                feature: "cplex-solver",
+            }),
+            _ => Err(SolverTypeError::Unknown(s.to_string())),
         }
     }
 }
 
+impl Default for SolverType {
+    /// GLPK when compiled in, otherwise the embedded microlp backend
+    fn default() -> Self {
+        #[cfg(feature = "glpk-solver")]
+        return SolverType::Glpk;
+        #[cfg(not(feature = "glpk-solver"))]
+        return SolverType::Microlp;
+    }
+}
+
 /// Create a solver instance with specified cache size
 pub fn create_solver_with_cache(
     solver_type: SolverType,
     cache_size: Option<usize>,
 ) -> Box<dyn Solver> {
     match solver_type {
+        #[cfg(feature = "glpk-solver")]
         SolverType::Glpk => match cache_size {
             Some(size) => Box::new(GlpkSolver::with_cache_size(Some(size))),
             None => Box::new(GlpkSolver::without_cache()),
         },
+        SolverType::Microlp => match cache_size {
+            Some(size) => Box::new(MicrolpSolver::with_cache_size(Some(size))),
+            None => Box::new(MicrolpSolver::without_cache()),
+        },
         #[cfg(feature = "highs-solver")]
         SolverType::Highs => match cache_size {
             Some(size) => Box::new(HighsSolver::with_cache_size(Some(size))),
@@ -55,7 +184,34 @@ pub fn create_solver_with_cache(
             Some(size) => Box::new(GurobiSolver::with_cache_size(Some(size))),
             None => Box::new(GurobiSolver::without_cache()),
         },
+        #[cfg(feature = "lpsolve-solver")]
+        SolverType::LpSolve => match cache_size {
+            Some(size) => Box::new(LpSolveSolver::with_cache_size(Some(size))),
+            None => Box::new(LpSolveSolver::without_cache()),
+        },
+        #[cfg(feature = "cplex-solver")]
+        SolverType::Cplex => match cache_size {
+            Some(size) => Box::new(CplexSolver::with_cache_size(Some(size))),
+            None => Box::new(CplexSolver::without_cache()),
+        },
+    }
+}
+
+/// Create a solver that tries each backend in order until one answers
+///
+/// A single backend is returned as is.
+pub fn create_solver_chain(
+    solver_types: &[SolverType],
+    cache_size: Option<usize>,
+) -> Box<dyn Solver> {
+    let mut solvers: Vec<Box<dyn Solver>> = solver_types
+        .iter()
+        .map(|&solver_type| create_solver_with_cache(solver_type, cache_size))
+        .collect();
+    if solvers.len() == 1 {
+        return solvers.remove(0);
     }
+    Box::new(FallbackSolver::new(solvers))
 }
 
 #[cfg(test)]
@@ -68,8 +224,11 @@ mod tests {
 
     #[test]
     fn test_solver_type_from_str() {
+        #[cfg(feature = "glpk-solver")]
         assert_eq!(SolverType::from_str("glpk"), Some(SolverType::Glpk));
+        #[cfg(feature = "glpk-solver")]
         assert_eq!(SolverType::from_str("GLPK"), Some(SolverType::Glpk));
+        assert_eq!(SolverType::from_str("microlp"), Some(SolverType::Microlp));
         #[cfg(feature = "highs-solver")]
         assert_eq!(SolverType::from_str("highs"), Some(SolverType::Highs));
         #[cfg(feature = "highs-solver")]
@@ -81,12 +240,72 @@ mod tests {
         assert_eq!(SolverType::from_str("unknown"), None);
     }
 
+    #[test]
+    fn test_solver_type_try_from_name() {
+        #[cfg(feature = "glpk-solver")]
+        assert_eq!(SolverType::try_from_name(" glpk "), Ok(SolverType::Glpk));
+        assert_eq!(
+            SolverType::try_from_name("MicroLP"),
+            Ok(SolverType::Microlp)
+        );
+        assert_eq!(
+            SolverType::try_from_name("unknown"),
+            Err(SolverTypeError::Unknown("unknown".to_string()))
+        );
+        #[cfg(not(feature = "gurobi-solver"))]
+        assert!(matches!(
+            SolverType::try_from_name("gurobi"),
+            Err(SolverTypeError::NotCompiled { .. })
+        ));
+    }
+
+    #[test]
+    fn test_name_round_trips() {
+        for solver_type in SolverType::all() {
+            assert_eq!(
+                SolverType::try_from_name(solver_type.name()),
+                Ok(solver_type)
+            );
+        }
+    }
+
+    #[cfg(feature = "glpk-solver")]
+    #[test]
+    fn test_all_includes_glpk() {
+        assert!(SolverType::all().contains(&SolverType::Glpk));
+    }
+
+    #[test]
+    fn test_all_includes_microlp() {
+        assert!(SolverType::all().contains(&SolverType::Microlp));
+    }
+
+    #[cfg(feature = "glpk-solver")]
+    #[test]
+    fn test_default_is_glpk() {
+        assert_eq!(SolverType::default(), SolverType::Glpk);
+    }
+
+    #[cfg(feature = "glpk-solver")]
+    #[test]
+    fn test_glpk_has_no_license() {
+        let solver = create_solver(SolverType::Glpk);
+        assert!(solver.license_status().is_none());
+    }
+
+    #[cfg(feature = "glpk-solver")]
     #[test]
     fn test_create_glpk_solver() {
         let solver = create_solver(SolverType::Glpk);
         assert_eq!(solver.name(), "GLPK");
     }
 
+    #[test]
+    fn test_create_microlp_solver() {
+        let solver = create_solver(SolverType::Microlp);
+        assert_eq!(solver.name(), "microlp");
+    }
+
     #[cfg(feature = "highs-solver")]
     #[test]
     fn test_create_highs_solver() {
@@ -100,4 +319,30 @@ mod tests {
         let solver = create_solver(SolverType::Gurobi);
         assert_eq!(solver.name(), "Gurobi");
     }
+
+    #[cfg(feature = "lpsolve-solver")]
+    #[test]
+    fn test_create_lpsolve_solver() {
+        let solver = create_solver(SolverType::LpSolve);
+        assert_eq!(solver.name(), "lp_solve");
+    }
+
+    #[cfg(feature = "cplex-solver")]
+    #[test]
+    fn test_create_cplex_solver() {
+        let solver = create_solver(SolverType::Cplex);
+        assert_eq!(solver.name(), "CPLEX");
+    }
+
+    #[test]
+    fn test_create_solver_chain() {
+        assert_eq!(
+            create_solver_chain(&[SolverType::Microlp], None).name(),
+            "microlp"
+        );
+        assert_eq!(
+            create_solver_chain(&[SolverType::Microlp, SolverType::Microlp], None).name(),
+            "microlp > microlp"
+        );
+    }
 }
@@ -0,0 +1,254 @@
+use crate::domain::solver::Solver;
+use crate::domain::solver_factory::{create_solver_with_cache, SolverType, SolverTypeError};
+use crate::domain::solvers::{RacingSolver, RemoteSolver};
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Holds the solver used for incoming solve requests.
+///
+/// The default backend can be swapped at runtime (e.g. from the admin API)
+/// without restarting the server. Requests already running keep the solver
+/// instance they started with, since each one holds its own `Arc`.
+pub struct SolverRegistry {
+    cache_size: Option<usize>,
+    /// `None` when the default is not a local backend (e.g. remote dispatch)
+    default: RwLock<(Option<SolverType>, Arc<dyn Solver>)>,
+    /// Backends requested explicitly, created on first use
+    backends: RwLock<HashMap<SolverType, Arc<dyn Solver>>>,
+    race: Option<Arc<dyn Solver>>,
+    /// Worker dispatch, when solves are forwarded instead of run here
+    remote: Option<RemoteSolver>,
+}
+
+impl SolverRegistry {
+    /// Create a registry with the given default backend and model cache size
+    pub fn new(solver_type: SolverType, cache_size: Option<usize>) -> Self {
+        let solver: Arc<dyn Solver> = Arc::from(create_solver_with_cache(solver_type, cache_size));
+        SolverRegistry {
+            cache_size,
+            default: RwLock::new((Some(solver_type), solver)),
+            backends: RwLock::new(HashMap::new()),
+            race: None,
+            remote: None,
+        }
+    }
+
+    /// Create a registry whose default solver is not one of the local
+    /// backends, such as a `RemoteSolver` dispatching to worker instances
+    pub fn with_solver(solver: Arc<dyn Solver>, cache_size: Option<usize>) -> Self {
+        SolverRegistry {
+            cache_size,
+            default: RwLock::new((None, solver)),
+            backends: RwLock::new(HashMap::new()),
+            race: None,
+            remote: None,
+        }
+    }
+
+    /// Create a registry forwarding solves to worker instances
+    pub fn with_remote(remote: RemoteSolver, cache_size: Option<usize>) -> Self {
+        SolverRegistry {
+            remote: Some(remote.clone()),
+            ..Self::with_solver(Arc::new(remote), cache_size)
+        }
+    }
+
+    /// Worker dispatch for requests naming a backend, which the workers
+    /// are asked to solve with
+    ///
+    /// `None` without workers, and for names that aren't a backend. The
+    /// backend doesn't need to be compiled into this server.
+    pub fn remote_solver(&self, name: &str) -> Option<Arc<dyn Solver>> {
+        let remote = self.remote.as_ref()?;
+        let backend = match SolverType::try_from_name(name) {
+            Ok(solver_type) => solver_type.name().to_string(),
+            Err(SolverTypeError::NotCompiled { .. }) => name.trim().to_lowercase(),
+            Err(SolverTypeError::Unknown(_)) => return None,
+        };
+        Some(Arc::new(remote.for_backend(&backend)))
+    }
+
+    /// Solver used for requests that don't ask for a specific backend
+    pub fn default_solver(&self) -> Arc<dyn Solver> {
+        Arc::clone(&self.default.read().1)
+    }
+
+    /// Solver for requests that ask for a specific backend
+    ///
+    /// Shares the default solver's instance when it is that backend, so the
+    /// model cache is not duplicated.
+    pub fn solver(&self, solver_type: SolverType) -> Arc<dyn Solver> {
+        {
+            let default = self.default.read();
+            if default.0 == Some(solver_type) {
+                return Arc::clone(&default.1);
+            }
+        }
+        if let Some(solver) = self.backends.read().get(&solver_type) {
+            return Arc::clone(solver);
+        }
+        let mut backends = self.backends.write();
+        Arc::clone(
+            backends.entry(solver_type).or_insert_with(|| {
+                Arc::from(create_solver_with_cache(solver_type, self.cache_size))
+            }),
+        )
+    }
+
+    /// Allow requests to race the given backends against each other
+    pub fn enable_race(&mut self, solver_types: &[SolverType]) {
+        let solvers = solver_types
+            .iter()
+            .map(|&solver_type| self.solver(solver_type))
+            .collect();
+        self.race = Some(Arc::new(RacingSolver::new(solvers)));
+    }
+
+    /// Solver racing the configured backends, if racing is enabled
+    pub fn race_solver(&self) -> Option<Arc<dyn Solver>> {
+        self.race.clone()
+    }
+
+    /// Backend type of the current default solver, if it is a local backend
+    pub fn default_type(&self) -> Option<SolverType> {
+        self.default.read().0
+    }
+
+    /// Replace the default solver, returning the one it replaced.
+    ///
+    /// Switching to the backend that is already the default is a no-op, and a
+    /// backend that requests already asked for keeps its instance, so existing
+    /// model caches are kept. `None` when forwarding to workers, which each
+    /// solve with their own default.
+    pub fn set_default(&self, solver_type: SolverType) -> Option<Arc<dyn Solver>> {
+        if self.remote.is_some() {
+            return None;
+        }
+        let mut default = self.default.write();
+        if default.0 == Some(solver_type) {
+            return Some(Arc::clone(&default.1));
+        }
+
+        let mut backends = self.backends.write();
+        let solver = backends
+            .remove(&solver_type)
+            .unwrap_or_else(|| Arc::from(create_solver_with_cache(solver_type, self.cache_size)));
+        let (previous_type, previous) =
+            std::mem::replace(&mut *default, (Some(solver_type), solver));
+        // Kept for requests that still ask for it
+        if let Some(previous_type) = previous_type {
+            backends.insert(previous_type, Arc::clone(&previous));
+        }
+        Some(previous)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::worker_pool::WorkerPool;
+    use std::time::Duration;
+
+    #[cfg(feature = "glpk-solver")]
+    #[test]
+    fn test_registry_returns_default_solver() {
+        let registry = SolverRegistry::new(SolverType::Glpk, None);
+        assert_eq!(registry.default_type(), Some(SolverType::Glpk));
+        assert_eq!(registry.default_solver().name(), "GLPK");
+    }
+
+    #[cfg(feature = "glpk-solver")]
+    #[test]
+    fn test_set_default_to_same_type_keeps_instance() {
+        let registry = SolverRegistry::new(SolverType::Glpk, None);
+        let before = registry.default_solver();
+        registry.set_default(SolverType::Glpk).unwrap();
+        assert!(Arc::ptr_eq(&before, &registry.default_solver()));
+    }
+
+    #[cfg(feature = "glpk-solver")]
+    #[test]
+    fn test_set_default_replaces_custom_solver() {
+        let custom: Arc<dyn Solver> = Arc::from(create_solver_with_cache(SolverType::Glpk, None));
+        let registry = SolverRegistry::with_solver(Arc::clone(&custom), None);
+        assert_eq!(registry.default_type(), None);
+
+        let previous = registry.set_default(SolverType::Glpk).unwrap();
+        assert!(Arc::ptr_eq(&previous, &custom));
+        assert_eq!(registry.default_type(), Some(SolverType::Glpk));
+    }
+
+    #[cfg(all(feature = "glpk-solver", feature = "highs-solver"))]
+    #[test]
+    fn test_set_default_switches_backend() {
+        let registry = SolverRegistry::new(SolverType::Glpk, None);
+        let previous = registry.set_default(SolverType::Highs).unwrap();
+        assert_eq!(previous.name(), "GLPK");
+        assert_eq!(registry.default_type(), Some(SolverType::Highs));
+        assert_eq!(registry.default_solver().name(), "HiGHS");
+    }
+
+    #[test]
+    fn test_set_default_switches_to_microlp() {
+        let registry = SolverRegistry::new(SolverType::default(), None);
+        registry.set_default(SolverType::Microlp).unwrap();
+        assert_eq!(registry.default_type(), Some(SolverType::Microlp));
+        assert_eq!(registry.default_solver().name(), "microlp");
+    }
+
+    #[test]
+    fn test_set_default_reuses_requested_backend() {
+        let registry = SolverRegistry::new(SolverType::default(), None);
+        let microlp = registry.solver(SolverType::Microlp);
+        let previous = registry.set_default(SolverType::Microlp).unwrap();
+        assert!(Arc::ptr_eq(&registry.default_solver(), &microlp));
+        assert!(Arc::ptr_eq(
+            &registry.solver(SolverType::default()),
+            &previous
+        ));
+    }
+
+    #[test]
+    fn test_set_default_is_rejected_for_remote_dispatch() {
+        let pool = WorkerPool::new(Vec::new(), Duration::from_secs(60));
+        let remote = RemoteSolver::new(Arc::new(pool), None, Duration::from_secs(5));
+        let registry = SolverRegistry::with_remote(remote, None);
+        assert!(registry.set_default(SolverType::Microlp).is_none());
+        assert_eq!(registry.default_solver().name(), "Remote");
+    }
+
+    #[test]
+    fn test_solver_reuses_instances() {
+        let registry = SolverRegistry::new(SolverType::default(), None);
+        assert!(Arc::ptr_eq(
+            &registry.solver(SolverType::default()),
+            &registry.default_solver()
+        ));
+        assert!(Arc::ptr_eq(
+            &registry.solver(SolverType::Microlp),
+            &registry.solver(SolverType::Microlp)
+        ));
+    }
+
+    #[test]
+    fn test_remote_solver_needs_workers_and_a_backend_name() {
+        let registry = SolverRegistry::new(SolverType::default(), None);
+        assert!(registry.remote_solver("microlp").is_none());
+
+        let pool = WorkerPool::new(Vec::new(), Duration::from_secs(60));
+        let remote = RemoteSolver::new(Arc::new(pool), None, Duration::from_secs(5));
+        let registry = SolverRegistry::with_remote(remote, None);
+        assert_eq!(registry.remote_solver("cplex").unwrap().name(), "Remote");
+        assert!(registry.remote_solver("simplex-9000").is_none());
+    }
+
+    #[test]
+    fn test_race_is_disabled_by_default() {
+        let mut registry = SolverRegistry::new(SolverType::default(), None);
+        assert!(registry.race_solver().is_none());
+
+        registry.enable_race(&[SolverType::default(), SolverType::Microlp]);
+        assert!(registry.race_solver().is_some());
+    }
+}
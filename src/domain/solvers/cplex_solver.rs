@@ -0,0 +1,659 @@
+use crate::domain::cancellation::CancellationToken;
+use crate::domain::solver::{SolveOptions, SolveOutcome, Solver};
+use crate::domain::validate::{validate_objectives_owned, SolveInputError};
+use crate::models::{
+    ApiLicenseStatus, ApiSolution, LpMethod, SolverDirection, SparseLEIntegerPolyhedron, Status,
+};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int, c_longlong, c_void};
+use std::time::{Duration, Instant};
+
+/// Bindings to the parts of the CPLEX Callable Library used here
+mod ffi {
+    use std::os::raw::{c_char, c_int, c_longlong, c_void};
+
+    #[repr(C)]
+    pub struct CpxEnv {
+        _private: [u8; 0],
+    }
+
+    #[repr(C)]
+    pub struct CpxLp {
+        _private: [u8; 0],
+    }
+
+    #[repr(C)]
+    pub struct CpxCallbackContext {
+        _private: [u8; 0],
+    }
+
+    pub const CPX_MAX: c_int = -1;
+    pub const CPX_MIN: c_int = 1;
+    pub const CPX_ON: c_int = 1;
+    pub const CPX_OFF: c_int = 0;
+    pub const CPXMESSAGEBUFSIZE: usize = 1024;
+
+    pub const CPX_PARAM_PREIND: c_int = 1030;
+    pub const CPX_PARAM_SCRIND: c_int = 1035;
+    pub const CPX_PARAM_TILIM: c_int = 1039;
+    pub const CPX_PARAM_EPGAP: c_int = 2009;
+    pub const CPX_PARAM_STARTALG: c_int = 2025;
+
+    pub const CPX_ALG_PRIMAL: c_int = 1;
+    pub const CPX_ALG_DUAL: c_int = 2;
+    pub const CPX_ALG_BARRIER: c_int = 4;
+
+    pub const CPXMIP_OPTIMAL: c_int = 101;
+    pub const CPXMIP_OPTIMAL_TOL: c_int = 102;
+    pub const CPXMIP_INFEASIBLE: c_int = 103;
+    pub const CPXMIP_TIME_LIM_FEAS: c_int = 107;
+    pub const CPXMIP_TIME_LIM_INFEAS: c_int = 108;
+    pub const CPXMIP_ABORT_FEAS: c_int = 113;
+    pub const CPXMIP_ABORT_INFEAS: c_int = 114;
+    pub const CPXMIP_UNBOUNDED: c_int = 118;
+    pub const CPXMIP_INF_OR_UNBD: c_int = 119;
+
+    pub const CPX_CALLBACKCONTEXT_GLOBAL_PROGRESS: c_longlong = 0x0010;
+
+    /// Generic callback; aborting the solve goes through `CPXcallbackabort`
+    pub type CallbackFunc = unsafe extern "C" fn(
+        context: *mut CpxCallbackContext,
+        contextid: c_longlong,
+        userhandle: *mut c_void,
+    ) -> c_int;
+
+    #[link(name = "cplex")]
+    extern "C" {
+        pub fn CPXopenCPLEX(status_p: *mut c_int) -> *mut CpxEnv;
+        pub fn CPXcloseCPLEX(env_p: *mut *mut CpxEnv) -> c_int;
+        pub fn CPXgeterrorstring(
+            env: *const CpxEnv,
+            errcode: c_int,
+            buffer_str: *mut c_char,
+        ) -> *const c_char;
+        pub fn CPXsetdefaults(env: *mut CpxEnv) -> c_int;
+        pub fn CPXsetintparam(env: *mut CpxEnv, whichparam: c_int, newvalue: c_int) -> c_int;
+        pub fn CPXsetdblparam(env: *mut CpxEnv, whichparam: c_int, newvalue: f64) -> c_int;
+        pub fn CPXcreateprob(
+            env: *const CpxEnv,
+            status_p: *mut c_int,
+            probname_str: *const c_char,
+        ) -> *mut CpxLp;
+        pub fn CPXfreeprob(env: *const CpxEnv, lp_p: *mut *mut CpxLp) -> c_int;
+        pub fn CPXchgobjsen(env: *const CpxEnv, lp: *mut CpxLp, maxormin: c_int) -> c_int;
+        pub fn CPXnewcols(
+            env: *const CpxEnv,
+            lp: *mut CpxLp,
+            ccnt: c_int,
+            obj: *const f64,
+            lb: *const f64,
+            ub: *const f64,
+            xctype: *const c_char,
+            colname: *mut *mut c_char,
+        ) -> c_int;
+        pub fn CPXaddrows(
+            env: *const CpxEnv,
+            lp: *mut CpxLp,
+            ccnt: c_int,
+            rcnt: c_int,
+            nzcnt: c_int,
+            rhs: *const f64,
+            sense: *const c_char,
+            rmatbeg: *const c_int,
+            rmatind: *const c_int,
+            rmatval: *const f64,
+            colname: *mut *mut c_char,
+            rowname: *mut *mut c_char,
+        ) -> c_int;
+        pub fn CPXcallbacksetfunc(
+            env: *mut CpxEnv,
+            lp: *mut CpxLp,
+            contextmask: c_longlong,
+            callback: Option<CallbackFunc>,
+            userhandle: *mut c_void,
+        ) -> c_int;
+        pub fn CPXcallbackabort(context: *mut CpxCallbackContext);
+        pub fn CPXmipopt(env: *const CpxEnv, lp: *mut CpxLp) -> c_int;
+        pub fn CPXgetstat(env: *const CpxEnv, lp: *const CpxLp) -> c_int;
+        pub fn CPXgetobjval(env: *const CpxEnv, lp: *const CpxLp, objval_p: *mut f64) -> c_int;
+        pub fn CPXgetx(
+            env: *const CpxEnv,
+            lp: *const CpxLp,
+            x: *mut f64,
+            begin: c_int,
+            end: c_int,
+        ) -> c_int;
+    }
+}
+
+/// CPLEX's message for an error code
+fn error_string(env: *const ffi::CpxEnv, code: c_int) -> String {
+    let mut buffer = [0 as c_char; ffi::CPXMESSAGEBUFSIZE];
+    let message = unsafe { ffi::CPXgeterrorstring(env, code, buffer.as_mut_ptr()) };
+    if message.is_null() {
+        return format!("CPLEX error {}", code);
+    }
+    unsafe { CStr::from_ptr(message) }
+        .to_string_lossy()
+        .trim_end()
+        .to_string()
+}
+
+/// Owned CPLEX environment, closed on drop
+struct CplexEnv(*mut ffi::CpxEnv);
+
+// SAFETY: Only used behind `CplexSolver::env`'s mutex, so one thread at a
+// time sets parameters and solves problems in it
+unsafe impl Send for CplexEnv {}
+
+impl CplexEnv {
+    /// Open an environment, which checks out a CPLEX license
+    fn open() -> Result<Self, SolveInputError> {
+        let mut status: c_int = 0;
+        let env = unsafe { ffi::CPXopenCPLEX(&mut status) };
+        if env.is_null() {
+            return Err(SolveInputError {
+                details: format!(
+                    "Failed to open CPLEX environment: {}",
+                    error_string(std::ptr::null(), status)
+                ),
+            });
+        }
+        Ok(CplexEnv(env))
+    }
+
+    /// Fail with CPLEX's message when `code` is non-zero
+    fn check(&self, code: c_int, action: &str) -> Result<(), SolveInputError> {
+        if code == 0 {
+            return Ok(());
+        }
+        Err(SolveInputError {
+            details: format!("Failed to {}: {}", action, error_string(self.0, code)),
+        })
+    }
+
+    /// Reset the parameters, then set the ones a request controls
+    fn configure(&self, options: &SolveOptions) -> Result<(), SolveInputError> {
+        unsafe {
+            self.check(ffi::CPXsetdefaults(self.0), "reset CPLEX parameters")?;
+            self.check(
+                ffi::CPXsetintparam(self.0, ffi::CPX_PARAM_SCRIND, ffi::CPX_OFF),
+                "disable CPLEX screen output",
+            )?;
+            let presolve = if options.use_presolve {
+                ffi::CPX_ON
+            } else {
+                ffi::CPX_OFF
+            };
+            self.check(
+                ffi::CPXsetintparam(self.0, ffi::CPX_PARAM_PREIND, presolve),
+                "set CPLEX presolve",
+            )?;
+            if let Some(gap) = options.mip_rel_gap {
+                self.check(
+                    ffi::CPXsetdblparam(self.0, ffi::CPX_PARAM_EPGAP, gap),
+                    "set CPLEX MIP gap",
+                )?;
+            }
+            if let Some(method) = options.method {
+                self.check(
+                    ffi::CPXsetintparam(self.0, ffi::CPX_PARAM_STARTALG, root_algorithm(method)),
+                    "set CPLEX root algorithm",
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Drop for CplexEnv {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::CPXcloseCPLEX(&mut self.0);
+        }
+    }
+}
+
+/// Owned CPLEX problem, freed on drop
+struct CplexProblem<'a> {
+    env: &'a CplexEnv,
+    lp: *mut ffi::CpxLp,
+}
+
+impl Drop for CplexProblem<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::CPXfreeprob(self.env.0, &mut self.lp);
+        }
+    }
+}
+
+/// Root algorithm (`CPX_PARAM_STARTALG`) for a request's LP method
+fn root_algorithm(method: LpMethod) -> c_int {
+    match method {
+        LpMethod::Primal => ffi::CPX_ALG_PRIMAL,
+        LpMethod::Dual => ffi::CPX_ALG_DUAL,
+        LpMethod::Barrier => ffi::CPX_ALG_BARRIER,
+    }
+}
+
+/// Progress callback; `handle` points at the request's cancellation token
+unsafe extern "C" fn abort_when_cancelled(
+    context: *mut ffi::CpxCallbackContext,
+    _contextid: c_longlong,
+    handle: *mut c_void,
+) -> c_int {
+    let cancel = &*(handle as *const CancellationToken);
+    if cancel.is_cancelled() {
+        ffi::CPXcallbackabort(context);
+    }
+    0
+}
+
+/// Constraint rows in the layout `CPXaddrows` takes
+struct Rows {
+    rhs: Vec<f64>,
+    sense: Vec<c_char>,
+    beg: Vec<c_int>,
+    ind: Vec<c_int>,
+    val: Vec<f64>,
+}
+
+impl Rows {
+    /// Group the sparse matrix by row, keeping each row's entries in
+    /// request order
+    fn from_polyhedron(polyhedron: &SparseLEIntegerPolyhedron) -> Self {
+        let mut by_row: Vec<Vec<(c_int, f64)>> = vec![Vec::new(); polyhedron.a.shape.nrows];
+        for ((&row, &col), &val) in polyhedron
+            .a
+            .rows
+            .iter()
+            .zip(&polyhedron.a.cols)
+            .zip(&polyhedron.a.vals)
+        {
+            by_row[row as usize].push((col as c_int, val as f64));
+        }
+
+        let mut rows = Rows {
+            rhs: polyhedron.b.iter().map(|&b| b as f64).collect(),
+            sense: vec![b'L' as c_char; by_row.len()],
+            beg: Vec::with_capacity(by_row.len()),
+            ind: Vec::with_capacity(polyhedron.a.vals.len()),
+            val: Vec::with_capacity(polyhedron.a.vals.len()),
+        };
+        for entries in by_row {
+            rows.beg.push(rows.ind.len() as c_int);
+            for (col, val) in entries {
+                rows.ind.push(col);
+                rows.val.push(val);
+            }
+        }
+        rows
+    }
+}
+
+/// CPLEX solver implementation, linking the system `libcplex`.
+///
+/// Meant for organizations that hold CPLEX licenses and compare against
+/// results from their existing CPLEX runs. Every objective is solved on a
+/// freshly built problem, so answers match a standalone CPLEX run on the
+/// same model.
+///
+/// One environment is opened on first use and shared by all requests, so the
+/// license is checked out once rather than per request. CPLEX parameters
+/// belong to the environment, so solves take turns on it. It is opened again
+/// if solving in it fails.
+///
+/// Note: Model caching is not supported to keep runs independent.
+/// The cache_size parameter is accepted for API consistency but has no effect.
+pub struct CplexSolver {
+    env: Mutex<Option<CplexEnv>>,
+}
+
+impl CplexSolver {
+    /// Create a new CPLEX solver with specified cache size
+    /// Note: Cache is not supported for CPLEX, parameter ignored
+    pub fn with_cache_size(_size: Option<usize>) -> Self {
+        Self::without_cache()
+    }
+
+    /// Create solver with caching disabled (same as default for CPLEX)
+    pub fn without_cache() -> Self {
+        CplexSolver {
+            env: Mutex::new(None),
+        }
+    }
+
+    fn convert_status(status: c_int) -> Status {
+        match status {
+            ffi::CPXMIP_OPTIMAL | ffi::CPXMIP_OPTIMAL_TOL => Status::Optimal,
+            ffi::CPXMIP_INFEASIBLE => Status::Infeasible,
+            ffi::CPXMIP_UNBOUNDED | ffi::CPXMIP_INF_OR_UNBD => Status::Unbounded,
+            ffi::CPXMIP_TIME_LIM_FEAS | ffi::CPXMIP_TIME_LIM_INFEAS => Status::TimeLimit,
+            _ => Status::Undefined,
+        }
+    }
+
+    /// Build the problem for one objective
+    fn build_problem<'a>(
+        env: &'a CplexEnv,
+        polyhedron: &SparseLEIntegerPolyhedron,
+        rows: &Rows,
+        objective: &HashMap<String, f64>,
+        direction: SolverDirection,
+    ) -> Result<CplexProblem<'a>, SolveInputError> {
+        let mut status: c_int = 0;
+        let lp = unsafe { ffi::CPXcreateprob(env.0, &mut status, c"optimization".as_ptr()) };
+        if lp.is_null() {
+            env.check(status, "create CPLEX problem")?;
+        }
+        let problem = CplexProblem { env, lp };
+
+        let obj: Vec<f64> = polyhedron
+            .variables
+            .iter()
+            .map(|var| objective.get(&var.id).copied().unwrap_or(0.0))
+            .collect();
+        let lower: Vec<f64> = polyhedron
+            .variables
+            .iter()
+            .map(|var| var.bound.0 as f64)
+            .collect();
+        let upper: Vec<f64> = polyhedron
+            .variables
+            .iter()
+            .map(|var| var.bound.1 as f64)
+            .collect();
+        let types = vec![b'I' as c_char; polyhedron.variables.len()];
+
+        unsafe {
+            let sense = match direction {
+                SolverDirection::Maximize => ffi::CPX_MAX,
+                SolverDirection::Minimize => ffi::CPX_MIN,
+            };
+            env.check(
+                ffi::CPXchgobjsen(env.0, lp, sense),
+                "set CPLEX objective sense",
+            )?;
+            env.check(
+                ffi::CPXnewcols(
+                    env.0,
+                    lp,
+                    obj.len() as c_int,
+                    obj.as_ptr(),
+                    lower.as_ptr(),
+                    upper.as_ptr(),
+                    types.as_ptr(),
+                    std::ptr::null_mut(),
+                ),
+                "add columns to CPLEX problem",
+            )?;
+            env.check(
+                ffi::CPXaddrows(
+                    env.0,
+                    lp,
+                    0,
+                    rows.rhs.len() as c_int,
+                    rows.val.len() as c_int,
+                    rows.rhs.as_ptr(),
+                    rows.sense.as_ptr(),
+                    rows.beg.as_ptr(),
+                    rows.ind.as_ptr(),
+                    rows.val.as_ptr(),
+                    std::ptr::null_mut(),
+                    std::ptr::null_mut(),
+                ),
+                "add constraints to CPLEX problem",
+            )?;
+        }
+
+        Ok(problem)
+    }
+
+    /// Solve the objectives in `env`
+    fn solve_in(
+        env: &CplexEnv,
+        polyhedron: &SparseLEIntegerPolyhedron,
+        objectives: &[HashMap<String, f64>],
+        direction: SolverDirection,
+        options: &SolveOptions,
+        started: Instant,
+    ) -> Result<Vec<ApiSolution>, SolveInputError> {
+        env.configure(options)?;
+        let rows = Rows::from_polyhedron(polyhedron);
+        let n_cols = polyhedron.variables.len();
+
+        let mut solutions = Vec::with_capacity(objectives.len());
+        for objective in objectives {
+            options.check_cancelled()?;
+
+            // The time limit covers all objectives, so each gets what is left
+            let remaining = options.remaining_time(started);
+            if remaining == Some(Duration::ZERO) {
+                solutions.push(ApiSolution {
+                    status: Status::TimeLimit,
+                    objective: 0.0,
+                    solution: HashMap::new(),
+                    error: Some("Time limit reached before this objective was solved".to_string()),
+                });
+                continue;
+            }
+            if let Some(remaining) = remaining {
+                unsafe {
+                    env.check(
+                        ffi::CPXsetdblparam(env.0, ffi::CPX_PARAM_TILIM, remaining.as_secs_f64()),
+                        "set CPLEX time limit",
+                    )?;
+                }
+            }
+
+            let problem = Self::build_problem(env, polyhedron, &rows, objective, direction)?;
+            let status = unsafe {
+                // The token outlives the solve call, which is the only time
+                // CPLEX invokes the callback
+                env.check(
+                    ffi::CPXcallbacksetfunc(
+                        env.0,
+                        problem.lp,
+                        ffi::CPX_CALLBACKCONTEXT_GLOBAL_PROGRESS,
+                        Some(abort_when_cancelled),
+                        &options.cancel as *const CancellationToken as *mut c_void,
+                    ),
+                    "set CPLEX callback",
+                )?;
+                env.check(ffi::CPXmipopt(env.0, problem.lp), "solve with CPLEX")?;
+                ffi::CPXgetstat(env.0, problem.lp)
+            };
+            if matches!(status, ffi::CPXMIP_ABORT_FEAS | ffi::CPXMIP_ABORT_INFEAS) {
+                options.check_cancelled()?;
+            }
+
+            let api_status = Self::convert_status(status);
+            let has_solution = matches!(api_status, Status::Optimal)
+                || matches!(status, ffi::CPXMIP_TIME_LIM_FEAS | ffi::CPXMIP_ABORT_FEAS);
+            if !has_solution {
+                let error = match api_status {
+                    Status::TimeLimit => {
+                        Some("No feasible solution found within the time limit".to_string())
+                    }
+                    Status::Undefined => Some(format!("CPLEX solve ended with status {}", status)),
+                    _ => None,
+                };
+                solutions.push(ApiSolution {
+                    status: api_status,
+                    objective: 0.0,
+                    solution: HashMap::new(),
+                    error,
+                });
+                continue;
+            }
+
+            let mut values = vec![0.0; n_cols];
+            let mut objective_value = 0.0;
+            unsafe {
+                if n_cols > 0 {
+                    env.check(
+                        ffi::CPXgetx(
+                            env.0,
+                            problem.lp,
+                            values.as_mut_ptr(),
+                            0,
+                            n_cols as c_int - 1,
+                        ),
+                        "read CPLEX solution",
+                    )?;
+                }
+                env.check(
+                    ffi::CPXgetobjval(env.0, problem.lp, &mut objective_value),
+                    "read CPLEX objective value",
+                )?;
+            }
+
+            let solution_map: HashMap<String, i32> = polyhedron
+                .variables
+                .iter()
+                .zip(&values)
+                .map(|(var, value)| (var.id.clone(), value.round() as i32))
+                .collect();
+
+            solutions.push(ApiSolution {
+                status: api_status,
+                objective: objective_value,
+                solution: solution_map,
+                error: None,
+            });
+        }
+
+        Ok(solutions)
+    }
+}
+
+impl Solver for CplexSolver {
+    fn solve(
+        &self,
+        polyhedron: SparseLEIntegerPolyhedron,
+        objectives: Vec<HashMap<String, f64>>,
+        direction: SolverDirection,
+        options: &SolveOptions,
+    ) -> Result<SolveOutcome, SolveInputError> {
+        let started = Instant::now();
+        validate_objectives_owned(&polyhedron.variables, &objectives)?;
+
+        let mut env = self.env.lock();
+        if env.is_none() {
+            *env = Some(CplexEnv::open()?);
+        }
+        let result = Self::solve_in(
+            env.as_ref().unwrap(),
+            &polyhedron,
+            &objectives,
+            direction,
+            options,
+            started,
+        );
+        if result.is_err() && !options.cancel.is_cancelled() {
+            // E.g. a lost license; open a fresh environment next time
+            *env = None;
+        }
+
+        Ok(SolveOutcome::finished(result?, started))
+    }
+
+    fn name(&self) -> &str {
+        "CPLEX"
+    }
+
+    fn license_status(&self) -> Option<ApiLicenseStatus> {
+        // Opening the shared environment checks out a license, which it
+        // then holds for later solves. A solve holding it has one.
+        let error = self.env.try_lock().and_then(|mut env| {
+            if env.is_some() {
+                return None;
+            }
+            match CplexEnv::open() {
+                Ok(opened) => {
+                    *env = Some(opened);
+                    None
+                }
+                Err(e) => Some(e.details),
+            }
+        });
+        // The Callable Library doesn't report the license's expiry, only
+        // whether a license could be checked out
+        Some(ApiLicenseStatus {
+            solver: self.name().to_string(),
+            valid: error.is_none(),
+            error,
+            expires: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ApiIntegerSparseMatrix, ApiShape, ApiVariable};
+
+    #[test]
+    fn test_groups_rows_in_cplex_layout() {
+        // Entries arrive out of row order
+        let polyhedron = SparseLEIntegerPolyhedron {
+            a: ApiIntegerSparseMatrix {
+                rows: vec![1, 0, 0],
+                cols: vec![1, 0, 1],
+                vals: vec![1, 1, 2],
+                shape: ApiShape { nrows: 2, ncols: 2 },
+            },
+            b: vec![10, 5],
+            variables: vec![],
+        };
+
+        let rows = Rows::from_polyhedron(&polyhedron);
+
+        assert_eq!(rows.beg, vec![0, 2]);
+        assert_eq!(rows.ind, vec![0, 1, 1]);
+        assert_eq!(rows.val, vec![1.0, 2.0, 1.0]);
+        assert_eq!(rows.rhs, vec![10.0, 5.0]);
+        assert_eq!(rows.sense, vec![b'L' as c_char; 2]);
+    }
+
+    #[test]
+    fn test_solves_small_problem() {
+        // x + 2y <= 10, y <= 5
+        let polyhedron = SparseLEIntegerPolyhedron {
+            a: ApiIntegerSparseMatrix {
+                rows: vec![0, 0, 1],
+                cols: vec![0, 1, 1],
+                vals: vec![1, 2, 1],
+                shape: ApiShape { nrows: 2, ncols: 2 },
+            },
+            b: vec![10, 5],
+            variables: vec![
+                ApiVariable {
+                    id: "x".to_string(),
+                    bound: (0, 10),
+                },
+                ApiVariable {
+                    id: "y".to_string(),
+                    bound: (0, 10),
+                },
+            ],
+        };
+        let objective = HashMap::from([("x".to_string(), 1.0), ("y".to_string(), 3.0)]);
+
+        let solutions = CplexSolver::without_cache()
+            .solve(
+                polyhedron,
+                vec![objective],
+                SolverDirection::Maximize,
+                &SolveOptions::with_presolve(true),
+            )
+            .ok()
+            .unwrap()
+            .solutions;
+        assert!(matches!(solutions[0].status, Status::Optimal));
+        assert_eq!(solutions[0].objective, 15.0);
+    }
+}
@@ -0,0 +1,253 @@
+use crate::domain::solver::{SolveOptions, SolveOutcome, Solver};
+use crate::domain::validate::{validate_objectives_owned, SolveInputError};
+use crate::models::{ApiLicenseStatus, SolverDirection, SparseLEIntegerPolyhedron};
+use std::any::Any;
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+/// Solver that tries a chain of backends in order
+///
+/// When a backend returns an error or panics (e.g. no license available, or a
+/// crash inside the native library), the request is retried on the next
+/// backend with the time limit that is left. The error of the last backend
+/// tried is returned when all of them fail or the time runs out. Problems
+/// every backend would reject, such as objectives over unknown variables,
+/// fail without trying any.
+pub struct FallbackSolver {
+    solvers: Vec<Box<dyn Solver>>,
+    name: String,
+}
+
+impl FallbackSolver {
+    /// Create a chain trying `solvers` in order. Panics if `solvers` is empty.
+    pub fn new(solvers: Vec<Box<dyn Solver>>) -> Self {
+        assert!(
+            !solvers.is_empty(),
+            "Fallback chain needs at least one solver"
+        );
+        let name = solvers
+            .iter()
+            .map(|solver| solver.name())
+            .collect::<Vec<_>>()
+            .join(" > ");
+        FallbackSolver { solvers, name }
+    }
+}
+
+impl Solver for FallbackSolver {
+    fn solve(
+        &self,
+        polyhedron: SparseLEIntegerPolyhedron,
+        objectives: Vec<HashMap<String, f64>>,
+        direction: SolverDirection,
+        options: &SolveOptions,
+    ) -> std::result::Result<SolveOutcome, SolveInputError> {
+        self.solve_attributed(polyhedron, objectives, direction, options)
+            .map(|(outcome, _)| outcome)
+    }
+
+    fn solve_attributed(
+        &self,
+        polyhedron: SparseLEIntegerPolyhedron,
+        objectives: Vec<HashMap<String, f64>>,
+        direction: SolverDirection,
+        options: &SolveOptions,
+    ) -> std::result::Result<(SolveOutcome, String), SolveInputError> {
+        let started = Instant::now();
+        validate_objectives_owned(&polyhedron.variables, &objectives)?;
+
+        let mut last_error = None;
+        for solver in &self.solvers {
+            // A cancelled request shouldn't start the next backend
+            options.check_cancelled()?;
+            // Nor should one that has used up its time limit
+            if last_error.is_some() && options.remaining_time(started) == Some(Duration::ZERO) {
+                break;
+            }
+            let attempt_options = SolveOptions {
+                time_limit: options.remaining_time(started),
+                ..options.clone()
+            };
+            let attempt = panic::catch_unwind(AssertUnwindSafe(|| {
+                solver.solve_attributed(
+                    polyhedron.clone(),
+                    objectives.clone(),
+                    direction,
+                    &attempt_options,
+                )
+            }));
+            let error = match attempt {
+                Ok(Ok(answer)) => return Ok(answer),
+                Ok(Err(error)) => error,
+                Err(payload) => SolveInputError {
+                    details: format!("{} crashed: {}", solver.name(), panic_message(&payload)),
+                },
+            };
+            log::warn!("Solver {} failed: {}", solver.name(), error.details);
+            last_error = Some(error);
+        }
+
+        Err(last_error.expect("Fallback chain is never empty"))
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn license_status(&self) -> Option<ApiLicenseStatus> {
+        self.solvers
+            .iter()
+            .find_map(|solver| solver.license_status())
+    }
+}
+
+fn panic_message(payload: &Box<dyn Any + Send>) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ApiIntegerSparseMatrix, ApiShape, ApiSolution, Status};
+    use std::time::Instant;
+
+    enum Behaviour {
+        Answer,
+        Fail,
+        /// Fails after using up 20ms
+        FailSlowly,
+        Panic,
+    }
+
+    struct StubSolver {
+        name: &'static str,
+        behaviour: Behaviour,
+    }
+
+    impl Solver for StubSolver {
+        fn solve(
+            &self,
+            _polyhedron: SparseLEIntegerPolyhedron,
+            _objectives: Vec<HashMap<String, f64>>,
+            _direction: SolverDirection,
+            _options: &SolveOptions,
+        ) -> std::result::Result<SolveOutcome, SolveInputError> {
+            match self.behaviour {
+                Behaviour::Answer => Ok(SolveOutcome::finished(
+                    vec![ApiSolution {
+                        status: Status::Optimal,
+                        objective: 0.0,
+                        solution: HashMap::new(),
+                        error: None,
+                    }],
+                    Instant::now(),
+                )),
+                Behaviour::Fail => Err(SolveInputError {
+                    details: format!("{} has no license", self.name),
+                }),
+                Behaviour::FailSlowly => {
+                    std::thread::sleep(Duration::from_millis(20));
+                    Err(SolveInputError {
+                        details: format!("{} timed out", self.name),
+                    })
+                }
+                Behaviour::Panic => panic!("segfault in {}", self.name),
+            }
+        }
+
+        fn name(&self) -> &str {
+            self.name
+        }
+    }
+
+    fn chain(stubs: Vec<(&'static str, Behaviour)>) -> FallbackSolver {
+        FallbackSolver::new(
+            stubs
+                .into_iter()
+                .map(|(name, behaviour)| {
+                    Box::new(StubSolver { name, behaviour }) as Box<dyn Solver>
+                })
+                .collect(),
+        )
+    }
+
+    fn solve(solver: &FallbackSolver) -> Result<(SolveOutcome, String), SolveInputError> {
+        solve_with(solver, HashMap::new(), SolveOptions::with_presolve(true))
+    }
+
+    fn solve_with(
+        solver: &FallbackSolver,
+        objective: HashMap<String, f64>,
+        options: SolveOptions,
+    ) -> Result<(SolveOutcome, String), SolveInputError> {
+        let polyhedron = SparseLEIntegerPolyhedron {
+            a: ApiIntegerSparseMatrix {
+                rows: vec![],
+                cols: vec![],
+                vals: vec![],
+                shape: ApiShape { nrows: 0, ncols: 0 },
+            },
+            b: vec![],
+            variables: vec![],
+        };
+        solver.solve_attributed(
+            polyhedron,
+            vec![objective],
+            SolverDirection::Maximize,
+            &options,
+        )
+    }
+
+    #[test]
+    fn test_falls_back_after_error_and_panic() {
+        let solver = chain(vec![
+            ("Gurobi", Behaviour::Fail),
+            ("HiGHS", Behaviour::Panic),
+            ("GLPK", Behaviour::Answer),
+        ]);
+        assert_eq!(solver.name(), "Gurobi > HiGHS > GLPK");
+
+        let (outcome, answered_by) = solve(&solver).ok().unwrap();
+        assert_eq!(outcome.solutions.len(), 1);
+        assert_eq!(answered_by, "GLPK");
+    }
+
+    #[test]
+    fn test_returns_last_error_when_all_fail() {
+        let solver = chain(vec![
+            ("Gurobi", Behaviour::Fail),
+            ("HiGHS", Behaviour::Panic),
+        ]);
+        let error = solve(&solver).err().unwrap();
+        assert_eq!(error.details, "HiGHS crashed: segfault in HiGHS");
+    }
+
+    #[test]
+    fn test_does_not_fall_back_on_input_errors() {
+        let solver = chain(vec![("Gurobi", Behaviour::Answer)]);
+        let objective = HashMap::from([("y".to_string(), 1.0)]);
+        let error = solve_with(&solver, objective, SolveOptions::with_presolve(true))
+            .err()
+            .unwrap();
+        assert_eq!(error.details, "Objective contains missing variable y");
+    }
+
+    #[test]
+    fn test_stops_when_time_runs_out() {
+        let solver = chain(vec![
+            ("Gurobi", Behaviour::FailSlowly),
+            ("GLPK", Behaviour::Answer),
+        ]);
+        let options = SolveOptions {
+            time_limit: Some(Duration::from_millis(10)),
+            ..SolveOptions::with_presolve(true)
+        };
+        let error = solve_with(&solver, HashMap::new(), options).err().unwrap();
+        assert_eq!(error.details, "Gurobi timed out");
+    }
+}
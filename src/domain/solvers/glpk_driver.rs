@@ -0,0 +1,582 @@
+//! Direct GLPK calls for what `glpk_rust::solve_ilps` doesn't expose
+//!
+//! Follows `solve_ilps` step by step, so answers and error messages are the
+//! same, but installs a branch-and-cut callback that stops the search when
+//! the solve is cancelled, and a terminal hook that captures GLPK's output
+//! instead of printing it. Uses the `libglpk` that glpk-rust links.
+
+use crate::domain::cancellation::CancellationToken;
+use crate::domain::solver::SolveOptions;
+use crate::domain::validate::SolveInputError;
+use crate::models::{ApiSolution, LpMethod, Status};
+use glpk_rust::{
+    glp_consts, validate_and_deduplicate_matrix, MatrixValidationError, Objective, SolverError,
+    SparseLEIntegerPolyhedron,
+};
+use std::collections::HashMap;
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int, c_void};
+use std::time::{Duration, Instant};
+
+/// Bindings to the parts of the GLPK C API used here
+mod ffi {
+    use std::os::raw::{c_char, c_double, c_int, c_void};
+
+    #[repr(C)]
+    pub struct GlpProb {
+        _private: [u8; 0],
+    }
+
+    #[repr(C)]
+    pub struct GlpTree {
+        _private: [u8; 0],
+    }
+
+    /// Return code when `tm_lim` ran out
+    pub const GLP_ETMLIM: c_int = 0x09;
+    /// `glp_intopt` return code after `glp_ios_terminate`
+    pub const GLP_ESTOP: c_int = 0x0D;
+    /// `glp_intopt` return code when the search stopped within `mip_gap`
+    pub const GLP_EMIPGAP: c_int = 0x0E;
+
+    /// Simplex variants for `meth`
+    pub const GLP_PRIMAL: c_int = 1;
+    pub const GLP_DUALP: c_int = 2;
+
+    /// Message levels for `msg_lev`
+    pub const GLP_MSG_OFF: c_int = 0;
+    pub const GLP_MSG_ALL: c_int = 3;
+
+    pub type IosCallback = unsafe extern "C" fn(tree: *mut GlpTree, info: *mut c_void);
+    /// Receives each chunk of terminal output; non-zero suppresses printing
+    pub type TermHook = unsafe extern "C" fn(info: *mut c_void, s: *const c_char) -> c_int;
+
+    #[repr(C)]
+    pub struct GlpSmcp {
+        pub msg_lev: c_int,
+        pub meth: c_int,
+        pub pricing: c_int,
+        pub r_test: c_int,
+        pub tol_bnd: c_double,
+        pub tol_dj: c_double,
+        pub tol_piv: c_double,
+        pub obj_ll: c_double,
+        pub obj_ul: c_double,
+        pub it_lim: c_int,
+        pub tm_lim: c_int,
+        pub out_frq: c_int,
+        pub out_dly: c_int,
+        pub presolve: c_int,
+        pub excl: c_int,
+        pub shift: c_int,
+        pub aorn: c_int,
+        pub foo_bar: [c_double; 36],
+    }
+
+    #[repr(C)]
+    pub struct GlpIocp {
+        pub msg_lev: c_int,
+        pub br_tech: c_int,
+        pub bt_tech: c_int,
+        pub tol_int: c_double,
+        pub tol_obj: c_double,
+        pub tm_lim: c_int,
+        pub out_frq: c_int,
+        pub out_dly: c_int,
+        pub cb_func: Option<IosCallback>,
+        pub cb_info: *mut c_void,
+        pub cb_size: c_int,
+        pub pp_tech: c_int,
+        pub mip_gap: c_double,
+        pub mir_cuts: c_int,
+        pub gmi_cuts: c_int,
+        pub cov_cuts: c_int,
+        pub clq_cuts: c_int,
+        pub presolve: c_int,
+        pub binarize: c_int,
+        pub fp_heur: c_int,
+        pub ps_heur: c_int,
+        pub ps_tm_lim: c_int,
+        pub sr_heur: c_int,
+        pub use_sol: c_int,
+        pub save_sol: *const c_char,
+        pub alien: c_int,
+        pub flip: c_int,
+        pub foo_bar: [c_double; 23],
+    }
+
+    extern "C" {
+        pub fn glp_create_prob() -> *mut GlpProb;
+        pub fn glp_delete_prob(lp: *mut GlpProb);
+        pub fn glp_set_obj_dir(lp: *mut GlpProb, dir: c_int);
+        pub fn glp_add_rows(lp: *mut GlpProb, nrs: c_int) -> c_int;
+        pub fn glp_add_cols(lp: *mut GlpProb, ncs: c_int) -> c_int;
+        pub fn glp_set_row_bnds(lp: *mut GlpProb, i: c_int, type_: c_int, lb: f64, ub: f64);
+        pub fn glp_set_col_bnds(lp: *mut GlpProb, j: c_int, type_: c_int, lb: f64, ub: f64);
+        pub fn glp_set_col_kind(lp: *mut GlpProb, j: c_int, kind: c_int);
+        pub fn glp_set_obj_coef(lp: *mut GlpProb, j: c_int, coef: f64);
+        pub fn glp_load_matrix(
+            lp: *mut GlpProb,
+            ne: c_int,
+            ia: *const c_int,
+            ja: *const c_int,
+            ar: *const f64,
+        );
+        pub fn glp_init_smcp(parm: *mut GlpSmcp);
+        pub fn glp_simplex(lp: *mut GlpProb, parm: *const GlpSmcp) -> c_int;
+        pub fn glp_exact(lp: *mut GlpProb, parm: *const GlpSmcp) -> c_int;
+        pub fn glp_init_iocp(parm: *mut GlpIocp);
+        pub fn glp_intopt(lp: *mut GlpProb, parm: *const GlpIocp) -> c_int;
+        pub fn glp_ios_terminate(tree: *mut GlpTree);
+        pub fn glp_mip_status(lp: *mut GlpProb) -> c_int;
+        pub fn glp_mip_obj_val(lp: *mut GlpProb) -> f64;
+        pub fn glp_mip_col_val(lp: *mut GlpProb, j: c_int) -> f64;
+        pub fn glp_term_out(flag: c_int) -> c_int;
+        pub fn glp_term_hook(func: Option<TermHook>, info: *mut c_void);
+        pub fn glp_free_env() -> c_int;
+    }
+}
+
+/// Branch-and-cut callback; `info` points at the request's cancellation token
+unsafe extern "C" fn terminate_when_cancelled(tree: *mut ffi::GlpTree, info: *mut c_void) {
+    let cancel = &*(info as *const CancellationToken);
+    if cancel.is_cancelled() {
+        ffi::glp_ios_terminate(tree);
+    }
+}
+
+/// Terminal hook; `info` points at the solve's log buffer
+unsafe extern "C" fn capture_output(info: *mut c_void, s: *const c_char) -> c_int {
+    let log = &mut *(info as *mut String);
+    log.push_str(&CStr::from_ptr(s).to_string_lossy());
+    1
+}
+
+/// GLPK's `tm_lim`: whole milliseconds, at least one so it isn't "no limit"
+fn time_limit_millis(remaining: Duration) -> c_int {
+    remaining.as_millis().clamp(1, c_int::MAX as u128) as c_int
+}
+
+/// GLPK's `meth` for the requested LP method, `None` for GLPK's default
+///
+/// GLPK's interior-point solver leaves no basis for `glp_intopt` to start
+/// from, so barrier is rejected.
+fn simplex_method(method: Option<LpMethod>) -> Result<Option<c_int>, SolveInputError> {
+    match method {
+        None => Ok(None),
+        Some(LpMethod::Primal) => Ok(Some(ffi::GLP_PRIMAL)),
+        // Dual simplex, switching to primal if the dual one fails
+        Some(LpMethod::Dual) => Ok(Some(ffi::GLP_DUALP)),
+        Some(LpMethod::Barrier) => Err(SolveInputError {
+            details: "GLPK has no barrier method for integer problems, use primal or dual"
+                .to_string(),
+        }),
+    }
+}
+
+/// Apply the request's options on top of GLPK's default MIP parameters
+///
+/// `presolve` turns on GLPK's MIP presolver, which also lets `glp_intopt`
+/// handle LP relaxations that the initial simplex left without an optimal
+/// basis. Without a `mip_rel_gap` GLPK's own default of 0 applies.
+fn configure_mip(
+    params: &mut ffi::GlpIocp,
+    options: &SolveOptions,
+    remaining: Option<Duration>,
+    msg_lev: c_int,
+) {
+    params.presolve = options.use_presolve as c_int;
+    if let Some(gap) = options.mip_rel_gap {
+        params.mip_gap = gap;
+    }
+    if let Some(remaining) = remaining {
+        params.tm_lim = time_limit_millis(remaining);
+    }
+    params.msg_lev = msg_lev;
+    // GLPK only calls back during glp_intopt, while `options` is borrowed
+    params.cb_func = Some(terminate_when_cancelled);
+    params.cb_info = &options.cancel as *const CancellationToken as *mut c_void;
+}
+
+/// Check the polyhedron the same way `solve_ilps` does before touching GLPK
+fn validate(polytope: &SparseLEIntegerPolyhedron) -> Result<(), SolverError> {
+    let a = &polytope.a;
+    if a.rows.is_empty() || a.cols.is_empty() {
+        return Err(SolverError::EmptyConstraintMatrix);
+    }
+    if a.rows.len() != a.cols.len() || a.rows.len() != a.vals.len() {
+        return Err(SolverError::MatrixValidation(
+            MatrixValidationError::LengthMismatch {
+                rows: a.rows.len(),
+                cols: a.cols.len(),
+                vals: a.vals.len(),
+            },
+        ));
+    }
+
+    let n_cols = polytope.variables.len();
+    let poly_n_cols = (*a.cols.iter().max().unwrap() + 1) as usize;
+    if n_cols < poly_n_cols {
+        return Err(SolverError::VariableColumnMismatch {
+            n_variables: n_cols,
+            max_col_index: poly_n_cols,
+        });
+    }
+
+    let max_row_idx = (*a.rows.iter().max().unwrap() + 1) as usize;
+    if max_row_idx > polytope.b.len() {
+        return Err(SolverError::RowBoundMismatch {
+            n_bounds: polytope.b.len(),
+            max_row_index: max_row_idx,
+        });
+    }
+    Ok(())
+}
+
+/// Solve each objective over `polytope`, like `glpk_rust::solve_ilps`
+///
+/// Returns a "Solve cancelled" error when `options.cancel` is cancelled
+/// before or during any of the objectives. Objectives cut short by
+/// `options.time_limit` get `Status::TimeLimit` with GLPK's incumbent, if any.
+///
+/// GLPK's output is appended to `log` when given, and silenced otherwise.
+pub fn solve_ilps(
+    polytope: &SparseLEIntegerPolyhedron,
+    objectives: &[Objective],
+    maximize: bool,
+    options: &SolveOptions,
+    log: Option<&mut String>,
+) -> Result<Vec<ApiSolution>, SolveInputError> {
+    let started = Instant::now();
+    validate(polytope)?;
+    let a = &polytope.a;
+    let (clean_rows, clean_cols, clean_vals) = validate_and_deduplicate_matrix(
+        &a.rows,
+        &a.cols,
+        &a.vals,
+        polytope.b.len(),
+        polytope.variables.len(),
+    )
+    .map_err(SolverError::from)?;
+
+    // 1-based, with an unused leading entry
+    let rows: Vec<c_int> = std::iter::once(0)
+        .chain(clean_rows.iter().map(|x| *x + 1))
+        .collect();
+    let cols: Vec<c_int> = std::iter::once(0)
+        .chain(clean_cols.iter().map(|x| *x + 1))
+        .collect();
+    let vals: Vec<f64> = std::iter::once(0.0)
+        .chain(clean_vals.iter().map(|x| *x as f64))
+        .collect();
+
+    let msg_lev = if log.is_some() {
+        ffi::GLP_MSG_ALL
+    } else {
+        ffi::GLP_MSG_OFF
+    };
+    let mut solutions = Vec::with_capacity(objectives.len());
+
+    unsafe {
+        // The environment is per thread and freed below, taking the hook with
+        // it, so `log` outlives every call GLPK makes to it
+        ffi::glp_term_out(log.is_some() as c_int);
+        if let Some(log) = log {
+            ffi::glp_term_hook(Some(capture_output), log as *mut String as *mut c_void);
+        }
+
+        let lp = ffi::glp_create_prob();
+        let direction = if maximize {
+            glp_consts::GLP_MAX
+        } else {
+            glp_consts::GLP_MIN
+        };
+        ffi::glp_set_obj_dir(lp, direction);
+
+        ffi::glp_add_rows(lp, polytope.b.len() as c_int);
+        let row_type = if polytope.double_bound {
+            glp_consts::GLP_DB
+        } else {
+            glp_consts::GLP_UP
+        };
+        for (i, &(lower, upper)) in polytope.b.iter().enumerate() {
+            ffi::glp_set_row_bnds(lp, (i + 1) as c_int, row_type, lower as f64, upper as f64);
+        }
+
+        ffi::glp_add_cols(lp, polytope.variables.len() as c_int);
+        for (i, var) in polytope.variables.iter().enumerate() {
+            let col_type = if var.bound.0 == var.bound.1 {
+                glp_consts::GLP_FX
+            } else {
+                glp_consts::GLP_DB
+            };
+            let col = (i + 1) as c_int;
+            ffi::glp_set_col_bnds(lp, col, col_type, var.bound.0 as f64, var.bound.1 as f64);
+            ffi::glp_set_col_kind(lp, col, glp_consts::GLP_IV);
+        }
+
+        ffi::glp_load_matrix(
+            lp,
+            (vals.len() - 1) as c_int,
+            rows.as_ptr(),
+            cols.as_ptr(),
+            vals.as_ptr(),
+        );
+
+        let result = solve_objectives(
+            lp,
+            polytope,
+            objectives,
+            options,
+            started,
+            msg_lev,
+            &mut solutions,
+        );
+
+        ffi::glp_delete_prob(lp);
+        ffi::glp_free_env();
+        result?;
+    }
+
+    Ok(solutions)
+}
+
+/// Solution without variable values
+fn empty_solution(status: Status, error: String) -> ApiSolution {
+    ApiSolution {
+        status,
+        objective: 0.0,
+        solution: HashMap::new(),
+        error: Some(error),
+    }
+}
+
+/// Read the MIP solution GLPK currently holds
+unsafe fn mip_solution(
+    lp: *mut ffi::GlpProb,
+    polytope: &SparseLEIntegerPolyhedron,
+    status: Status,
+) -> ApiSolution {
+    let solution = polytope
+        .variables
+        .iter()
+        .enumerate()
+        .map(|(j, var)| {
+            let x = ffi::glp_mip_col_val(lp, (j + 1) as c_int);
+            (var.id.to_string(), x as i32)
+        })
+        .collect();
+    ApiSolution {
+        status,
+        objective: ffi::glp_mip_obj_val(lp),
+        solution,
+        error: None,
+    }
+}
+
+/// Solve each objective on the loaded problem, appending to `solutions`
+unsafe fn solve_objectives(
+    lp: *mut ffi::GlpProb,
+    polytope: &SparseLEIntegerPolyhedron,
+    objectives: &[Objective],
+    options: &SolveOptions,
+    started: Instant,
+    msg_lev: c_int,
+    solutions: &mut Vec<ApiSolution>,
+) -> Result<(), SolveInputError> {
+    let meth = simplex_method(options.method)?;
+    for objective in objectives {
+        options.check_cancelled()?;
+
+        let remaining = options.remaining_time(started);
+        if remaining == Some(Duration::ZERO) {
+            solutions.push(empty_solution(
+                Status::TimeLimit,
+                "Time limit reached before this objective was solved".to_string(),
+            ));
+            continue;
+        }
+
+        for (j, var) in polytope.variables.iter().enumerate() {
+            let coef = objective.get(&var.id).copied().unwrap_or(0.0);
+            ffi::glp_set_obj_coef(lp, (j + 1) as c_int, coef);
+        }
+
+        // The LP relaxation provides the initial basis for the MIP solver
+        let mut simplex_params: ffi::GlpSmcp = std::mem::zeroed();
+        ffi::glp_init_smcp(&mut simplex_params);
+        simplex_params.msg_lev = msg_lev;
+        if let Some(meth) = meth {
+            simplex_params.meth = meth;
+        }
+        if let Some(remaining) = remaining {
+            simplex_params.tm_lim = time_limit_millis(remaining);
+        }
+        let simplex_ret = ffi::glp_simplex(lp, &simplex_params);
+        if simplex_ret == ffi::GLP_ETMLIM {
+            solutions.push(empty_solution(
+                Status::TimeLimit,
+                "Time limit reached while solving the LP relaxation".to_string(),
+            ));
+            continue;
+        }
+        if simplex_ret != 0 {
+            solutions.push(empty_solution(
+                Status::SimplexFailed,
+                format!("GLPK simplex solver failed with code: {}", simplex_ret),
+            ));
+            continue;
+        }
+
+        // Refine the floating-point basis in rational arithmetic, so the
+        // relaxation's bounds are exact even for ill-conditioned matrices
+        if options.exact_lp {
+            options.check_cancelled()?;
+            let remaining = options.remaining_time(started);
+            if let Some(remaining) = remaining {
+                simplex_params.tm_lim = time_limit_millis(remaining);
+            }
+            let exact_ret = if remaining == Some(Duration::ZERO) {
+                ffi::GLP_ETMLIM
+            } else {
+                ffi::glp_exact(lp, &simplex_params)
+            };
+            if exact_ret == ffi::GLP_ETMLIM {
+                solutions.push(empty_solution(
+                    Status::TimeLimit,
+                    "Time limit reached while solving the LP relaxation".to_string(),
+                ));
+                continue;
+            }
+            if exact_ret != 0 {
+                solutions.push(empty_solution(
+                    Status::SimplexFailed,
+                    format!("GLPK exact simplex solver failed with code: {}", exact_ret),
+                ));
+                continue;
+            }
+        }
+
+        let mut mip_params: ffi::GlpIocp = std::mem::zeroed();
+        ffi::glp_init_iocp(&mut mip_params);
+        configure_mip(
+            &mut mip_params,
+            options,
+            options.remaining_time(started),
+            msg_lev,
+        );
+        let mip_ret = ffi::glp_intopt(lp, &mip_params);
+
+        if mip_ret == ffi::GLP_ESTOP {
+            options.check_cancelled()?;
+        }
+        if mip_ret == ffi::GLP_ETMLIM {
+            // Keep the incumbent when the search found one
+            let solution = if ffi::glp_mip_status(lp) == glp_consts::GLP_FEAS {
+                mip_solution(lp, polytope, Status::TimeLimit)
+            } else {
+                empty_solution(
+                    Status::TimeLimit,
+                    "No feasible solution found within the time limit".to_string(),
+                )
+            };
+            solutions.push(solution);
+            continue;
+        }
+        if mip_ret == ffi::GLP_EMIPGAP {
+            // The incumbent is within the requested gap, which counts as optimal
+            solutions.push(mip_solution(lp, polytope, Status::Optimal));
+            continue;
+        }
+        if mip_ret != 0 {
+            solutions.push(empty_solution(
+                Status::MIPFailed,
+                format!("GLPK MIP solver failed with code: {}", mip_ret),
+            ));
+            continue;
+        }
+
+        let solution = match ffi::glp_mip_status(lp) {
+            glp_consts::GLP_UNDEF => {
+                empty_solution(Status::Undefined, "Solution is undefined".to_string())
+            }
+            glp_consts::GLP_FEAS => mip_solution(lp, polytope, Status::Feasible),
+            glp_consts::GLP_OPT => mip_solution(lp, polytope, Status::Optimal),
+            glp_consts::GLP_INFEAS => {
+                empty_solution(Status::Infeasible, "Infeasible solution exists".to_string())
+            }
+            glp_consts::GLP_NOFEAS => empty_solution(
+                Status::NoFeasible,
+                "No feasible solution exists".to_string(),
+            ),
+            glp_consts::GLP_UNBND => {
+                empty_solution(Status::Unbounded, "Problem is unbounded".to_string())
+            }
+            status => return Err(SolverError::UnknownStatus(status).into()),
+        };
+        solutions.push(solution);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presolve_follows_options() {
+        // All-zero is a valid starting point; glp_init_iocp isn't needed here
+        let mut params: ffi::GlpIocp = unsafe { std::mem::zeroed() };
+
+        configure_mip(&mut params, &SolveOptions::with_presolve(true), None, 0);
+        assert_eq!(params.presolve, 1);
+        assert!(params.cb_func.is_some());
+
+        configure_mip(&mut params, &SolveOptions::with_presolve(false), None, 0);
+        assert_eq!(params.presolve, 0);
+    }
+
+    #[test]
+    fn test_mip_gap_follows_options() {
+        let mut params: ffi::GlpIocp = unsafe { std::mem::zeroed() };
+        let options = SolveOptions {
+            mip_rel_gap: Some(0.05),
+            ..SolveOptions::with_presolve(true)
+        };
+
+        configure_mip(&mut params, &options, None, 0);
+        assert_eq!(params.mip_gap, 0.05);
+    }
+
+    #[test]
+    fn test_captures_terminal_output() {
+        let mut log = String::new();
+        let info = &mut log as *mut String as *mut c_void;
+        for line in [c"GLPK Simplex Optimizer\n", c"OPTIMAL SOLUTION FOUND\n"] {
+            assert_eq!(unsafe { capture_output(info, line.as_ptr()) }, 1);
+        }
+        assert_eq!(log, "GLPK Simplex Optimizer\nOPTIMAL SOLUTION FOUND\n");
+    }
+
+    #[test]
+    fn test_maps_lp_method_to_simplex_variant() {
+        assert_eq!(simplex_method(None).ok().unwrap(), None);
+        assert_eq!(
+            simplex_method(Some(LpMethod::Primal)).ok().unwrap(),
+            Some(ffi::GLP_PRIMAL)
+        );
+        assert_eq!(
+            simplex_method(Some(LpMethod::Dual)).ok().unwrap(),
+            Some(ffi::GLP_DUALP)
+        );
+        assert!(simplex_method(Some(LpMethod::Barrier)).is_err());
+    }
+
+    #[test]
+    fn test_time_limit_in_whole_milliseconds() {
+        assert_eq!(time_limit_millis(Duration::from_secs_f64(1.5)), 1500);
+        // Never 0 or negative, which GLPK would read as no limit
+        assert_eq!(time_limit_millis(Duration::from_micros(10)), 1);
+        assert_eq!(time_limit_millis(Duration::from_secs(u64::MAX)), c_int::MAX);
+    }
+}
@@ -1,14 +1,21 @@
 use crate::convert::{to_borrowed_objective, to_glpk_polyhedron};
-use crate::domain::solver::Solver;
+use crate::domain::solver::{SolveOptions, SolveOutcome, Solver};
+use crate::domain::solvers::glpk_driver::solve_ilps;
 use crate::domain::validate::{validate_objectives_owned, SolveInputError};
-use crate::models::{ApiSolution, SolverDirection, SparseLEIntegerPolyhedron};
-use glpk_rust::solve_ilps;
+use crate::models::{SolverDirection, SparseLEIntegerPolyhedron};
 use std::collections::HashMap;
-
-const NO_TERMINAL_OUTPUT: bool = false;
+use std::time::Instant;
 
 /// GLPK solver implementation
 ///
+/// Cancellation is checked between objectives and from GLPK's branch-and-cut
+/// callback, so long MIP searches stop soon after the client goes away. A
+/// request time limit is passed on as GLPK's `tm_lim`.
+///
+/// GLPK's terminal output is captured per solve rather than printed. It is
+/// returned as the outcome's log when the request asks for it, and logged at
+/// debug level under the `glpk` target.
+///
 /// Note: GLPK does not support model caching due to its mutable API design.
 /// The cache_size parameter is accepted for API consistency but has no effect.
 pub struct GlpkSolver;
@@ -32,12 +39,11 @@ impl Solver for GlpkSolver {
         polyhedron: SparseLEIntegerPolyhedron,
         objectives: Vec<HashMap<String, f64>>,
         direction: SolverDirection,
-        _use_presolve: bool,
-    ) -> Result<Vec<ApiSolution>, SolveInputError> {
-        let glpk_polyhedron = to_glpk_polyhedron(&polyhedron);
-
+        options: &SolveOptions,
+    ) -> Result<SolveOutcome, SolveInputError> {
+        let started = Instant::now();
         // Validate objectives against variables
-        validate_objectives_owned(&glpk_polyhedron.variables, &objectives)?;
+        validate_objectives_owned(&polyhedron.variables, &objectives)?;
 
         // Convert to borrowed objectives for GLPK
         let borrowed_objectives: Vec<HashMap<&str, f64>> = objectives
@@ -47,22 +53,26 @@ impl Solver for GlpkSolver {
 
         let maximize = direction == SolverDirection::Maximize;
 
-        // Solver expects &mut
-        let mut mut_polyhedron = glpk_polyhedron;
+        let glpk_polyhedron = to_glpk_polyhedron(&polyhedron);
+
+        let debug_log = log::log_enabled!(target: "glpk", log::Level::Debug);
+        let mut log = (options.include_log || debug_log).then(String::new);
 
-        // Call the GLPK library solver
-        let lib_solutions = solve_ilps(
-            &mut mut_polyhedron,
-            borrowed_objectives,
+        // Call the GLPK solver
+        let result = solve_ilps(
+            &glpk_polyhedron,
+            &borrowed_objectives,
             maximize,
-            _use_presolve,
-            NO_TERMINAL_OUTPUT,
-        )?;
-
-        // Convert GLPK solutions to API solutions
-        let api_solutions: Vec<ApiSolution> = lib_solutions.into_iter().map(|s| s.into()).collect();
+            options,
+            log.as_mut(),
+        );
+        if let (true, Some(log)) = (debug_log, &log) {
+            log::debug!(target: "glpk", "{}", log.trim_end());
+        }
+        let api_solutions = result?;
 
-        Ok(api_solutions)
+        Ok(SolveOutcome::finished(api_solutions, started)
+            .with_log(log.filter(|_| options.include_log)))
     }
 
     fn name(&self) -> &str {
@@ -1,19 +1,134 @@
-use crate::convert::to_glpk_polyhedron;
-use crate::domain::solver::Solver;
+use crate::domain::solver::{PresolveReductions, SolveOptions, SolveOutcome, Solver};
 use crate::domain::validate::{validate_objectives_owned, SolveInputError};
-use crate::models::{ApiSolution, SolverDirection, SparseLEIntegerPolyhedron, Status};
+use crate::models::{
+    ApiLicenseStatus, ApiSolution, ApiSolverOption, LpMethod, SolverDirection,
+    SparseLEIntegerPolyhedron, Status,
+};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+use grb::callback::{CbResult, Where};
+use grb::parameter::{DoubleParam, IntParam};
 use grb::prelude::*;
 use lru::LruCache;
 use parking_lot::Mutex;
 use std::num::NonZeroUsize;
 
+/// Integer parameters accepted in `solver_options`
+const INT_PARAMS: &[(&str, IntParam)] = &[
+    ("MIPFocus", param::MIPFocus),
+    ("Cuts", param::Cuts),
+    ("Threads", param::Threads),
+    ("Method", param::Method),
+    ("Seed", param::Seed),
+    ("SolutionLimit", param::SolutionLimit),
+];
+
+/// Double parameters accepted in `solver_options`
+const DOUBLE_PARAMS: &[(&str, DoubleParam)] = &[
+    ("Heuristics", param::Heuristics),
+    ("NodeLimit", param::NodeLimit),
+    ("TimeLimit", param::TimeLimit),
+    ("MIPGap", param::MIPGap),
+    ("ImproveStartTime", param::ImproveStartTime),
+];
+
+/// A Gurobi parameter with its value
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum GurobiParam {
+    Int(IntParam, i32),
+    Double(DoubleParam, f64),
+}
+
+impl GurobiParam {
+    fn set(self, model: &mut Model) -> grb::Result<()> {
+        match self {
+            GurobiParam::Int(p, v) => model.set_param(p, v),
+            GurobiParam::Double(p, v) => model.set_param(p, v),
+        }
+    }
+
+    /// The same parameter with the model's current value
+    fn current(self, model: &Model) -> grb::Result<Self> {
+        Ok(match self {
+            GurobiParam::Int(p, _) => GurobiParam::Int(p, model.get_param(p)?),
+            GurobiParam::Double(p, _) => GurobiParam::Double(p, model.get_param(p)?),
+        })
+    }
+}
+
+/// Map `solver_options` onto Gurobi parameters, matching names
+/// case-insensitively as Gurobi does and checking each value's type
+fn gurobi_params(
+    options: &HashMap<String, ApiSolverOption>,
+) -> Result<Vec<GurobiParam>, SolveInputError> {
+    // Sorted so the same request always reports the same error
+    let mut names: Vec<&String> = options.keys().collect();
+    names.sort();
+
+    names
+        .into_iter()
+        .map(|name| {
+            let value = &options[name];
+            if let Some(&(_, p)) = INT_PARAMS
+                .iter()
+                .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            {
+                let value = match value {
+                    ApiSolverOption::Int(v) => i32::try_from(*v).ok(),
+                    _ => None,
+                };
+                return value
+                    .map(|v| GurobiParam::Int(p, v))
+                    .ok_or_else(|| SolveInputError {
+                        details: format!("Gurobi parameter '{}' must be an integer", name),
+                    });
+            }
+            if let Some(&(_, p)) = DOUBLE_PARAMS
+                .iter()
+                .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            {
+                let value = match value {
+                    ApiSolverOption::Int(v) => Some(*v as f64),
+                    ApiSolverOption::Float(v) if v.is_finite() => Some(*v),
+                    _ => None,
+                };
+                return value
+                    .map(|v| GurobiParam::Double(p, v))
+                    .ok_or_else(|| SolveInputError {
+                        details: format!("Gurobi parameter '{}' must be a number", name),
+                    });
+            }
+            Err(SolveInputError {
+                details: format!("Unknown Gurobi parameter '{}'", name),
+            })
+        })
+        .collect()
+}
+
 /// Cached Gurobi model structure
 struct GurobiModel {
     model: Model,
     vars: Vec<Var>,
+    /// Values of the parameters the last solve changed, to restore
+    tuned: Vec<GurobiParam>,
+}
+
+impl GurobiModel {
+    /// Set a request's parameters, first restoring any the previous
+    /// request changed so tuning doesn't leak between requests
+    fn apply_params(&mut self, params: &[GurobiParam]) -> grb::Result<()> {
+        // In reverse, so a parameter given twice ends at its original value
+        for previous in std::mem::take(&mut self.tuned).into_iter().rev() {
+            previous.set(&mut self.model)?;
+        }
+        for &p in params {
+            self.tuned.push(p.current(&self.model)?);
+            p.set(&mut self.model)?;
+        }
+        Ok(())
+    }
 }
 
 // SAFETY: Gurobi model is properly synchronized through Arc and Mutex
@@ -21,6 +136,13 @@ struct GurobiModel {
 unsafe impl Send for GurobiModel {}
 unsafe impl Sync for GurobiModel {}
 
+/// Environment shared by all models of a `GurobiSolver`
+struct SharedEnv(Env);
+
+// SAFETY: Only used behind `GurobiSolver::env`'s mutex, so one thread at a
+// time creates models from it. Models get their own copy of the environment.
+unsafe impl Send for SharedEnv {}
+
 /// Gurobi solver implementation with model caching
 ///
 /// This implementation includes model caching:
@@ -28,8 +150,13 @@ unsafe impl Sync for GurobiModel {}
 /// - LRU eviction policy when cache is full
 /// - Reuses cached models across multiple objectives
 /// - Thread-safe via parking_lot::Mutex
+///
+/// One environment is started on first use and shared by all models, so the
+/// license (or compute-server token) is checked out once rather than per
+/// request. It is started again if creating a model from it fails.
 pub struct GurobiSolver {
     model_cache: Option<Arc<Mutex<LruCache<SparseLEIntegerPolyhedron, Arc<Mutex<GurobiModel>>>>>>,
+    env: Mutex<Option<SharedEnv>>,
 }
 
 impl GurobiSolver {
@@ -41,13 +168,17 @@ impl GurobiSolver {
                 model_cache: Some(Arc::new(Mutex::new(LruCache::new(
                     NonZeroUsize::new(s).unwrap(),
                 )))),
+                env: Mutex::new(None),
             },
         }
     }
 
     /// Create solver with caching disabled
     pub fn without_cache() -> Self {
-        GurobiSolver { model_cache: None }
+        GurobiSolver {
+            model_cache: None,
+            env: Mutex::new(None),
+        }
     }
 
     /// Convert Gurobi status to our API status
@@ -56,116 +187,141 @@ impl GurobiSolver {
             grb::Status::Optimal => Status::Optimal,
             grb::Status::Infeasible => Status::Infeasible,
             grb::Status::InfOrUnbd | grb::Status::Unbounded => Status::Unbounded,
+            grb::Status::TimeLimit => Status::TimeLimit,
             _ => Status::Undefined,
         }
     }
 
-    /// Build a new Gurobi model for the given polyhedron
-    fn build_model(
-        polyhedron: &SparseLEIntegerPolyhedron,
-        use_presolve: bool,
-    ) -> Result<Arc<Mutex<GurobiModel>>, SolveInputError> {
-        // Create Gurobi environment
+    /// Start an environment with the settings every model shares
+    fn start_env() -> Result<Env, SolveInputError> {
         let mut env = Env::new("").map_err(|e| SolveInputError {
             details: format!("Failed to create Gurobi environment: {}", e),
         })?;
 
-        // Disable Gurobi console output
+        // Disable Gurobi console output. Models switch output on when the
+        // log is captured, which then only reaches the message callback
         env.set(param::OutputFlag, 0).map_err(|e| SolveInputError {
             details: format!("Failed to set Gurobi output flag: {}", e),
         })?;
+        env.set(param::LogToConsole, 0)
+            .map_err(|e| SolveInputError {
+                details: format!("Failed to set Gurobi console logging: {}", e),
+            })?;
 
         // Use all available threads
         env.set(param::Threads, 0).map_err(|e| SolveInputError {
             details: format!("Failed to set Gurobi thread count: {}", e),
         })?;
 
+        Ok(env)
+    }
+
+    /// Create an empty model from the shared environment, starting it first
+    /// if needed
+    fn new_model(&self) -> Result<Model, SolveInputError> {
+        let mut env = self.env.lock();
+        if env.is_none() {
+            *env = Some(SharedEnv(Self::start_env()?));
+        }
+        let model = Model::with_env("optimization", &env.as_ref().unwrap().0);
+        model.map_err(|e| {
+            // E.g. a lost license; start a fresh environment next time
+            *env = None;
+            SolveInputError {
+                details: format!("Failed to create Gurobi model: {}", e),
+            }
+        })
+    }
+
+    /// Build a new Gurobi model for the given polyhedron
+    fn build_model(
+        &self,
+        polyhedron: &SparseLEIntegerPolyhedron,
+        use_presolve: bool,
+    ) -> Result<Arc<Mutex<GurobiModel>>, SolveInputError> {
+        let mut model = self.new_model()?;
+
         // Configure presolve: -1 = auto, 0 = off, 1 = conservative, 2 = aggressive
-        env.set(param::Presolve, if use_presolve { -1 } else { 0 })
+        model
+            .set_param(param::Presolve, if use_presolve { -1 } else { 0 })
             .map_err(|e| SolveInputError {
                 details: format!("Failed to set Gurobi presolve: {}", e),
             })?;
 
-        // Create a Gurobi model
-        let mut model = Model::with_env("optimization", &env).map_err(|e| SolveInputError {
-            details: format!("Failed to create Gurobi model: {}", e),
-        })?;
-
-        // Add variables
-        let mut vars: Vec<Var> = Vec::new();
-        for var in polyhedron.variables.iter() {
-            let (lower, upper) = var.bound;
-
-            // Use binary variables for [0,1] bounds
-            let gurobi_var = if lower == 0 && upper == 1 {
-                add_binvar!(
-                    model,
-                    name: &var.id
-                )
-                .map_err(|e| SolveInputError {
-                    details: format!("Failed to add binary variable: {}", e),
-                })?
-            } else {
-                add_intvar!(
-                    model,
-                    name: &var.id,
-                    bounds: lower as f64..upper as f64
+        // Add variables, using binary variables for [0,1] bounds. Gurobi
+        // buffers them until the update below, which also takes the
+        // constraints, so the model is built in a single update
+        let vars = polyhedron
+            .variables
+            .iter()
+            .map(|var| {
+                let (lower, upper) = var.bound;
+                let vtype = if lower == 0 && upper == 1 {
+                    VarType::Binary
+                } else {
+                    VarType::Integer
+                };
+                model.add_var(
+                    &var.id,
+                    vtype,
+                    0.0,
+                    lower as f64,
+                    upper as f64,
+                    std::iter::empty(),
                 )
-                .map_err(|e| SolveInputError {
-                    details: format!("Failed to add integer variable: {}", e),
-                })?
-            };
-
-            vars.push(gurobi_var);
-        }
-
-        model.update().map_err(|e| SolveInputError {
-            details: format!("Failed to update model after adding variables: {}", e),
-        })?;
+            })
+            .collect::<grb::Result<Vec<Var>>>()
+            .map_err(|e| SolveInputError {
+                details: format!("Failed to add variables: {}", e),
+            })?;
 
-        // Build sparse matrix structure
+        // Group the sparse matrix by row in one pass
         let n_rows = polyhedron.a.shape.nrows;
         let n_cols = polyhedron.a.shape.ncols;
         let mut row_data: Vec<Vec<(usize, f64)>> = vec![Vec::new(); n_rows];
-
-        for i in 0..polyhedron.a.rows.len() {
-            let row = polyhedron.a.rows[i] as usize;
-            let col = polyhedron.a.cols[i] as usize;
-            let val = polyhedron.a.vals[i] as f64;
-
+        for ((&row, &col), &val) in polyhedron
+            .a
+            .rows
+            .iter()
+            .zip(&polyhedron.a.cols)
+            .zip(&polyhedron.a.vals)
+        {
+            let (row, col) = (row as usize, col as usize);
             if row < n_rows && col < n_cols {
-                row_data[row].push((col, val));
+                row_data[row].push((col, val as f64));
             }
         }
 
-        // Add constraints (Ax <= b)
-        for (row_idx, entries) in row_data.iter().enumerate() {
-            if entries.is_empty() {
-                continue;
-            }
-
-            let rhs = polyhedron.b.get(row_idx).copied().unwrap_or(0) as f64;
-
-            // Build linear expression
-            let expr = entries
-                .iter()
-                .fold(Expr::Constant(0.0), |acc, &(col_idx, coeff)| {
-                    acc + coeff * vars[col_idx]
-                });
-
-            let constraint_name = format!("c{}", row_idx);
-            model
-                .add_constr(&constraint_name, c!(expr <= rhs))
-                .map_err(|e| SolveInputError {
-                    details: format!("Failed to add constraint: {}", e),
-                })?;
-        }
+        // Add all constraints (Ax <= b) in one batch, with flat linear
+        // expressions built straight from the row's terms
+        let names: Vec<String> = (0..n_rows).map(|row_idx| format!("c{}", row_idx)).collect();
+        let constraints = row_data
+            .iter()
+            .enumerate()
+            .filter(|(_, entries)| !entries.is_empty())
+            .map(|(row_idx, entries)| {
+                let mut lhs = LinExpr::new();
+                for &(col_idx, coeff) in entries {
+                    lhs.add_term(coeff, vars[col_idx]);
+                }
+                let rhs = polyhedron.b.get(row_idx).copied().unwrap_or(0) as f64;
+                (&names[row_idx], c!(lhs <= rhs))
+            });
+        model
+            .add_constrs(constraints)
+            .map_err(|e| SolveInputError {
+                details: format!("Failed to add constraints: {}", e),
+            })?;
 
         model.update().map_err(|e| SolveInputError {
             details: format!("Failed to update model after adding constraints: {}", e),
         })?;
 
-        Ok(Arc::new(Mutex::new(GurobiModel { model, vars })))
+        Ok(Arc::new(Mutex::new(GurobiModel {
+            model,
+            vars,
+            tuned: Vec::new(),
+        })))
     }
 
     /// Get or build a model for the given polyhedron
@@ -185,7 +341,7 @@ impl GurobiSolver {
                 }
 
                 // Not in cache, build new model
-                let model = Self::build_model(polyhedron, use_presolve)?;
+                let model = self.build_model(polyhedron, use_presolve)?;
 
                 // Store in cache
                 {
@@ -197,7 +353,7 @@ impl GurobiSolver {
             }
             None => {
                 // Cache disabled, always build new model
-                Self::build_model(polyhedron, use_presolve)
+                self.build_model(polyhedron, use_presolve)
             }
         }
     }
@@ -209,15 +365,48 @@ impl Solver for GurobiSolver {
         polyhedron: SparseLEIntegerPolyhedron,
         objectives: Vec<HashMap<String, f64>>,
         direction: SolverDirection,
-        use_presolve: bool,
-    ) -> std::result::Result<Vec<ApiSolution>, SolveInputError> {
-        // Use GLPK polyhedron for validation
-        let glpk_polyhedron = to_glpk_polyhedron(&polyhedron);
-        validate_objectives_owned(&glpk_polyhedron.variables, &objectives)?;
+        options: &SolveOptions,
+    ) -> std::result::Result<SolveOutcome, SolveInputError> {
+        let started = Instant::now();
+        validate_objectives_owned(&polyhedron.variables, &objectives)?;
+        // An explicit "Method" in solver_options comes later, so it wins
+        let mut params: Vec<GurobiParam> = options
+            .method
+            .map(|method| {
+                let value = match method {
+                    LpMethod::Primal => 0,
+                    LpMethod::Dual => 1,
+                    LpMethod::Barrier => 2,
+                };
+                GurobiParam::Int(param::Method, value)
+            })
+            .into_iter()
+            .collect();
+        // Likewise an explicit "MIPGap"
+        if let Some(gap) = options.mip_rel_gap {
+            params.push(GurobiParam::Double(param::MIPGap, gap));
+        }
+        params.extend(gurobi_params(&options.solver_options)?);
 
         // Get or build cached model
-        let cached_model = self.obtain_model(&polyhedron, use_presolve)?;
+        let cached_model = self.obtain_model(&polyhedron, options.use_presolve)?;
         let mut model_lock = cached_model.lock();
+        model_lock
+            .apply_params(&params)
+            .map_err(|e| SolveInputError {
+                details: format!("Failed to set Gurobi parameters: {}", e),
+            })?;
+
+        // Gurobi's log is captured through the message callback, for the
+        // response and for debug logging
+        let debug_log = log::log_enabled!(target: "gurobi", log::Level::Debug);
+        let mut log = (options.include_log || debug_log).then(String::new);
+        model_lock
+            .model
+            .set_param(param::OutputFlag, log.is_some() as i32)
+            .map_err(|e| SolveInputError {
+                details: format!("Failed to set Gurobi output flag: {}", e),
+            })?;
 
         let sense = match direction {
             SolverDirection::Maximize => ModelSense::Maximize,
@@ -225,9 +414,24 @@ impl Solver for GurobiSolver {
         };
 
         let mut solutions = Vec::new();
+        // Reported by the presolve callback of the latest optimize call
+        let mut presolve_reductions = None;
 
         // Solve each objective by updating objective coefficients
         for objective in objectives {
+            options.check_cancelled()?;
+
+            // The time limit covers all objectives, so each gets what is left
+            if options.remaining_time(started) == Some(Duration::ZERO) {
+                solutions.push(ApiSolution {
+                    status: Status::TimeLimit,
+                    objective: 0.0,
+                    solution: HashMap::new(),
+                    error: Some("Time limit reached before this objective was solved".to_string()),
+                });
+                continue;
+            }
+
             // Build objective expression
             let obj_expr = polyhedron.variables.iter().enumerate().fold(
                 Expr::Constant(0.0),
@@ -248,16 +452,67 @@ impl Solver for GurobiSolver {
                     details: format!("Failed to set objective: {}", e),
                 })?;
 
-            // Optimize
-            model_lock.model.optimize().map_err(|e| SolveInputError {
-                details: format!("Failed to optimize: {}", e),
-            })?;
+            // Optimize, recording presolve reductions and the log, and
+            // asking Gurobi to stop once the solve is cancelled or out of time
+            let mut out_of_time = false;
+            let mut on_callback = |w: Where| -> CbResult {
+                out_of_time =
+                    out_of_time || options.remaining_time(started) == Some(Duration::ZERO);
+                let stop = options.cancel.is_cancelled() || out_of_time;
+                match w {
+                    Where::PreSolve(ctx) => {
+                        if let (Ok(rows), Ok(cols)) = (ctx.row_del(), ctx.col_del()) {
+                            presolve_reductions = Some(PresolveReductions {
+                                rows_removed: rows.max(0) as usize,
+                                cols_removed: cols.max(0) as usize,
+                            });
+                        }
+                        if stop {
+                            ctx.terminate();
+                        }
+                    }
+                    Where::Polling(ctx) if stop => ctx.terminate(),
+                    Where::Simplex(ctx) if stop => ctx.terminate(),
+                    Where::MIP(ctx) if stop => ctx.terminate(),
+                    Where::MIPNode(ctx) if stop => ctx.terminate(),
+                    Where::Barrier(ctx) if stop => ctx.terminate(),
+                    Where::Message(ctx) => {
+                        if let (Some(log), Ok(message)) = (log.as_mut(), ctx.message()) {
+                            log.push_str(&message);
+                        }
+                    }
+                    _ => {}
+                }
+                Ok(())
+            };
+            model_lock
+                .model
+                .optimize_with_callback(&mut on_callback)
+                .map_err(|e| SolveInputError {
+                    details: format!("Failed to optimize: {}", e),
+                })?;
+            options.check_cancelled()?;
 
             // Extract solution
             let model_status = model_lock.model.status().map_err(|e| SolveInputError {
                 details: format!("Failed to get model status: {}", e),
             })?;
-            let status = Self::convert_status(model_status);
+            let status = match model_status {
+                grb::Status::Interrupted if out_of_time => Status::TimeLimit,
+                _ => Self::convert_status(model_status),
+            };
+
+            // Stopped by the time limit before finding any solution
+            let solution_count = model_lock.model.get_attr(attr::SolCount).unwrap_or(0);
+            if matches!(status, Status::TimeLimit) && solution_count == 0 {
+                solutions.push(ApiSolution {
+                    status,
+                    objective: 0.0,
+                    solution: HashMap::new(),
+                    error: Some("No feasible solution found within the time limit".to_string()),
+                });
+                continue;
+            }
 
             // Map solution back to variable names
             let mut solution_map: HashMap<String, i32> = HashMap::new();
@@ -280,26 +535,120 @@ impl Solver for GurobiSolver {
                 solution_map.insert(var.id.clone(), value.round() as i32);
             }
 
-            // Calculate objective value
-            let objective_value: f64 = solution_map
-                .iter()
-                .filter_map(|(var_id, &val)| {
-                    objective.get(var_id).map(|coeff| coeff * (val as f64))
-                })
-                .sum();
+            // Only available with a solution, e.g. not when infeasible
+            let objective_value = model_lock.model.get_attr(attr::ObjVal).unwrap_or(0.0);
 
             solutions.push(ApiSolution {
                 status,
-                objective: objective_value.round() as i32,
+                objective: objective_value,
                 solution: solution_map,
                 error: None,
             });
         }
 
-        Ok(solutions)
+        if let (true, Some(log)) = (debug_log, &log) {
+            log::debug!(target: "gurobi", "{}", log.trim_end());
+        }
+
+        Ok(SolveOutcome::finished(solutions, started)
+            .with_presolve_reductions(presolve_reductions)
+            .with_log(log.filter(|_| options.include_log)))
     }
 
     fn name(&self) -> &str {
         "Gurobi"
     }
+
+    fn license_status(&self) -> Option<ApiLicenseStatus> {
+        // Starting the shared environment checks out a license (or a token
+        // on compute-server licenses), which it then holds for later solves
+        let status = match self.new_model() {
+            Ok(model) => ApiLicenseStatus {
+                solver: self.name().to_string(),
+                valid: true,
+                error: None,
+                expires: model
+                    .get_attr(attr::LicenseExpiration)
+                    .ok()
+                    .and_then(license_expiry),
+            },
+            Err(e) => ApiLicenseStatus {
+                solver: self.name().to_string(),
+                valid: false,
+                error: Some(e.details),
+                expires: None,
+            },
+        };
+        Some(status)
+    }
+}
+
+/// Format Gurobi's `LicenseExpiration` (`YYYYMMDD`) as `YYYY-MM-DD`
+///
+/// Perpetual licenses report `99999999` and unknown expiry `0`; both are
+/// `None`.
+fn license_expiry(date: i32) -> Option<String> {
+    if date <= 0 || date == 99_999_999 {
+        return None;
+    }
+    Some(format!(
+        "{:04}-{:02}-{:02}",
+        date / 10_000,
+        date / 100 % 100,
+        date % 100
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_formats_license_expiry() {
+        assert_eq!(license_expiry(20271031), Some("2027-10-31".to_string()));
+        assert_eq!(license_expiry(99_999_999), None);
+        assert_eq!(license_expiry(0), None);
+    }
+
+    fn options(entries: &[(&str, ApiSolverOption)]) -> HashMap<String, ApiSolverOption> {
+        entries
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_maps_solver_options_to_parameters() {
+        let params = gurobi_params(&options(&[
+            ("mipfocus", ApiSolverOption::Int(1)),
+            ("NodeLimit", ApiSolverOption::Int(500)),
+            ("Heuristics", ApiSolverOption::Float(0.2)),
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            params,
+            vec![
+                GurobiParam::Double(param::Heuristics, 0.2),
+                GurobiParam::Double(param::NodeLimit, 500.0),
+                GurobiParam::Int(param::MIPFocus, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rejects_mistyped_and_unknown_parameters() {
+        let err = gurobi_params(&options(&[("Cuts", ApiSolverOption::Float(1.5))])).unwrap_err();
+        assert_eq!(err.details, "Gurobi parameter 'Cuts' must be an integer");
+
+        let err = gurobi_params(&options(&[(
+            "MIPGap",
+            ApiSolverOption::Text("small".to_string()),
+        )]))
+        .unwrap_err();
+        assert_eq!(err.details, "Gurobi parameter 'MIPGap' must be a number");
+
+        let err = gurobi_params(&options(&[("Bogus", ApiSolverOption::Int(1))])).unwrap_err();
+        assert_eq!(err.details, "Unknown Gurobi parameter 'Bogus'");
+    }
 }
@@ -1,21 +1,174 @@
-use crate::convert::to_glpk_polyhedron;
-use crate::domain::solver::Solver;
+use crate::domain::cancellation::CancellationToken;
+use crate::domain::solver::{HighsOptions, SolveOptions, SolveOutcome, Solver};
 use crate::domain::validate::{validate_objectives_owned, SolveInputError};
-use crate::models::{ApiSolution, SolverDirection, SparseLEIntegerPolyhedron, Status};
+use crate::models::{
+    ApiIntegerSparseMatrix, ApiSolution, LpMethod, SolverDirection, SparseLEIntegerPolyhedron,
+    Status,
+};
 use std::collections::HashMap;
 use std::ffi::CString;
-use std::os::raw::c_void;
+use std::os::raw::{c_char, c_int, c_void};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use highs_sys::*;
 use lru::LruCache;
 use parking_lot::Mutex;
 use std::num::NonZeroUsize;
 
+/// HiGHS callback API, declared here with opaque data pointers
+mod callback_ffi {
+    use std::os::raw::{c_char, c_int, c_void};
+
+    /// `kHighsCallbackMipInterrupt`
+    pub const MIP_INTERRUPT: c_int = 6;
+
+    /// `data_in` points at `HighsCallbackDataIn`, whose first field is
+    /// `user_interrupt`
+    pub type HighsCallback = unsafe extern "C" fn(
+        callback_type: c_int,
+        message: *const c_char,
+        data_out: *const c_void,
+        data_in: *mut c_int,
+        user_callback_data: *mut c_void,
+    );
+
+    extern "C" {
+        pub fn Highs_setCallback(
+            highs: *mut c_void,
+            user_callback: HighsCallback,
+            user_callback_data: *mut c_void,
+        ) -> c_int;
+        pub fn Highs_startCallback(highs: *mut c_void, callback_type: c_int) -> c_int;
+    }
+}
+
+/// Interrupt callback; `user_callback_data` points at the request's token
+unsafe extern "C" fn interrupt_when_cancelled(
+    _callback_type: c_int,
+    _message: *const c_char,
+    _data_out: *const c_void,
+    data_in: *mut c_int,
+    user_callback_data: *mut c_void,
+) {
+    let cancel = &*(user_callback_data as *const CancellationToken);
+    if cancel.is_cancelled() && !data_in.is_null() {
+        *data_in = 1;
+    }
+}
+
+/// Convert the coordinate-format matrix to CSC (Compressed Sparse Column)
+///
+/// Returns `(col_start, row_index, value)` with `n_cols + 1` column starts.
+/// Entries are bucketed by column in one counting pass, keeping their input
+/// order within each column; entries outside `0..n_cols` are dropped.
+fn to_csc(a: &ApiIntegerSparseMatrix, n_cols: usize) -> (Vec<i32>, Vec<i32>, Vec<f64>) {
+    let entries = || {
+        a.rows
+            .iter()
+            .zip(&a.cols)
+            .zip(&a.vals)
+            .map(|((&row, &col), &val)| (row, col as usize, val))
+            .filter(|&(_, col, _)| col < n_cols)
+    };
+
+    // Count entries per column, then turn the counts into start offsets
+    let mut col_start = vec![0i32; n_cols + 1];
+    for (_, col, _) in entries() {
+        col_start[col + 1] += 1;
+    }
+    for col in 0..n_cols {
+        col_start[col + 1] += col_start[col];
+    }
+
+    let nnz = col_start[n_cols] as usize;
+    let mut row_index = vec![0i32; nnz];
+    let mut value = vec![0.0; nnz];
+    let mut next: Vec<usize> = col_start[..n_cols].iter().map(|&s| s as usize).collect();
+    for (row, col, val) in entries() {
+        row_index[next[col]] = row;
+        value[next[col]] = val as f64;
+        next[col] += 1;
+    }
+    (col_start, row_index, value)
+}
+
+/// `kHighsStatusError`
+const HIGHS_STATUS_ERROR: i32 = -1;
+/// `kHighsMatrixFormatColwise`
+const HIGHS_MATRIX_FORMAT_COLWISE: i32 = 1;
+/// `kHighsObjSenseMinimize`
+const HIGHS_OBJ_SENSE_MINIMIZE: i32 = 1;
+/// `kHighsVarTypeInteger`
+const HIGHS_VAR_TYPE_INTEGER: i32 = 1;
+/// `kSolutionStatusFeasible`
+const HIGHS_SOLUTION_STATUS_FEASIBLE: i32 = 2;
+/// HiGHS's default `mip_rel_gap`
+const HIGHS_DEFAULT_MIP_REL_GAP: f64 = 1e-4;
+/// `simplex_strategy` values: serial dual (HiGHS's default) and primal
+const HIGHS_SIMPLEX_STRATEGY_DUAL: i32 = 1;
+const HIGHS_SIMPLEX_STRATEGY_PRIMAL: i32 = 4;
+
+unsafe fn set_double_option(highs_ptr: *mut c_void, name: &str, value: f64) {
+    let name = CString::new(name).unwrap();
+    Highs_setDoubleOptionValue(highs_ptr, name.as_ptr(), value);
+}
+
+/// Apply per-request settings, resetting unset ones to HiGHS's defaults since
+/// cached models are shared between requests
+unsafe fn apply_options(highs_ptr: *mut c_void, highs: &HighsOptions, method: Option<LpMethod>) {
+    let threads = CString::new("threads").unwrap();
+    Highs_setIntOptionValue(
+        highs_ptr,
+        threads.as_ptr(),
+        highs.threads.unwrap_or(0) as i32,
+    );
+
+    let parallel = CString::new("parallel").unwrap();
+    let parallel_value = CString::new(highs.parallel.map_or("choose", |p| p.name())).unwrap();
+    Highs_setStringOptionValue(highs_ptr, parallel.as_ptr(), parallel_value.as_ptr());
+
+    let simplex_strategy = CString::new("simplex_strategy").unwrap();
+    Highs_setIntOptionValue(
+        highs_ptr,
+        simplex_strategy.as_ptr(),
+        simplex_strategy_for(highs, method),
+    );
+
+    let solver = CString::new("solver").unwrap();
+    let solver_value = CString::new(match method {
+        Some(LpMethod::Barrier) => "ipm",
+        _ => "choose",
+    })
+    .unwrap();
+    Highs_setStringOptionValue(highs_ptr, solver.as_ptr(), solver_value.as_ptr());
+}
+
+/// HiGHS `simplex_strategy` for the settings and LP method
+///
+/// A requested method overrides the simplex variant, though a dual
+/// variant from the settings is kept when the dual method is requested.
+fn simplex_strategy_for(highs: &HighsOptions, method: Option<LpMethod>) -> i32 {
+    match method {
+        Some(LpMethod::Primal) => HIGHS_SIMPLEX_STRATEGY_PRIMAL,
+        Some(LpMethod::Dual) => highs
+            .simplex_strategy
+            .filter(|strategy| (1..=3).contains(strategy))
+            .unwrap_or(HIGHS_SIMPLEX_STRATEGY_DUAL),
+        _ => highs
+            .simplex_strategy
+            .unwrap_or(HIGHS_SIMPLEX_STRATEGY_DUAL),
+    }
+}
+
 /// Cached HiGHS model structure
 struct HighsModel {
     highs_ptr: *mut c_void,
     n_cols: i32,
+    /// Last feasible point found on this model, offered to HiGHS as the MIP
+    /// start of the next solve. Any earlier objective's optimum is feasible
+    /// for the next one, since only the costs change.
+    incumbent: Option<Vec<f64>>,
 }
 
 // `HighsModel` contains a raw pointer to a HiGHS instance, which is
@@ -43,6 +196,7 @@ impl Drop for HighsModel {
 /// - Models are cached based on polyhedron hash
 /// - LRU eviction policy when cache is full
 /// - Reuses cached models across multiple objectives
+/// - Starts each solve from the model's last feasible point (MIP start)
 /// - Thread-safe via parking_lot::Mutex
 pub struct HighsSolver {
     model_cache: Option<Arc<Mutex<LruCache<SparseLEIntegerPolyhedron, Arc<Mutex<HighsModel>>>>>>,
@@ -72,6 +226,7 @@ impl HighsSolver {
         const HIGHS_MODEL_STATUS_INFEASIBLE: i32 = 8;
         const HIGHS_MODEL_STATUS_UNBOUNDED: i32 = 10;
         const HIGHS_MODEL_STATUS_UNBOUNDED_OR_INFEASIBLE: i32 = 9;
+        const HIGHS_MODEL_STATUS_TIME_LIMIT: i32 = 13;
 
         match status {
             HIGHS_MODEL_STATUS_OPTIMAL => Status::Optimal,
@@ -79,6 +234,7 @@ impl HighsSolver {
             HIGHS_MODEL_STATUS_UNBOUNDED | HIGHS_MODEL_STATUS_UNBOUNDED_OR_INFEASIBLE => {
                 Status::Unbounded
             }
+            HIGHS_MODEL_STATUS_TIME_LIMIT => Status::TimeLimit,
             _ => Status::Undefined,
         }
     }
@@ -119,38 +275,8 @@ impl HighsSolver {
         let row_lower = vec![f64::NEG_INFINITY; n_rows as usize];
         let row_upper: Vec<f64> = polyhedron.b.iter().map(|&b| b as f64).collect();
 
-        // Add rows FIRST (before columns reference them)
-        unsafe {
-            Highs_addRows(
-                highs_ptr,
-                n_rows,
-                row_lower.as_ptr(),
-                row_upper.as_ptr(),
-                0, // num_nz = 0, will add via columns
-                std::ptr::null(),
-                std::ptr::null(),
-                std::ptr::null(),
-            );
-        }
-
         // Build sparse constraint matrix in CSC (Column Sparse Compressed) format
-        let mut col_start: Vec<i32> = Vec::with_capacity((n_cols + 1) as usize);
-        let mut col_index: Vec<i32> = Vec::new();
-        let mut col_value: Vec<f64> = Vec::new();
-
-        // Build column-wise sparse matrix
-        for col_idx in 0..n_cols as usize {
-            col_start.push(col_index.len() as i32);
-
-            // Find all entries for this column
-            for i in 0..polyhedron.a.rows.len() {
-                if polyhedron.a.cols[i] as usize == col_idx {
-                    col_index.push(polyhedron.a.rows[i] as i32);
-                    col_value.push(polyhedron.a.vals[i] as f64);
-                }
-            }
-        }
-        col_start.push(col_index.len() as i32); // Final element
+        let (col_start, col_index, col_value) = to_csc(&polyhedron.a, n_cols as usize);
 
         // Prepare column bounds and costs (zero costs, will be updated per objective)
         let col_costs = vec![0.0; n_cols as usize];
@@ -164,30 +290,41 @@ impl HighsSolver {
             .iter()
             .map(|v| v.bound.1 as f64)
             .collect();
+        let integrality = vec![HIGHS_VAR_TYPE_INTEGER; n_cols as usize];
 
-        // Add columns with constraints
-        unsafe {
-            Highs_addCols(
+        // Pass the whole model, integrality included, in one call
+        let status = unsafe {
+            Highs_passMip(
                 highs_ptr,
                 n_cols,
+                n_rows,
+                col_index.len() as i32,
+                HIGHS_MATRIX_FORMAT_COLWISE,
+                HIGHS_OBJ_SENSE_MINIMIZE, // Set per solve
+                0.0,
                 col_costs.as_ptr(),
                 col_lower.as_ptr(),
                 col_upper.as_ptr(),
-                col_index.len() as i32,
+                row_lower.as_ptr(),
+                row_upper.as_ptr(),
                 col_start.as_ptr(),
                 col_index.as_ptr(),
                 col_value.as_ptr(),
-            );
-        }
-
-        // Set integrality for all columns
-        for col_idx in 0..n_cols {
-            unsafe {
-                Highs_changeColIntegrality(highs_ptr, col_idx, 1); // 1 = integer
-            }
+                integrality.as_ptr(),
+            )
+        };
+        if status == HIGHS_STATUS_ERROR {
+            unsafe { Highs_destroy(highs_ptr) };
+            return Err(SolveInputError {
+                details: "HiGHS rejected the model".to_string(),
+            });
         }
 
-        Ok(Arc::new(Mutex::new(HighsModel { highs_ptr, n_cols })))
+        Ok(Arc::new(Mutex::new(HighsModel {
+            highs_ptr,
+            n_cols,
+            incumbent: None,
+        })))
     }
 
     /// Get or build a model for the given polyhedron
@@ -231,15 +368,14 @@ impl Solver for HighsSolver {
         polyhedron: SparseLEIntegerPolyhedron,
         objectives: Vec<HashMap<String, f64>>,
         direction: SolverDirection,
-        use_presolve: bool,
-    ) -> Result<Vec<ApiSolution>, SolveInputError> {
-        // Use GLPK polyhedron for validation
-        let glpk_polyhedron = to_glpk_polyhedron(&polyhedron);
-        validate_objectives_owned(&glpk_polyhedron.variables, &objectives)?;
+        options: &SolveOptions,
+    ) -> Result<SolveOutcome, SolveInputError> {
+        let started = Instant::now();
+        validate_objectives_owned(&polyhedron.variables, &objectives)?;
 
         // Get or build cached model, then lock mutex for entire solve call
-        let model_mutex = self.obtain_model(&polyhedron, use_presolve)?;
-        let model = model_mutex.lock();
+        let model_mutex = self.obtain_model(&polyhedron, options.use_presolve)?;
+        let mut model = model_mutex.lock();
 
         let highs_ptr = model.highs_ptr;
         let n_cols = model.n_cols;
@@ -249,14 +385,58 @@ impl Solver for HighsSolver {
             SolverDirection::Minimize => 1,
             SolverDirection::Maximize => -1,
         };
-        unsafe {
+        let callback_status = unsafe {
             Highs_changeObjectiveSense(highs_ptr, sense);
+            apply_options(highs_ptr, &options.highs, options.method);
+            set_double_option(
+                highs_ptr,
+                "mip_rel_gap",
+                options.mip_rel_gap.unwrap_or(HIGHS_DEFAULT_MIP_REL_GAP),
+            );
+            // Cached models are shared, so point the callback at this
+            // request's token while the model is locked. Setting the
+            // callback deactivates every callback type, so the interrupt
+            // is started again afterwards
+            match callback_ffi::Highs_setCallback(
+                highs_ptr,
+                interrupt_when_cancelled,
+                &options.cancel as *const CancellationToken as *mut c_void,
+            ) {
+                HIGHS_STATUS_ERROR => HIGHS_STATUS_ERROR,
+                _ => callback_ffi::Highs_startCallback(highs_ptr, callback_ffi::MIP_INTERRUPT),
+            }
+        };
+        if callback_status == HIGHS_STATUS_ERROR {
+            return Err(SolveInputError {
+                details: "Failed to start the HiGHS interrupt callback".to_string(),
+            });
         }
 
         let mut solutions = Vec::with_capacity(objectives.len());
 
         // Solve each objective by updating objective coefficients
         for objective in objectives {
+            options.check_cancelled()?;
+
+            // The time limit covers all objectives, so each gets what is left
+            let remaining = options.remaining_time(started);
+            if remaining == Some(Duration::ZERO) {
+                solutions.push(ApiSolution {
+                    status: Status::TimeLimit,
+                    objective: 0.0,
+                    solution: HashMap::new(),
+                    error: Some("Time limit reached before this objective was solved".to_string()),
+                });
+                continue;
+            }
+            unsafe {
+                set_double_option(
+                    highs_ptr,
+                    "time_limit",
+                    remaining.map_or(f64::INFINITY, |remaining| remaining.as_secs_f64()),
+                );
+            }
+
             // Update objective coefficients
             for (col_idx, var) in polyhedron.variables.iter().enumerate() {
                 let obj_coeff = objective.get(&var.id).copied().unwrap_or(0.0);
@@ -265,12 +445,31 @@ impl Solver for HighsSolver {
                 }
             }
 
+            // Changing costs keeps the basis but drops the stored solution,
+            // so hand the previous incumbent back as a MIP start
+            if let Some(incumbent) = &model.incumbent {
+                let accepted = unsafe {
+                    Highs_setSolution(
+                        highs_ptr,
+                        incumbent.as_ptr(),
+                        std::ptr::null(),
+                        std::ptr::null(),
+                        std::ptr::null(),
+                    )
+                };
+                if accepted != 0 {
+                    log::debug!(target: "highs", "MIP start rejected with status {}", accepted);
+                }
+            }
+
             // Solve
             let status = unsafe { Highs_run(highs_ptr) };
-            if status != 0 {
+            options.check_cancelled()?;
+            // Stopping at the time limit is only a warning
+            if status == HIGHS_STATUS_ERROR {
                 solutions.push(ApiSolution {
                     status: Status::Undefined,
-                    objective: 0,
+                    objective: 0.0,
                     solution: HashMap::new(),
                     error: Some(format!("HiGHS solve failed with status {}", status)),
                 });
@@ -293,6 +492,23 @@ impl Solver for HighsSolver {
                 );
             }
 
+            let mut primal_status: c_int = 0;
+            let info_name = CString::new("primal_solution_status").unwrap();
+            unsafe {
+                Highs_getIntInfoValue(highs_ptr, info_name.as_ptr(), &mut primal_status);
+            }
+            if primal_status == HIGHS_SOLUTION_STATUS_FEASIBLE {
+                model.incumbent = Some(solution_values.clone());
+            } else if matches!(api_status, Status::TimeLimit) {
+                solutions.push(ApiSolution {
+                    status: Status::TimeLimit,
+                    objective: 0.0,
+                    solution: HashMap::new(),
+                    error: Some("No feasible solution found within the time limit".to_string()),
+                });
+                continue;
+            }
+
             // Map solution back to variable names
             let mut solution_map: HashMap<String, i32> = HashMap::new();
             for (col_idx, var) in polyhedron.variables.iter().enumerate() {
@@ -301,23 +517,15 @@ impl Solver for HighsSolver {
                 solution_map.insert(var.id.clone(), rounded_value);
             }
 
-            // Calculate objective value
-            let objective_value: f64 = solution_map
-                .iter()
-                .filter_map(|(var_id, &val)| {
-                    objective.get(var_id).map(|coeff| coeff * (val as f64))
-                })
-                .sum();
-
             solutions.push(ApiSolution {
                 status: api_status,
-                objective: objective_value.round() as i32,
+                objective: unsafe { Highs_getObjectiveValue(highs_ptr) },
                 solution: solution_map,
                 error: None,
             });
         }
 
-        Ok(solutions)
+        Ok(SolveOutcome::finished(solutions, started))
     }
 
     fn name(&self) -> &str {
@@ -353,6 +561,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_to_csc_buckets_entries_by_column() {
+        // Entries given out of column order, plus one past the last column
+        let a = ApiIntegerSparseMatrix {
+            rows: vec![0, 1, 0, 2, 1],
+            cols: vec![2, 0, 0, 2, 5],
+            vals: vec![1, 2, 3, 4, 5],
+            shape: ApiShape { nrows: 3, ncols: 3 },
+        };
+        let (col_start, row_index, value) = to_csc(&a, 3);
+        assert_eq!(col_start, vec![0, 2, 2, 4]);
+        assert_eq!(row_index, vec![1, 0, 0, 2]);
+        assert_eq!(value, vec![2.0, 3.0, 1.0, 4.0]);
+    }
+
     #[test]
     fn test_cache_reuses_model() {
         let solver = HighsSolver::with_cache_size(Some(10));
@@ -371,7 +594,7 @@ mod tests {
             polyhedron.clone(),
             vec![obj1.clone()],
             SolverDirection::Maximize,
-            true,
+            &SolveOptions::with_presolve(true),
         );
         assert!(result1.is_ok());
 
@@ -380,7 +603,7 @@ mod tests {
             polyhedron.clone(),
             vec![obj2],
             SolverDirection::Maximize,
-            true,
+            &SolveOptions::with_presolve(true),
         );
         assert!(result2.is_ok());
 
@@ -389,11 +612,38 @@ mod tests {
             polyhedron.clone(),
             vec![obj1],
             SolverDirection::Maximize,
-            true,
+            &SolveOptions::with_presolve(true),
         );
         assert!(result3.is_ok());
     }
 
+    #[test]
+    fn test_keeps_incumbent_between_objectives() {
+        let solver = HighsSolver::with_cache_size(Some(10));
+        let polyhedron = create_test_polyhedron();
+        let objectives = vec![
+            HashMap::from([("x".to_string(), 1.0), ("y".to_string(), 3.0)]),
+            HashMap::from([("x".to_string(), 1.0)]),
+        ];
+
+        let outcome = solver
+            .solve(
+                polyhedron.clone(),
+                objectives,
+                SolverDirection::Maximize,
+                &SolveOptions::with_presolve(true),
+            )
+            .ok()
+            .unwrap();
+        assert_eq!(outcome.solutions[0].objective, 15.0);
+        // Starting the second objective from (0, 5) must not change its optimum
+        assert_eq!(outcome.solutions[1].objective, 10.0);
+
+        // The cached model holds the last optimum for the next request
+        let model = solver.obtain_model(&polyhedron, true).ok().unwrap();
+        assert_eq!(model.lock().incumbent, Some(vec![10.0, 0.0]));
+    }
+
     #[test]
     fn test_cache_disabled() {
         let solver = HighsSolver::without_cache();
@@ -403,7 +653,104 @@ mod tests {
         obj.insert("x".to_string(), 1.0);
         obj.insert("y".to_string(), 2.0);
 
-        let result = solver.solve(polyhedron, vec![obj], SolverDirection::Maximize, true);
+        let result = solver.solve(
+            polyhedron,
+            vec![obj],
+            SolverDirection::Maximize,
+            &SolveOptions::with_presolve(true),
+        );
         assert!(result.is_ok());
     }
+
+    /// Market split instance: `rows` equality constraints over `10 * (rows - 1)`
+    /// binaries, which branch and bound takes a very long time to settle
+    fn market_split(rows: usize) -> SparseLEIntegerPolyhedron {
+        let n = 10 * (rows - 1);
+        let mut seed: u64 = 42;
+        let mut next = || {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((seed >> 33) % 100) as i32
+        };
+        let (mut a_rows, mut a_cols, mut a_vals, mut b) = (vec![], vec![], vec![], vec![]);
+        for row in 0..rows {
+            let coeffs: Vec<i32> = (0..n).map(|_| next()).collect();
+            let rhs = coeffs.iter().sum::<i32>() / 2;
+            // a·x = rhs as a·x <= rhs and -a·x <= -rhs
+            for (sign, bound) in [(1, rhs), (-1, -rhs)] {
+                for (col, &coeff) in coeffs.iter().enumerate() {
+                    a_rows.push(2 * row as i32 + (sign < 0) as i32);
+                    a_cols.push(col as i32);
+                    a_vals.push(sign * coeff);
+                }
+                b.push(bound);
+            }
+        }
+        SparseLEIntegerPolyhedron {
+            a: ApiIntegerSparseMatrix {
+                rows: a_rows,
+                cols: a_cols,
+                vals: a_vals,
+                shape: ApiShape {
+                    nrows: 2 * rows,
+                    ncols: n,
+                },
+            },
+            b,
+            variables: (0..n)
+                .map(|col| ApiVariable {
+                    id: format!("x{}", col),
+                    bound: (0, 1),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_cancel_interrupts_running_mip() {
+        let solver = HighsSolver::with_cache_size(Some(10));
+        let polyhedron = market_split(6);
+        let objective = HashMap::from([("x0".to_string(), 1.0)]);
+        // Without a working interrupt the solve ends at the time limit instead
+        let options = SolveOptions {
+            time_limit: Some(Duration::from_secs(60)),
+            ..SolveOptions::with_presolve(true)
+        };
+
+        let cancel = options.cancel.clone();
+        let canceller = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(500));
+            cancel.cancel();
+        });
+        let started = Instant::now();
+        let result = solver.solve(
+            polyhedron,
+            vec![objective],
+            SolverDirection::Maximize,
+            &options,
+        );
+        canceller.join().unwrap();
+
+        assert_eq!(result.err().unwrap().details, "Solve cancelled");
+        assert!(started.elapsed() < Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_method_overrides_simplex_strategy() {
+        let pami = HighsOptions {
+            simplex_strategy: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(simplex_strategy_for(&pami, None), 2);
+        assert_eq!(simplex_strategy_for(&pami, Some(LpMethod::Dual)), 2);
+        assert_eq!(simplex_strategy_for(&pami, Some(LpMethod::Primal)), 4);
+
+        let primal = HighsOptions {
+            simplex_strategy: Some(4),
+            ..Default::default()
+        };
+        assert_eq!(simplex_strategy_for(&primal, Some(LpMethod::Dual)), 1);
+        assert_eq!(simplex_strategy_for(&HighsOptions::default(), None), 1);
+    }
 }
@@ -0,0 +1,332 @@
+use crate::domain::cancellation::CancellationToken;
+use crate::domain::solver::{SolveOptions, SolveOutcome, Solver};
+use crate::domain::validate::{validate_objectives_owned, SolveInputError};
+use crate::models::{ApiSolution, SolverDirection, SparseLEIntegerPolyhedron, Status};
+use std::collections::HashMap;
+use std::os::raw::{c_int, c_uchar, c_void};
+use std::time::Instant;
+
+/// Bindings to the parts of the lp_solve 5.5 C API used here
+mod ffi {
+    use std::os::raw::{c_int, c_uchar, c_void};
+
+    #[repr(C)]
+    pub struct LpRec {
+        _private: [u8; 0],
+    }
+
+    pub const LE: c_int = 1;
+    pub const NEUTRAL: c_int = 0;
+    pub const PRESOLVE_NONE: c_int = 0;
+    pub const PRESOLVE_ROWS: c_int = 1;
+    pub const PRESOLVE_COLS: c_int = 2;
+    pub const PRESOLVE_LINDEP: c_int = 4;
+
+    pub const OPTIMAL: c_int = 0;
+    pub const SUBOPTIMAL: c_int = 1;
+    pub const INFEASIBLE: c_int = 2;
+    pub const UNBOUNDED: c_int = 3;
+    pub const USERABORT: c_int = 6;
+
+    /// Polled during the solve; a non-zero return aborts it
+    pub type AbortFunc = unsafe extern "C" fn(lp: *mut LpRec, userhandle: *mut c_void) -> c_int;
+
+    #[link(name = "lpsolve55")]
+    extern "C" {
+        pub fn make_lp(rows: c_int, columns: c_int) -> *mut LpRec;
+        pub fn delete_lp(lp: *mut LpRec);
+        pub fn set_verbose(lp: *mut LpRec, verbose: c_int);
+        pub fn set_maxim(lp: *mut LpRec);
+        pub fn set_minim(lp: *mut LpRec);
+        pub fn set_presolve(lp: *mut LpRec, mode: c_int, maxloop: c_int);
+        pub fn get_presolveloop(lp: *mut LpRec) -> c_int;
+        pub fn set_add_rowmode(lp: *mut LpRec, turnon: c_uchar) -> c_uchar;
+        pub fn set_obj_fnex(
+            lp: *mut LpRec,
+            count: c_int,
+            row: *mut f64,
+            colno: *mut c_int,
+        ) -> c_uchar;
+        pub fn add_constraintex(
+            lp: *mut LpRec,
+            count: c_int,
+            row: *mut f64,
+            colno: *mut c_int,
+            constr_type: c_int,
+            rh: f64,
+        ) -> c_uchar;
+        pub fn set_bounds(lp: *mut LpRec, colnr: c_int, lower: f64, upper: f64) -> c_uchar;
+        pub fn set_int(lp: *mut LpRec, colnr: c_int, must_be_int: c_uchar) -> c_uchar;
+        pub fn put_abortfunc(lp: *mut LpRec, newctrlc: AbortFunc, ctrlchandle: *mut c_void);
+        pub fn solve(lp: *mut LpRec) -> c_int;
+        pub fn get_objective(lp: *mut LpRec) -> f64;
+        pub fn get_variables(lp: *mut LpRec, var: *mut f64) -> c_uchar;
+    }
+}
+
+const TRUE: c_uchar = 1;
+const FALSE: c_uchar = 0;
+
+/// Owned lp_solve model, deleted on drop
+struct LpModel(*mut ffi::LpRec);
+
+impl Drop for LpModel {
+    fn drop(&mut self) {
+        unsafe { ffi::delete_lp(self.0) }
+    }
+}
+
+/// Abort callback; `handle` points at the request's cancellation token
+unsafe extern "C" fn abort_when_cancelled(_lp: *mut ffi::LpRec, handle: *mut c_void) -> c_int {
+    let cancel = &*(handle as *const CancellationToken);
+    cancel.is_cancelled() as c_int
+}
+
+/// lp_solve solver implementation, linking the system `liblpsolve55`.
+///
+/// Meant for deployments validated against lp_solve results. Every objective
+/// is solved on a freshly built model, with rows and columns added in request
+/// order, so answers match a standalone lp_solve run on the same model.
+///
+/// Note: Model caching is not supported to keep runs independent.
+/// The cache_size parameter is accepted for API consistency but has no effect.
+pub struct LpSolveSolver;
+
+impl LpSolveSolver {
+    /// Create a new lp_solve solver with specified cache size
+    /// Note: Cache is not supported for lp_solve, parameter ignored
+    pub fn with_cache_size(_size: Option<usize>) -> Self {
+        LpSolveSolver
+    }
+
+    /// Create solver with caching disabled (same as default for lp_solve)
+    pub fn without_cache() -> Self {
+        LpSolveSolver
+    }
+
+    fn convert_status(status: c_int) -> Status {
+        match status {
+            ffi::OPTIMAL => Status::Optimal,
+            ffi::SUBOPTIMAL => Status::Feasible,
+            ffi::INFEASIBLE => Status::Infeasible,
+            ffi::UNBOUNDED => Status::Unbounded,
+            _ => Status::Undefined,
+        }
+    }
+
+    /// Build the model for one objective
+    fn build_model(
+        polyhedron: &SparseLEIntegerPolyhedron,
+        rows: &[(Vec<f64>, Vec<c_int>)],
+        objective: &HashMap<String, f64>,
+        direction: SolverDirection,
+        use_presolve: bool,
+    ) -> Result<LpModel, SolveInputError> {
+        let n_cols = polyhedron.variables.len() as c_int;
+        let model = LpModel(unsafe { ffi::make_lp(0, n_cols) });
+        if model.0.is_null() {
+            return Err(SolveInputError {
+                details: "Failed to create lp_solve model".to_string(),
+            });
+        }
+        let lp = model.0;
+
+        // Columns are 1-based in lp_solve
+        let (mut obj_values, mut obj_cols): (Vec<f64>, Vec<c_int>) = polyhedron
+            .variables
+            .iter()
+            .enumerate()
+            .filter_map(|(col_idx, var)| {
+                objective
+                    .get(&var.id)
+                    .map(|&coeff| (coeff, col_idx as c_int + 1))
+            })
+            .unzip();
+
+        unsafe {
+            ffi::set_verbose(lp, ffi::NEUTRAL);
+            match direction {
+                SolverDirection::Maximize => ffi::set_maxim(lp),
+                SolverDirection::Minimize => ffi::set_minim(lp),
+            }
+            let presolve = if use_presolve {
+                ffi::PRESOLVE_ROWS | ffi::PRESOLVE_COLS | ffi::PRESOLVE_LINDEP
+            } else {
+                ffi::PRESOLVE_NONE
+            };
+            ffi::set_presolve(lp, presolve, ffi::get_presolveloop(lp));
+
+            // The objective must be set before adding rows in row mode
+            ffi::set_obj_fnex(
+                lp,
+                obj_values.len() as c_int,
+                obj_values.as_mut_ptr(),
+                obj_cols.as_mut_ptr(),
+            );
+
+            ffi::set_add_rowmode(lp, TRUE);
+            for ((values, cols), &rhs) in rows.iter().zip(&polyhedron.b) {
+                let mut values = values.clone();
+                let mut cols = cols.clone();
+                if ffi::add_constraintex(
+                    lp,
+                    values.len() as c_int,
+                    values.as_mut_ptr(),
+                    cols.as_mut_ptr(),
+                    ffi::LE,
+                    rhs as f64,
+                ) == FALSE
+                {
+                    return Err(SolveInputError {
+                        details: "Failed to add constraint to lp_solve model".to_string(),
+                    });
+                }
+            }
+            ffi::set_add_rowmode(lp, FALSE);
+
+            for (col_idx, var) in polyhedron.variables.iter().enumerate() {
+                let col = col_idx as c_int + 1;
+                ffi::set_bounds(lp, col, var.bound.0 as f64, var.bound.1 as f64);
+                ffi::set_int(lp, col, TRUE);
+            }
+        }
+
+        Ok(model)
+    }
+}
+
+impl Solver for LpSolveSolver {
+    fn solve(
+        &self,
+        polyhedron: SparseLEIntegerPolyhedron,
+        objectives: Vec<HashMap<String, f64>>,
+        direction: SolverDirection,
+        options: &SolveOptions,
+    ) -> Result<SolveOutcome, SolveInputError> {
+        let started = Instant::now();
+        validate_objectives_owned(&polyhedron.variables, &objectives)?;
+
+        // Group the sparse matrix by row, with 1-based column numbers
+        let mut rows: Vec<(Vec<f64>, Vec<c_int>)> =
+            vec![(Vec::new(), Vec::new()); polyhedron.a.shape.nrows];
+        for ((&row, &col), &val) in polyhedron
+            .a
+            .rows
+            .iter()
+            .zip(&polyhedron.a.cols)
+            .zip(&polyhedron.a.vals)
+        {
+            let (values, cols) = &mut rows[row as usize];
+            values.push(val as f64);
+            cols.push(col as c_int + 1);
+        }
+
+        let n_cols = polyhedron.variables.len();
+        let mut solutions = Vec::with_capacity(objectives.len());
+        for objective in &objectives {
+            options.check_cancelled()?;
+            let model = Self::build_model(
+                &polyhedron,
+                &rows,
+                objective,
+                direction,
+                options.use_presolve,
+            )?;
+
+            let status = unsafe {
+                // The token outlives the solve call, which is the only time
+                // lp_solve invokes the callback
+                ffi::put_abortfunc(
+                    model.0,
+                    abort_when_cancelled,
+                    &options.cancel as *const CancellationToken as *mut c_void,
+                );
+                ffi::solve(model.0)
+            };
+            if status == ffi::USERABORT {
+                options.check_cancelled()?;
+            }
+            let api_status = Self::convert_status(status);
+            if !matches!(api_status, Status::Optimal | Status::Feasible) {
+                solutions.push(ApiSolution {
+                    error: matches!(api_status, Status::Undefined)
+                        .then(|| format!("lp_solve solve failed with status {}", status)),
+                    status: api_status,
+                    objective: 0.0,
+                    solution: HashMap::new(),
+                });
+                continue;
+            }
+
+            let mut values = vec![0.0; n_cols];
+            let objective_value = unsafe {
+                ffi::get_variables(model.0, values.as_mut_ptr());
+                ffi::get_objective(model.0)
+            };
+
+            let solution_map: HashMap<String, i32> = polyhedron
+                .variables
+                .iter()
+                .zip(&values)
+                .map(|(var, value)| (var.id.clone(), value.round() as i32))
+                .collect();
+
+            solutions.push(ApiSolution {
+                status: api_status,
+                objective: objective_value,
+                solution: solution_map,
+                error: None,
+            });
+        }
+
+        Ok(SolveOutcome::finished(solutions, started))
+    }
+
+    fn name(&self) -> &str {
+        "lp_solve"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ApiIntegerSparseMatrix, ApiShape, ApiVariable};
+
+    #[test]
+    fn test_solves_small_problem() {
+        // x + 2y <= 10, y <= 5
+        let polyhedron = SparseLEIntegerPolyhedron {
+            a: ApiIntegerSparseMatrix {
+                rows: vec![0, 0, 1],
+                cols: vec![0, 1, 1],
+                vals: vec![1, 2, 1],
+                shape: ApiShape { nrows: 2, ncols: 2 },
+            },
+            b: vec![10, 5],
+            variables: vec![
+                ApiVariable {
+                    id: "x".to_string(),
+                    bound: (0, 10),
+                },
+                ApiVariable {
+                    id: "y".to_string(),
+                    bound: (0, 10),
+                },
+            ],
+        };
+        let objective = HashMap::from([("x".to_string(), 1.0), ("y".to_string(), 3.0)]);
+
+        let solutions = LpSolveSolver::without_cache()
+            .solve(
+                polyhedron,
+                vec![objective],
+                SolverDirection::Maximize,
+                &SolveOptions::with_presolve(true),
+            )
+            .ok()
+            .unwrap()
+            .solutions;
+        assert!(matches!(solutions[0].status, Status::Optimal));
+        assert_eq!(solutions[0].objective, 15.0);
+    }
+}
@@ -0,0 +1,244 @@
+use crate::domain::solver::{SolveOptions, SolveOutcome, Solver};
+use crate::domain::validate::{validate_objectives_owned, SolveInputError};
+use crate::models::{ApiSolution, SolverDirection, SparseLEIntegerPolyhedron, Status};
+use microlp::{ComparisonOp, OptimizationDirection, Problem};
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// Pure-Rust solver built on microlp
+///
+/// Has no native dependencies, so it is always compiled in and works in
+/// containers without GLPK system libraries. It is much slower than the
+/// native backends and meant for small models.
+///
+/// Note: microlp models can't be re-solved with a new objective, so a model is
+/// built per objective and the cache_size parameter has no effect.
+pub struct MicrolpSolver;
+
+impl MicrolpSolver {
+    /// Create a new microlp solver with specified cache size
+    /// Note: Cache is not supported for microlp, parameter ignored
+    pub fn with_cache_size(_size: Option<usize>) -> Self {
+        MicrolpSolver
+    }
+
+    /// Create solver with caching disabled (same as default for microlp)
+    pub fn without_cache() -> Self {
+        MicrolpSolver
+    }
+
+    fn solve_objective(
+        polyhedron: &SparseLEIntegerPolyhedron,
+        rows: &[Vec<(usize, f64)>],
+        objective: &HashMap<String, f64>,
+        direction: OptimizationDirection,
+    ) -> ApiSolution {
+        let mut problem = Problem::new(direction);
+        let columns: Vec<_> = polyhedron
+            .variables
+            .iter()
+            .map(|var| {
+                let obj_coeff = objective.get(&var.id).copied().unwrap_or(0.0);
+                problem.add_integer_var(obj_coeff, var.bound)
+            })
+            .collect();
+
+        // Ax <= b, one constraint per row
+        for (row, &rhs) in rows.iter().zip(&polyhedron.b) {
+            let terms: Vec<_> = row
+                .iter()
+                .map(|&(col_idx, coeff)| (columns[col_idx], coeff))
+                .collect();
+            problem.add_constraint(terms, ComparisonOp::Le, rhs as f64);
+        }
+
+        let solution = match problem.solve() {
+            Ok(solution) => solution,
+            Err(microlp::Error::Infeasible) => return empty_solution(Status::Infeasible, None),
+            Err(microlp::Error::Unbounded) => return empty_solution(Status::Unbounded, None),
+            Err(microlp::Error::InternalError(e)) => {
+                return empty_solution(
+                    Status::Undefined,
+                    Some(format!("microlp solve failed: {}", e)),
+                )
+            }
+        };
+
+        // Map solution back to variable names
+        let solution_map: HashMap<String, i32> = polyhedron
+            .variables
+            .iter()
+            .zip(&columns)
+            .map(|(var, &column)| (var.id.clone(), solution.var_value(column).round() as i32))
+            .collect();
+
+        ApiSolution {
+            status: Status::Optimal,
+            objective: solution.objective(),
+            solution: solution_map,
+            error: None,
+        }
+    }
+}
+
+fn empty_solution(status: Status, error: Option<String>) -> ApiSolution {
+    ApiSolution {
+        status,
+        objective: 0.0,
+        solution: HashMap::new(),
+        error,
+    }
+}
+
+impl Solver for MicrolpSolver {
+    fn solve(
+        &self,
+        polyhedron: SparseLEIntegerPolyhedron,
+        objectives: Vec<HashMap<String, f64>>,
+        direction: SolverDirection,
+        options: &SolveOptions, // microlp has no presolve
+    ) -> Result<SolveOutcome, SolveInputError> {
+        let started = Instant::now();
+        validate_objectives_owned(&polyhedron.variables, &objectives)?;
+
+        // Group the sparse matrix by row once, shared by all objectives
+        let mut rows: Vec<Vec<(usize, f64)>> = vec![Vec::new(); polyhedron.a.shape.nrows];
+        for ((&row, &col), &val) in polyhedron
+            .a
+            .rows
+            .iter()
+            .zip(&polyhedron.a.cols)
+            .zip(&polyhedron.a.vals)
+        {
+            rows[row as usize].push((col as usize, val as f64));
+        }
+
+        let direction = match direction {
+            SolverDirection::Maximize => OptimizationDirection::Maximize,
+            SolverDirection::Minimize => OptimizationDirection::Minimize,
+        };
+
+        // microlp can't be interrupted mid-solve, so stop between objectives
+        let solutions = objectives
+            .iter()
+            .map(|objective| {
+                options.check_cancelled()?;
+                Ok(Self::solve_objective(
+                    &polyhedron,
+                    &rows,
+                    objective,
+                    direction,
+                ))
+            })
+            .collect::<Result<_, SolveInputError>>()?;
+        Ok(SolveOutcome::finished(solutions, started))
+    }
+
+    fn name(&self) -> &str {
+        "microlp"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::solver::TerminationReason;
+    use crate::models::{ApiIntegerSparseMatrix, ApiShape, ApiVariable};
+
+    fn create_test_polyhedron() -> SparseLEIntegerPolyhedron {
+        // x + 2y <= 10, y <= 5
+        SparseLEIntegerPolyhedron {
+            a: ApiIntegerSparseMatrix {
+                rows: vec![0, 0, 1],
+                cols: vec![0, 1, 1],
+                vals: vec![1, 2, 1],
+                shape: ApiShape { nrows: 2, ncols: 2 },
+            },
+            b: vec![10, 5],
+            variables: vec![
+                ApiVariable {
+                    id: "x".to_string(),
+                    bound: (0, 10),
+                },
+                ApiVariable {
+                    id: "y".to_string(),
+                    bound: (0, 10),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_solves_each_objective() {
+        let solver = MicrolpSolver::without_cache();
+        let objectives = vec![
+            HashMap::from([("x".to_string(), 1.0), ("y".to_string(), 3.0)]),
+            HashMap::from([("x".to_string(), 1.0)]),
+        ];
+
+        let outcome = solver
+            .solve(
+                create_test_polyhedron(),
+                objectives,
+                SolverDirection::Maximize,
+                &SolveOptions::with_presolve(true),
+            )
+            .ok()
+            .unwrap();
+        let solutions = &outcome.solutions;
+        assert_eq!(outcome.stats.termination, TerminationReason::Completed);
+        assert_eq!(outcome.stats.presolve_reductions, None);
+
+        // 1x + 3y is best at x=0, y=5
+        assert!(matches!(solutions[0].status, Status::Optimal));
+        assert_eq!(solutions[0].objective, 15.0);
+        assert_eq!(solutions[0].solution["y"], 5);
+        // x alone is best at x=10, y=0
+        assert_eq!(solutions[1].objective, 10.0);
+        assert_eq!(solutions[1].solution["x"], 10);
+    }
+
+    #[test]
+    fn test_reports_infeasible_problems() {
+        let mut polyhedron = create_test_polyhedron();
+        // x + 2y <= -1 has no solution with non-negative bounds
+        polyhedron.b = vec![-1, 5];
+
+        let solutions = MicrolpSolver::without_cache()
+            .solve(
+                polyhedron,
+                vec![HashMap::new()],
+                SolverDirection::Minimize,
+                &SolveOptions::with_presolve(true),
+            )
+            .ok()
+            .unwrap()
+            .solutions;
+        assert!(matches!(solutions[0].status, Status::Infeasible));
+    }
+
+    #[test]
+    fn test_rejects_unknown_objective_variables() {
+        let result = MicrolpSolver::without_cache().solve(
+            create_test_polyhedron(),
+            vec![HashMap::from([("z".to_string(), 1.0)])],
+            SolverDirection::Maximize,
+            &SolveOptions::with_presolve(true),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_stops_when_cancelled() {
+        let options = SolveOptions::with_presolve(true);
+        options.cancel.cancel();
+
+        let result = MicrolpSolver::without_cache().solve(
+            create_test_polyhedron(),
+            vec![HashMap::new()],
+            SolverDirection::Maximize,
+            &options,
+        );
+        assert_eq!(result.err().unwrap().details, "Solve cancelled");
+    }
+}
@@ -1,3 +1,12 @@
+pub mod fallback_solver;
+pub mod microlp_solver;
+pub mod parallel_objectives_solver;
+pub mod racing_solver;
+pub mod remote_solver;
+
+#[cfg(feature = "glpk-solver")]
+mod glpk_driver;
+#[cfg(feature = "glpk-solver")]
 pub mod glpk_solver;
 
 #[cfg(feature = "highs-solver")]
@@ -6,6 +15,19 @@ pub mod highs_solver;
 #[cfg(feature = "gurobi-solver")]
 pub mod gurobi_solver;
 
+#[cfg(feature = "lpsolve-solver")]
+pub mod lpsolve_solver;
+
+#[cfg(feature = "cplex-solver")]
+pub mod cplex_solver;
+
+pub use fallback_solver::FallbackSolver;
+pub use microlp_solver::MicrolpSolver;
+pub use parallel_objectives_solver::ParallelObjectivesSolver;
+pub use racing_solver::RacingSolver;
+pub use remote_solver::RemoteSolver;
+
+#[cfg(feature = "glpk-solver")]
 pub use glpk_solver::GlpkSolver;
 
 #[cfg(feature = "highs-solver")]
@@ -13,3 +35,9 @@ pub use highs_solver::HighsSolver;
 
 #[cfg(feature = "gurobi-solver")]
 pub use gurobi_solver::GurobiSolver;
+
+#[cfg(feature = "lpsolve-solver")]
+pub use lpsolve_solver::LpSolveSolver;
+
+#[cfg(feature = "cplex-solver")]
+pub use cplex_solver::CplexSolver;
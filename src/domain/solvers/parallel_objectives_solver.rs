@@ -0,0 +1,266 @@
+use crate::domain::solver::{SolveOptions, SolveOutcome, Solver};
+use crate::domain::validate::SolveInputError;
+use crate::models::{SolverDirection, SparseLEIntegerPolyhedron};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+use std::thread;
+use std::time::Instant;
+
+type Answer = Result<(SolveOutcome, String), SolveInputError>;
+
+/// Solver that splits the objectives over several threads
+///
+/// The objectives are cut into contiguous chunks, one per thread, and each
+/// chunk is solved by its own `solve` call on the wrapped backend. Solutions
+/// come back in the order of the objectives. If any chunk fails, the others
+/// are cancelled and the first error is returned.
+///
+/// Backends that cache one model per polyhedron (HiGHS, Gurobi) lock it for
+/// the whole call, so their chunks still run one after another.
+pub struct ParallelObjectivesSolver {
+    inner: Arc<dyn Solver>,
+    threads: usize,
+}
+
+impl ParallelObjectivesSolver {
+    /// Solve with `inner` on up to `threads` threads
+    pub fn new(inner: Arc<dyn Solver>, threads: usize) -> Self {
+        ParallelObjectivesSolver {
+            inner,
+            threads: threads.max(1),
+        }
+    }
+}
+
+/// Merge the chunks' answers, in objective order, into one
+fn merge(answers: Vec<(SolveOutcome, String)>, started: Instant) -> (SolveOutcome, String) {
+    let mut solutions = Vec::new();
+    let mut presolve_reductions = None;
+    let mut logs = Vec::new();
+    let mut answered_by: Vec<String> = Vec::new();
+    for (outcome, name) in answers {
+        solutions.extend(outcome.solutions);
+        presolve_reductions = presolve_reductions.or(outcome.stats.presolve_reductions);
+        logs.extend(outcome.log);
+        // A fallback chain may answer chunks with different backends
+        if !answered_by.contains(&name) {
+            answered_by.push(name);
+        }
+    }
+    let outcome = SolveOutcome::finished(solutions, started)
+        .with_presolve_reductions(presolve_reductions)
+        .with_log((!logs.is_empty()).then(|| logs.join("\n")));
+    (outcome, answered_by.join(", "))
+}
+
+impl Solver for ParallelObjectivesSolver {
+    fn solve(
+        &self,
+        polyhedron: SparseLEIntegerPolyhedron,
+        objectives: Vec<HashMap<String, f64>>,
+        direction: SolverDirection,
+        options: &SolveOptions,
+    ) -> std::result::Result<SolveOutcome, SolveInputError> {
+        self.solve_attributed(polyhedron, objectives, direction, options)
+            .map(|(outcome, _)| outcome)
+    }
+
+    fn solve_attributed(
+        &self,
+        polyhedron: SparseLEIntegerPolyhedron,
+        objectives: Vec<HashMap<String, f64>>,
+        direction: SolverDirection,
+        options: &SolveOptions,
+    ) -> std::result::Result<(SolveOutcome, String), SolveInputError> {
+        if self.threads == 1 || objectives.len() < 2 {
+            return self
+                .inner
+                .solve_attributed(polyhedron, objectives, direction, options);
+        }
+
+        let started = Instant::now();
+        let chunk_size = objectives.len().div_ceil(self.threads);
+        // Cancelled when a chunk fails, or with the caller's token
+        let chunk_options = SolveOptions {
+            cancel: options.cancel.child(),
+            ..options.clone()
+        };
+
+        // Recorded before cancelling the other chunks, so it isn't mistaken
+        // for their "cancelled" errors
+        let first_error: Mutex<Option<SolveInputError>> = Mutex::new(None);
+        // Remote dispatch blocks on the server's runtime, which the chunk
+        // threads have to enter themselves
+        let runtime = tokio::runtime::Handle::try_current().ok();
+
+        let answers: Vec<Option<(SolveOutcome, String)>> = thread::scope(|scope| {
+            let handles: Vec<_> = objectives
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    let polyhedron = polyhedron.clone();
+                    let chunk = chunk.to_vec();
+                    let (chunk_options, first_error) = (&chunk_options, &first_error);
+                    let runtime = runtime.clone();
+                    scope.spawn(move || {
+                        let _runtime = runtime.as_ref().map(|handle| handle.enter());
+                        let answer: Answer = panic::catch_unwind(AssertUnwindSafe(|| {
+                            self.inner
+                                .solve_attributed(polyhedron, chunk, direction, chunk_options)
+                        }))
+                        .unwrap_or_else(|_| {
+                            Err(SolveInputError {
+                                details: "Solver thread panicked".to_string(),
+                            })
+                        });
+                        answer
+                            .map_err(|error| {
+                                first_error.lock().get_or_insert(error);
+                                chunk_options.cancel.cancel();
+                            })
+                            .ok()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().ok().flatten())
+                .collect()
+        });
+
+        // The caller's own cancellation takes precedence over chunk errors
+        options.check_cancelled()?;
+        if let Some(error) = first_error.into_inner() {
+            return Err(error);
+        }
+        Ok(merge(answers.into_iter().flatten().collect(), started))
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn license_status(&self) -> Option<crate::models::ApiLicenseStatus> {
+        self.inner.license_status()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ApiIntegerSparseMatrix, ApiShape, ApiSolution, Status};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    /// Answers each objective with its "id" coefficient as the objective
+    /// value, after a short delay, tracking how many calls overlap
+    struct StubSolver {
+        running: AtomicUsize,
+        peak: AtomicUsize,
+        fail_on: Option<f64>,
+    }
+
+    impl Solver for StubSolver {
+        fn solve(
+            &self,
+            _polyhedron: SparseLEIntegerPolyhedron,
+            objectives: Vec<HashMap<String, f64>>,
+            _direction: SolverDirection,
+            options: &SolveOptions,
+        ) -> std::result::Result<SolveOutcome, SolveInputError> {
+            let started = Instant::now();
+            let now = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(50));
+            self.running.fetch_sub(1, Ordering::SeqCst);
+            options.check_cancelled()?;
+
+            let solutions = objectives
+                .iter()
+                .map(|objective| {
+                    let id = objective["id"];
+                    if Some(id) == self.fail_on {
+                        return Err(SolveInputError {
+                            details: format!("objective {} failed", id),
+                        });
+                    }
+                    Ok(ApiSolution {
+                        status: Status::Optimal,
+                        objective: id,
+                        solution: HashMap::new(),
+                        error: None,
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(SolveOutcome::finished(solutions, started))
+        }
+
+        fn name(&self) -> &str {
+            "stub"
+        }
+    }
+
+    fn stub(fail_on: Option<f64>) -> Arc<StubSolver> {
+        Arc::new(StubSolver {
+            running: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+            fail_on,
+        })
+    }
+
+    fn solve(solver: &ParallelObjectivesSolver, count: usize) -> Answer {
+        let polyhedron = SparseLEIntegerPolyhedron {
+            a: ApiIntegerSparseMatrix {
+                rows: vec![],
+                cols: vec![],
+                vals: vec![],
+                shape: ApiShape { nrows: 0, ncols: 0 },
+            },
+            b: vec![],
+            variables: vec![],
+        };
+        let objectives = (0..count)
+            .map(|id| HashMap::from([("id".to_string(), id as f64)]))
+            .collect();
+        solver.solve_attributed(
+            polyhedron,
+            objectives,
+            SolverDirection::Maximize,
+            &SolveOptions::with_presolve(true),
+        )
+    }
+
+    #[test]
+    fn test_solves_chunks_concurrently_in_order() {
+        let inner = stub(None);
+        let solver = ParallelObjectivesSolver::new(inner.clone(), 3);
+
+        let (outcome, answered_by) = solve(&solver, 7).ok().unwrap();
+
+        assert_eq!(answered_by, "stub");
+        let objectives: Vec<f64> = outcome.solutions.iter().map(|s| s.objective).collect();
+        assert_eq!(objectives, vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(inner.peak.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_single_objective_is_not_split() {
+        let inner = stub(None);
+        let solver = ParallelObjectivesSolver::new(inner.clone(), 4);
+
+        let (outcome, _) = solve(&solver, 1).ok().unwrap();
+
+        assert_eq!(outcome.solutions.len(), 1);
+        assert_eq!(inner.peak.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_returns_error_of_failing_chunk() {
+        let solver = ParallelObjectivesSolver::new(stub(Some(3.0)), 2);
+
+        let error = solve(&solver, 4).err().unwrap();
+
+        assert_eq!(error.details, "objective 3 failed");
+    }
+}
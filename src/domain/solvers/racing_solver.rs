@@ -0,0 +1,289 @@
+use crate::domain::solver::{SolveOptions, SolveOutcome, Solver};
+use crate::domain::validate::SolveInputError;
+use crate::models::{ApiSolution, SolverDirection, SparseLEIntegerPolyhedron, Status};
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+type Answer = Result<(SolveOutcome, String), SolveInputError>;
+
+/// Solver that runs the same model on several backends concurrently
+///
+/// The first answer in which every objective is proven optimal wins. When no
+/// backend proves optimality, the first successful answer is returned, or the
+/// last error if all of them fail.
+///
+/// Once the race is decided the losing backends are cancelled, and the answer
+/// is returned after they have stopped. Backends that only check for
+/// cancellation between objectives hold the race up until they do.
+pub struct RacingSolver {
+    solvers: Vec<Arc<dyn Solver>>,
+    name: String,
+}
+
+impl RacingSolver {
+    /// Create a race between `solvers`. Panics if `solvers` is empty.
+    pub fn new(solvers: Vec<Arc<dyn Solver>>) -> Self {
+        assert!(!solvers.is_empty(), "Race needs at least one solver");
+        let name = format!(
+            "race({})",
+            solvers
+                .iter()
+                .map(|solver| solver.name())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        RacingSolver { solvers, name }
+    }
+}
+
+fn proven_optimal(solutions: &[ApiSolution]) -> bool {
+    solutions
+        .iter()
+        .all(|solution| matches!(solution.status, Status::Optimal))
+}
+
+impl Solver for RacingSolver {
+    fn solve(
+        &self,
+        polyhedron: SparseLEIntegerPolyhedron,
+        objectives: Vec<HashMap<String, f64>>,
+        direction: SolverDirection,
+        options: &SolveOptions,
+    ) -> std::result::Result<SolveOutcome, SolveInputError> {
+        self.solve_attributed(polyhedron, objectives, direction, options)
+            .map(|(outcome, _)| outcome)
+    }
+
+    fn solve_attributed(
+        &self,
+        polyhedron: SparseLEIntegerPolyhedron,
+        objectives: Vec<HashMap<String, f64>>,
+        direction: SolverDirection,
+        options: &SolveOptions,
+    ) -> std::result::Result<(SolveOutcome, String), SolveInputError> {
+        // Cancelled when the race is decided, or with the caller's token
+        let race_options = SolveOptions {
+            cancel: options.cancel.child(),
+            ..options.clone()
+        };
+
+        // Scoped, so the losers are stopped and joined before returning and
+        // don't outlive the executor slot the race was started in
+        thread::scope(|scope| {
+            // Dropped before the scope joins the racers
+            let _stop_losers = race_options.cancel.cancel_on_drop();
+
+            let (sender, receiver) = mpsc::channel::<(&str, Answer)>();
+            for solver in &self.solvers {
+                let sender = sender.clone();
+                let polyhedron = polyhedron.clone();
+                let objectives = objectives.clone();
+                let race_options = &race_options;
+                scope.spawn(move || {
+                    // A panicking backend drops out of the race without answering
+                    if let Ok(answer) = panic::catch_unwind(AssertUnwindSafe(|| {
+                        solver.solve_attributed(polyhedron, objectives, direction, race_options)
+                    })) {
+                        // The race may already be decided and the receiver gone
+                        let _ = sender.send((solver.name(), answer));
+                    }
+                });
+            }
+            drop(sender);
+
+            let mut fallback: Option<Answer> = None;
+            // Ends once every backend has answered or panicked
+            for (name, answer) in receiver {
+                match answer {
+                    Ok((outcome, answered_by)) if proven_optimal(&outcome.solutions) => {
+                        return Ok((outcome, answered_by));
+                    }
+                    Ok(answer) => {
+                        if !matches!(fallback, Some(Ok(_))) {
+                            fallback = Some(Ok(answer));
+                        }
+                    }
+                    Err(error) => {
+                        log::warn!("Solver {} failed during race: {}", name, error.details);
+                        if !matches!(fallback, Some(Ok(_))) {
+                            fallback = Some(Err(error));
+                        }
+                    }
+                }
+            }
+
+            fallback.unwrap_or_else(|| {
+                Err(SolveInputError {
+                    details: "All solvers in the race crashed".to_string(),
+                })
+            })
+        })
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ApiIntegerSparseMatrix, ApiShape};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::{Duration, Instant};
+
+    struct StubSolver {
+        name: &'static str,
+        delay: Duration,
+        status: Option<Status>,
+    }
+
+    impl Solver for StubSolver {
+        fn solve(
+            &self,
+            _polyhedron: SparseLEIntegerPolyhedron,
+            _objectives: Vec<HashMap<String, f64>>,
+            _direction: SolverDirection,
+            options: &SolveOptions,
+        ) -> std::result::Result<SolveOutcome, SolveInputError> {
+            // Sleep in steps so losing stubs notice the race is over
+            let started = Instant::now();
+            while started.elapsed() < self.delay {
+                options.check_cancelled()?;
+                thread::sleep(Duration::from_millis(5));
+            }
+            match self.status {
+                Some(Status::Optimal) => Ok(SolveOutcome::finished(
+                    vec![solution(Status::Optimal)],
+                    started,
+                )),
+                Some(_) => Ok(SolveOutcome::finished(
+                    vec![solution(Status::Feasible)],
+                    started,
+                )),
+                None => Err(SolveInputError {
+                    details: format!("{} failed", self.name),
+                }),
+            }
+        }
+
+        fn name(&self) -> &str {
+            self.name
+        }
+    }
+
+    fn solution(status: Status) -> ApiSolution {
+        ApiSolution {
+            status,
+            objective: 0.0,
+            solution: HashMap::new(),
+            error: None,
+        }
+    }
+
+    fn stub(name: &'static str, millis: u64, status: Option<Status>) -> Arc<dyn Solver> {
+        Arc::new(StubSolver {
+            name,
+            delay: Duration::from_millis(millis),
+            status,
+        })
+    }
+
+    fn race(solver: &RacingSolver) -> Answer {
+        let polyhedron = SparseLEIntegerPolyhedron {
+            a: ApiIntegerSparseMatrix {
+                rows: vec![],
+                cols: vec![],
+                vals: vec![],
+                shape: ApiShape { nrows: 0, ncols: 0 },
+            },
+            b: vec![],
+            variables: vec![],
+        };
+        solver.solve_attributed(
+            polyhedron,
+            vec![HashMap::new()],
+            SolverDirection::Maximize,
+            &SolveOptions::with_presolve(true),
+        )
+    }
+
+    #[test]
+    fn test_first_optimal_answer_wins() {
+        let solver = RacingSolver::new(vec![
+            stub("slow", 2_000, Some(Status::Optimal)),
+            stub("fast", 10, Some(Status::Optimal)),
+        ]);
+        assert_eq!(solver.name(), "race(slow, fast)");
+
+        let (_, answered_by) = race(&solver).ok().unwrap();
+        assert_eq!(answered_by, "fast");
+    }
+
+    #[test]
+    fn test_waits_for_proven_optimal_answer() {
+        let solver = RacingSolver::new(vec![
+            stub("feasible", 10, Some(Status::Feasible)),
+            stub("failing", 20, None),
+            stub("optimal", 50, Some(Status::Optimal)),
+        ]);
+        let (_, answered_by) = race(&solver).ok().unwrap();
+        assert_eq!(answered_by, "optimal");
+    }
+
+    #[test]
+    fn test_falls_back_to_non_optimal_answer() {
+        let solver = RacingSolver::new(vec![
+            stub("failing", 10, None),
+            stub("feasible", 20, Some(Status::Feasible)),
+        ]);
+        let (outcome, answered_by) = race(&solver).ok().unwrap();
+        assert_eq!(answered_by, "feasible");
+        assert!(matches!(outcome.solutions[0].status, Status::Feasible));
+    }
+
+    /// Runs until cancelled, recording that it was
+    struct WatchingSolver {
+        saw_cancel: Arc<AtomicBool>,
+    }
+
+    impl Solver for WatchingSolver {
+        fn solve(
+            &self,
+            _polyhedron: SparseLEIntegerPolyhedron,
+            _objectives: Vec<HashMap<String, f64>>,
+            _direction: SolverDirection,
+            options: &SolveOptions,
+        ) -> std::result::Result<SolveOutcome, SolveInputError> {
+            while !options.cancel.is_cancelled() {
+                thread::sleep(Duration::from_millis(5));
+            }
+            self.saw_cancel.store(true, Ordering::SeqCst);
+            options
+                .check_cancelled()
+                .map(|_| SolveOutcome::finished(Vec::new(), Instant::now()))
+        }
+
+        fn name(&self) -> &str {
+            "watching"
+        }
+    }
+
+    #[test]
+    fn test_cancels_losing_backends() {
+        let saw_cancel = Arc::new(AtomicBool::new(false));
+        let solver = RacingSolver::new(vec![
+            Arc::new(WatchingSolver {
+                saw_cancel: Arc::clone(&saw_cancel),
+            }),
+            stub("fast", 10, Some(Status::Optimal)),
+        ]);
+        let (_, answered_by) = race(&solver).ok().unwrap();
+        assert_eq!(answered_by, "fast");
+        // The loser has stopped by the time the race returns
+        assert!(saw_cancel.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,356 @@
+use crate::domain::solver::{SolveOptions, SolveOutcome, Solver};
+use crate::domain::validate::SolveInputError;
+use crate::domain::worker_pool::WorkerPool;
+use crate::models::{ApiSolution, SolveRequest, SolverDirection, SparseLEIntegerPolyhedron};
+use futures_util::future::{self, Either};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How often a pending dispatch checks for cancellation
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Deserialize)]
+struct WorkerResponse {
+    solutions: Vec<ApiSolution>,
+}
+
+#[derive(Deserialize)]
+struct WorkerError {
+    error: String,
+}
+
+/// Why a single worker could not answer
+enum DispatchError {
+    /// The worker rejected the problem itself; other workers would too
+    Rejected(String),
+    /// The worker is unreachable or failing; try the next one
+    Unavailable(String),
+}
+
+/// Solver that forwards solves to worker instances over HTTP
+///
+/// Workers are tried round-robin, failing over to the next worker when one
+/// can't be reached or returns a server error. A failing registered worker is
+/// skipped until its next heartbeat. Problems a worker rejects (422) are
+/// reported back without retrying.
+#[derive(Clone)]
+pub struct RemoteSolver {
+    pool: Arc<WorkerPool>,
+    client: reqwest::Client,
+    token: Option<String>,
+    /// Backend to ask workers for, or `None` for each worker's default
+    backend: Option<String>,
+}
+
+impl RemoteSolver {
+    /// Create a remote solver sending `token` as `X-API-Key` to protected workers
+    pub fn new(pool: Arc<WorkerPool>, token: Option<String>, timeout: Duration) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .expect("Failed to build worker HTTP client");
+        RemoteSolver {
+            pool,
+            client,
+            token,
+            backend: None,
+        }
+    }
+
+    /// The same dispatch, sending only to workers with `backend` and asking
+    /// them to solve with it
+    pub fn for_backend(&self, backend: &str) -> Self {
+        RemoteSolver {
+            backend: Some(backend.to_string()),
+            ..self.clone()
+        }
+    }
+
+    async fn dispatch(&self, request: &SolveRequest) -> Result<Vec<ApiSolution>, SolveInputError> {
+        for url in self.pool.prune() {
+            log::warn!("Worker {} missed its heartbeats and was removed", url);
+        }
+
+        let workers = self.pool.dispatch_order(request.solver.as_deref());
+        if workers.is_empty() {
+            let details = match &request.solver {
+                Some(solver) => format!("No workers available for solver '{}'", solver),
+                None => "No workers available".to_string(),
+            };
+            return Err(SolveInputError { details });
+        }
+
+        let mut failures = Vec::new();
+        for url in workers {
+            match self.send(&url, request).await {
+                Ok(solutions) => return Ok(solutions),
+                Err(DispatchError::Rejected(details)) => return Err(SolveInputError { details }),
+                Err(DispatchError::Unavailable(reason)) => {
+                    log::warn!("Worker {} failed: {}", url, reason);
+                    self.pool.mark_unhealthy(&url);
+                    failures.push(format!("{}: {}", url, reason));
+                }
+            }
+        }
+
+        Err(SolveInputError {
+            details: format!("All workers failed ({})", failures.join("; ")),
+        })
+    }
+
+    async fn send(
+        &self,
+        url: &str,
+        request: &SolveRequest,
+    ) -> Result<Vec<ApiSolution>, DispatchError> {
+        let mut builder = self.client.post(format!("{}/solve", url)).json(request);
+        if let Some(token) = &self.token {
+            builder = builder.header("X-API-Key", token);
+        }
+
+        let response = builder
+            .send()
+            .await
+            .map_err(|e| DispatchError::Unavailable(e.to_string()))?;
+
+        match response.status() {
+            StatusCode::OK => response
+                .json::<WorkerResponse>()
+                .await
+                .map(|body| body.solutions)
+                .map_err(|e| DispatchError::Unavailable(format!("invalid response: {}", e))),
+            StatusCode::UNPROCESSABLE_ENTITY => {
+                let details = response
+                    .json::<WorkerError>()
+                    .await
+                    .map(|body| body.error)
+                    .unwrap_or_else(|e| format!("Worker rejected the problem: {}", e));
+                Err(DispatchError::Rejected(details))
+            }
+            status => Err(DispatchError::Unavailable(format!("HTTP {}", status))),
+        }
+    }
+}
+
+impl Solver for RemoteSolver {
+    fn solve(
+        &self,
+        polyhedron: SparseLEIntegerPolyhedron,
+        objectives: Vec<HashMap<String, f64>>,
+        direction: SolverDirection,
+        options: &SolveOptions, // workers use their own presolve setting
+    ) -> std::result::Result<SolveOutcome, SolveInputError> {
+        let started = Instant::now();
+        // Called from a blocking thread of the server's runtime, or a thread
+        // that entered it, which can wait on the HTTP round trip without
+        // stalling request handling
+        let runtime = tokio::runtime::Handle::try_current().map_err(|_| SolveInputError {
+            details: "Remote dispatch requires the server runtime".to_string(),
+        })?;
+
+        let request = SolveRequest {
+            polyhedron,
+            objectives,
+            direction,
+            solver: self.backend.clone(),
+            time_limit_seconds: options.time_limit.map(|limit| limit.as_secs_f64()),
+            mip_rel_gap: options.mip_rel_gap,
+            // Like presolve stats, solver logs stay on the worker
+            include_log: false,
+            exact_lp: options.exact_lp,
+            highs: options.highs.request_overrides(),
+            solver_options: options.solver_options.clone(),
+            // Spread over threads here already, when asked to
+            objective_threads: None,
+            // Goals are already rewritten into the polyhedron
+            goal: None,
+            method: options.method,
+        };
+        runtime.block_on(async {
+            // Dropping the dispatch closes the connection, which cancels the
+            // solve on the worker as well
+            let cancelled = Box::pin(async {
+                loop {
+                    if let Err(error) = options.check_cancelled() {
+                        return error;
+                    }
+                    actix_web::rt::time::sleep(CANCEL_POLL_INTERVAL).await;
+                }
+            });
+            match future::select(Box::pin(self.dispatch(&request)), cancelled).await {
+                // Timing includes the round trip; presolve stats stay on the worker
+                Either::Left((answer, _)) => {
+                    answer.map(|solutions| SolveOutcome::finished(solutions, started))
+                }
+                Either::Right((error, _)) => Err(error),
+            }
+        })
+    }
+
+    fn name(&self) -> &str {
+        "Remote"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::solver_registry::SolverRegistry;
+    use crate::domain::solvers::ParallelObjectivesSolver;
+    use crate::models::{ApiIntegerSparseMatrix, ApiShape, ApiVariable};
+    use actix_web::{web, App, HttpResponse, HttpServer};
+
+    fn polyhedron() -> SparseLEIntegerPolyhedron {
+        SparseLEIntegerPolyhedron {
+            a: ApiIntegerSparseMatrix {
+                rows: vec![0],
+                cols: vec![0],
+                vals: vec![1],
+                shape: ApiShape { nrows: 1, ncols: 1 },
+            },
+            b: vec![1],
+            variables: vec![ApiVariable {
+                id: "x".into(),
+                bound: (0, 1),
+            }],
+        }
+    }
+
+    /// Start a stand-in worker answering `/solve` with `response`
+    fn start_worker(response: fn() -> HttpResponse) -> String {
+        let server = HttpServer::new(move || {
+            App::new().route("/solve", web::post().to(move || async move { response() }))
+        })
+        .workers(1)
+        .bind(("127.0.0.1", 0))
+        .unwrap();
+        let url = format!("http://{}", server.addrs()[0]);
+        actix_web::rt::spawn(server.run());
+        url
+    }
+
+    fn solve_remotely(
+        urls: Vec<String>,
+    ) -> tokio::task::JoinHandle<Result<SolveOutcome, SolveInputError>> {
+        let solver = RemoteSolver::new(
+            Arc::new(WorkerPool::new(urls, Duration::from_secs(60))),
+            None,
+            Duration::from_secs(5),
+        );
+        tokio::task::spawn_blocking(move || {
+            solver.solve(
+                polyhedron(),
+                vec![HashMap::new()],
+                SolverDirection::Maximize,
+                &SolveOptions::with_presolve(true),
+            )
+        })
+    }
+
+    #[actix_web::test]
+    async fn test_fails_over_to_next_worker() {
+        let failing = start_worker(|| HttpResponse::InternalServerError().finish());
+        let healthy = start_worker(|| {
+            HttpResponse::Ok().json(serde_json::json!({
+                "solutions": [{ "status": "Optimal", "objective": 1, "solution": { "x": 1 }, "error": null }]
+            }))
+        });
+
+        let solutions = solve_remotely(vec![failing, healthy])
+            .await
+            .unwrap()
+            .ok()
+            .unwrap()
+            .solutions;
+        assert_eq!(solutions.len(), 1);
+        assert_eq!(solutions[0].solution["x"], 1);
+    }
+
+    #[actix_web::test]
+    async fn test_forwards_requested_solver() {
+        // Rejects every problem, naming the solver it was asked for
+        let server = HttpServer::new(|| {
+            App::new().route(
+                "/solve",
+                web::post().to(|body: web::Json<serde_json::Value>| async move {
+                    HttpResponse::UnprocessableEntity()
+                        .json(serde_json::json!({ "error": body["solver"] }))
+                }),
+            )
+        })
+        .workers(1)
+        .bind(("127.0.0.1", 0))
+        .unwrap();
+        let url = format!("http://{}", server.addrs()[0]);
+        actix_web::rt::spawn(server.run());
+
+        let pool = Arc::new(WorkerPool::new(Vec::new(), Duration::from_secs(60)));
+        pool.register(&url, vec!["glpk".to_string(), "highs".to_string()]);
+        let remote = RemoteSolver::new(pool, None, Duration::from_secs(5));
+        let solve_with = |backend: &str| {
+            let solver = remote.for_backend(backend);
+            tokio::task::spawn_blocking(move || {
+                solver.solve(
+                    polyhedron(),
+                    vec![HashMap::new()],
+                    SolverDirection::Maximize,
+                    &SolveOptions::with_presolve(true),
+                )
+            })
+        };
+
+        let error = solve_with("highs").await.unwrap().err().unwrap();
+        assert_eq!(error.details, "highs");
+        let error = solve_with("gurobi").await.unwrap().err().unwrap();
+        assert_eq!(error.details, "No workers available for solver 'gurobi'");
+    }
+
+    #[actix_web::test]
+    async fn test_reports_rejected_problems() {
+        let rejecting = start_worker(|| {
+            HttpResponse::UnprocessableEntity()
+                .json(serde_json::json!({ "error": "Objective contains missing variable y" }))
+        });
+
+        let error = solve_remotely(vec![rejecting])
+            .await
+            .unwrap()
+            .err()
+            .unwrap();
+        assert_eq!(error.details, "Objective contains missing variable y");
+    }
+
+    #[actix_web::test]
+    async fn test_dispatches_parallel_objective_chunks() {
+        let healthy = start_worker(|| {
+            HttpResponse::Ok().json(serde_json::json!({
+                "solutions": [{ "status": "Optimal", "objective": 1, "solution": { "x": 1 }, "error": null }]
+            }))
+        });
+        let remote = RemoteSolver::new(
+            Arc::new(WorkerPool::new(vec![healthy], Duration::from_secs(60))),
+            None,
+            Duration::from_secs(5),
+        );
+        let registry = SolverRegistry::with_remote(remote, None);
+        let solver = ParallelObjectivesSolver::new(registry.default_solver(), 2);
+
+        let solutions = tokio::task::spawn_blocking(move || {
+            solver.solve(
+                polyhedron(),
+                vec![HashMap::new(), HashMap::new()],
+                SolverDirection::Maximize,
+                &SolveOptions::with_presolve(true),
+            )
+        })
+        .await
+        .unwrap()
+        .ok()
+        .unwrap()
+        .solutions;
+        assert_eq!(solutions.len(), 2);
+    }
+}
@@ -1,11 +1,12 @@
 use std::collections::{HashMap, HashSet};
 
-use glpk_rust::Variable;
+use crate::models::{ApiVariable, SolveRequest};
 
 pub struct SolveInputError {
     pub details: String,
 }
 
+#[cfg(feature = "glpk-solver")]
 impl From<glpk_rust::SolverError> for SolveInputError {
     fn from(e: glpk_rust::SolverError) -> Self {
         SolveInputError {
@@ -15,10 +16,10 @@ impl From<glpk_rust::SolverError> for SolveInputError {
 }
 
 pub fn validate_objectives_owned(
-    variables: &Vec<Variable>,
+    variables: &[ApiVariable],
     objectives: &[HashMap<String, f64>],
 ) -> Result<(), SolveInputError> {
-    let variable_ids: HashSet<&str> = variables.iter().map(|v| v.id).collect();
+    let variable_ids: HashSet<&str> = variables.iter().map(|v| v.id.as_str()).collect();
 
     for objective in objectives {
         for objective_variable_id in objective.keys() {
@@ -36,6 +37,147 @@ pub fn validate_objectives_owned(
     Ok(())
 }
 
+/// Structural checks on a solve request: matrix shape, index bounds and size limits
+pub fn validate_solve_request(req: &SolveRequest) -> Result<(), SolveInputError> {
+    let variable_count = req.polyhedron.variables.len();
+    let column_count = req.polyhedron.a.shape.ncols;
+    if variable_count != column_count {
+        return Err(SolveInputError {
+            details: format!(
+                "Number of variables must match number of columns in A got {} variables and {} columns",
+                variable_count, column_count
+            ),
+        });
+    }
+
+    let b_count = req.polyhedron.b.len();
+    let row_count = req.polyhedron.a.shape.nrows;
+    if b_count != row_count {
+        return Err(SolveInputError {
+            details: format!(
+                "Number of values in b must match number of rows in A got {} values and {} rows",
+                b_count, row_count
+            ),
+        });
+    }
+
+    // Validate sparse matrix arrays have same length
+    let rows_len = req.polyhedron.a.rows.len();
+    let cols_len = req.polyhedron.a.cols.len();
+    let vals_len = req.polyhedron.a.vals.len();
+    if rows_len != cols_len || rows_len != vals_len {
+        return Err(SolveInputError {
+            details: format!(
+                "Sparse matrix arrays must have same length: got rows={}, cols={}, vals={}",
+                rows_len, cols_len, vals_len
+            ),
+        });
+    }
+
+    // Validate sparse matrix indices are within bounds
+    for i in 0..rows_len {
+        let row = req.polyhedron.a.rows[i];
+        let col = req.polyhedron.a.cols[i];
+
+        if row < 0 || row >= row_count as i32 {
+            return Err(SolveInputError {
+                details: format!(
+                    "Row index {} at position {} is out of bounds [0, {})",
+                    row, i, row_count
+                ),
+            });
+        }
+
+        if col < 0 || col >= column_count as i32 {
+            return Err(SolveInputError {
+                details: format!(
+                    "Column index {} at position {} is out of bounds [0, {})",
+                    col, i, column_count
+                ),
+            });
+        }
+    }
+
+    // Input size limits (prevent DoS/OOM)
+    const MAX_VARIABLES: usize = 100_000;
+    const MAX_CONSTRAINTS: usize = 100_000;
+    const MAX_NONZEROS: usize = 1_000_000;
+
+    if variable_count > MAX_VARIABLES {
+        return Err(SolveInputError {
+            details: format!(
+                "Too many variables: {} exceeds limit of {}",
+                variable_count, MAX_VARIABLES
+            ),
+        });
+    }
+
+    if row_count > MAX_CONSTRAINTS {
+        return Err(SolveInputError {
+            details: format!(
+                "Too many constraints: {} exceeds limit of {}",
+                row_count, MAX_CONSTRAINTS
+            ),
+        });
+    }
+
+    if rows_len > MAX_NONZEROS {
+        return Err(SolveInputError {
+            details: format!(
+                "Too many non-zero elements: {} exceeds limit of {}",
+                rows_len, MAX_NONZEROS
+            ),
+        });
+    }
+
+    if let Some(seconds) = req.time_limit_seconds {
+        if !(seconds > 0.0 && seconds.is_finite()) {
+            return Err(SolveInputError {
+                details: "time_limit_seconds must be a positive number".to_string(),
+            });
+        }
+    }
+
+    if let Some(gap) = req.mip_rel_gap {
+        if !(gap >= 0.0 && gap.is_finite()) {
+            return Err(SolveInputError {
+                details: "mip_rel_gap must be a non-negative number".to_string(),
+            });
+        }
+    }
+
+    if let Some(goal) = &req.goal {
+        if goal.len() != req.objectives.len() {
+            return Err(SolveInputError {
+                details: format!(
+                    "goal must have one target per objective, got {} targets for {} objectives",
+                    goal.len(),
+                    req.objectives.len()
+                ),
+            });
+        }
+    }
+
+    if req.objective_threads == Some(0) {
+        return Err(SolveInputError {
+            details: "objective_threads must be at least 1".to_string(),
+        });
+    }
+
+    if let Some(strategy) = req.highs.and_then(|highs| highs.simplex_strategy) {
+        if !(0..=4).contains(&strategy) {
+            return Err(SolveInputError {
+                details: format!(
+                    "highs.simplex_strategy must be between 0 and 4, got {}",
+                    strategy
+                ),
+            });
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -43,12 +185,12 @@ mod tests {
     #[test]
     fn test_validate_objectives_given_valid_objectives() {
         let variables = vec![
-            Variable {
-                id: "x1",
+            ApiVariable {
+                id: "x1".to_string(),
                 bound: (0, 1),
             },
-            Variable {
-                id: "x2",
+            ApiVariable {
+                id: "x2".to_string(),
                 bound: (0, 1),
             },
         ];
@@ -62,12 +204,12 @@ mod tests {
     #[test]
     fn test_validate_objectives_given_missing_variable() {
         let variables = vec![
-            Variable {
-                id: "x1",
+            ApiVariable {
+                id: "x1".to_string(),
                 bound: (0, 1),
             },
-            Variable {
-                id: "x2",
+            ApiVariable {
+                id: "x2".to_string(),
                 bound: (0, 1),
             },
         ];
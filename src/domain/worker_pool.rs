@@ -0,0 +1,262 @@
+use crate::models::ApiWorker;
+use parking_lot::RwLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+struct Worker {
+    url: String,
+    /// Solver backends compiled into the worker; empty when it hasn't said,
+    /// as for static workers
+    solvers: Vec<String>,
+    /// Last heartbeat; `None` for workers configured statically
+    last_seen: Option<Instant>,
+    /// Set when a dispatch to the worker fails; cleared by its next heartbeat
+    unhealthy: bool,
+}
+
+/// Worker instances that solves can be dispatched to.
+///
+/// Workers are plain instances of this server; the front-end forwards the
+/// `/solve` request body to them unchanged. Workers either come from static
+/// configuration or register themselves and keep sending heartbeats. A
+/// registered worker that misses heartbeats for longer than the TTL is
+/// dropped from the pool.
+pub struct WorkerPool {
+    workers: RwLock<Vec<Worker>>,
+    next: AtomicUsize,
+    ttl: Duration,
+}
+
+impl WorkerPool {
+    /// Create a pool from static worker base URLs, e.g. `http://worker-1:9000`.
+    ///
+    /// Static workers are never pruned. Registered workers expire after `ttl`
+    /// without a heartbeat.
+    pub fn new(urls: Vec<String>, ttl: Duration) -> Self {
+        let workers = urls
+            .iter()
+            .map(|url| Worker {
+                url: normalize_url(url),
+                solvers: Vec::new(),
+                last_seen: None,
+                unhealthy: false,
+            })
+            .collect();
+        WorkerPool {
+            workers: RwLock::new(workers),
+            next: AtomicUsize::new(0),
+            ttl,
+        }
+    }
+
+    /// Add a worker or refresh its heartbeat. Returns `true` for new workers.
+    pub fn register(&self, url: &str, solvers: Vec<String>) -> bool {
+        let url = normalize_url(url);
+        let mut workers = self.workers.write();
+        match workers.iter_mut().find(|worker| worker.url == url) {
+            Some(worker) => {
+                worker.solvers = solvers;
+                worker.unhealthy = false;
+                if worker.last_seen.is_some() {
+                    worker.last_seen = Some(Instant::now());
+                }
+                false
+            }
+            None => {
+                workers.push(Worker {
+                    url,
+                    solvers,
+                    last_seen: Some(Instant::now()),
+                    unhealthy: false,
+                });
+                true
+            }
+        }
+    }
+
+    /// Exclude a worker from dispatch after a failed request.
+    ///
+    /// Registered workers are tried again after their next heartbeat. Static
+    /// workers have no heartbeat and stay in rotation as failover targets.
+    pub fn mark_unhealthy(&self, url: &str) {
+        let mut workers = self.workers.write();
+        if let Some(worker) = workers
+            .iter_mut()
+            .find(|worker| worker.url == url && worker.last_seen.is_some())
+        {
+            worker.unhealthy = true;
+        }
+    }
+
+    /// Remove registered workers whose heartbeat expired, returning their URLs
+    pub fn prune(&self) -> Vec<String> {
+        let mut workers = self.workers.write();
+        let mut removed = Vec::new();
+        workers.retain(|worker| {
+            let expired = worker
+                .last_seen
+                .is_some_and(|seen| seen.elapsed() > self.ttl);
+            if expired {
+                removed.push(worker.url.clone());
+            }
+            !expired
+        });
+        removed
+    }
+
+    /// Worker URLs in the order they should be tried for the next request.
+    ///
+    /// With `solver`, only workers advertising that backend are included,
+    /// along with workers that advertise nothing. The starting worker rotates
+    /// between calls so that load is spread round-robin, and the remaining
+    /// workers follow as failover targets.
+    pub fn dispatch_order(&self, solver: Option<&str>) -> Vec<String> {
+        let workers = self.workers.read();
+        let urls: Vec<&String> = workers
+            .iter()
+            .filter(|worker| !worker.unhealthy)
+            .filter(|worker| {
+                solver.is_none_or(|solver| {
+                    worker.solvers.is_empty()
+                        || worker
+                            .solvers
+                            .iter()
+                            .any(|name| name.eq_ignore_ascii_case(solver))
+                })
+            })
+            .filter(|worker| {
+                worker
+                    .last_seen
+                    .is_none_or(|seen| seen.elapsed() <= self.ttl)
+            })
+            .map(|worker| &worker.url)
+            .collect();
+        if urls.is_empty() {
+            return Vec::new();
+        }
+        let start = self.next.fetch_add(1, Ordering::Relaxed) % urls.len();
+        urls[start..]
+            .iter()
+            .chain(&urls[..start])
+            .map(|url| url.to_string())
+            .collect()
+    }
+
+    /// Current workers, for the API
+    pub fn workers(&self) -> Vec<ApiWorker> {
+        self.workers
+            .read()
+            .iter()
+            .map(|worker| ApiWorker {
+                url: worker.url.clone(),
+                solvers: worker.solvers.clone(),
+                registered: worker.last_seen.is_some(),
+                healthy: !worker.unhealthy,
+                last_seen_seconds: worker.last_seen.map(|seen| seen.elapsed().as_secs()),
+            })
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.workers.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.workers.read().is_empty()
+    }
+}
+
+fn normalize_url(url: &str) -> String {
+    url.trim().trim_end_matches('/').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TTL: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn test_dispatch_order_rotates() {
+        let pool = WorkerPool::new(
+            vec!["http://a:9000/".to_string(), "http://b:9000".to_string()],
+            TTL,
+        );
+        assert_eq!(
+            pool.dispatch_order(None),
+            vec!["http://a:9000", "http://b:9000"]
+        );
+        assert_eq!(
+            pool.dispatch_order(None),
+            vec!["http://b:9000", "http://a:9000"]
+        );
+        assert_eq!(
+            pool.dispatch_order(None),
+            vec!["http://a:9000", "http://b:9000"]
+        );
+    }
+
+    #[test]
+    fn test_empty_pool_has_no_dispatch_order() {
+        let pool = WorkerPool::new(Vec::new(), TTL);
+        assert!(pool.dispatch_order(None).is_empty());
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    fn test_register_adds_and_refreshes_workers() {
+        let pool = WorkerPool::new(Vec::new(), TTL);
+        assert!(pool.register("http://a:9000/", vec!["glpk".to_string()]));
+        assert!(!pool.register(
+            "http://a:9000",
+            vec!["glpk".to_string(), "highs".to_string()]
+        ));
+
+        let workers = pool.workers();
+        assert_eq!(workers.len(), 1);
+        assert_eq!(workers[0].solvers, vec!["glpk", "highs"]);
+        assert!(workers[0].registered);
+    }
+
+    #[test]
+    fn test_unhealthy_workers_return_after_heartbeat() {
+        let pool = WorkerPool::new(Vec::new(), TTL);
+        pool.register("http://a:9000", Vec::new());
+        pool.mark_unhealthy("http://a:9000");
+        assert!(pool.dispatch_order(None).is_empty());
+
+        pool.register("http://a:9000", Vec::new());
+        assert_eq!(pool.dispatch_order(None), vec!["http://a:9000"]);
+    }
+
+    #[test]
+    fn test_dispatch_order_filters_by_solver() {
+        let pool = WorkerPool::new(vec!["http://static:9000".to_string()], TTL);
+        pool.register("http://glpk:9000", vec!["glpk".to_string()]);
+        pool.register(
+            "http://highs:9000",
+            vec!["glpk".to_string(), "highs".to_string()],
+        );
+
+        assert_eq!(
+            pool.dispatch_order(Some("highs")),
+            vec!["http://static:9000", "http://highs:9000"]
+        );
+        assert_eq!(pool.dispatch_order(Some("glpk")).len(), 3);
+        assert_eq!(
+            pool.dispatch_order(Some("gurobi")),
+            vec!["http://static:9000"]
+        );
+    }
+
+    #[test]
+    fn test_prune_removes_expired_registered_workers_only() {
+        let pool = WorkerPool::new(vec!["http://static:9000".to_string()], Duration::ZERO);
+        pool.register("http://registered:9000", Vec::new());
+        std::thread::sleep(Duration::from_millis(2));
+
+        assert_eq!(pool.dispatch_order(None), vec!["http://static:9000"]);
+        assert_eq!(pool.prune(), vec!["http://registered:9000"]);
+        assert_eq!(pool.len(), 1);
+    }
+}
@@ -0,0 +1,157 @@
+//! Conversion between the API schema and standard solver file formats
+//!
+//! LP and MPS parsing lives in the `glpk-api-formats` crate, shared with the
+//! client SDK; this module converts its models to and from API requests.
+
+use crate::models::{
+    ApiIntegerSparseMatrix, ApiShape, ApiVariable, SolveRequest, SolverDirection,
+    SparseLEIntegerPolyhedron,
+};
+use glpk_api_formats::{Direction, Model, Polyhedron, Shape, SparseMatrix, Variable};
+use std::collections::HashMap;
+use std::path::Path;
+
+pub use glpk_api_formats::FormatError;
+
+/// Supported model file formats
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    /// The `/solve` request body
+    Json,
+    /// CPLEX LP format
+    Lp,
+    /// Fixed or free MPS format
+    Mps,
+}
+
+impl Format {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "json" => Some(Format::Json),
+            "lp" => Some(Format::Lp),
+            "mps" => Some(Format::Mps),
+            _ => None,
+        }
+    }
+
+    /// Guess the format from a file extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(Self::from_name)
+    }
+}
+
+/// A model read from a solver file, in API form
+pub struct ImportedModel {
+    pub request: SolveRequest,
+    /// Columns the file declares continuous. The API only solves integer
+    /// problems, so these are solved as integers.
+    pub continuous: Vec<String>,
+}
+
+/// Read a model in the given format
+pub fn read(format: Format, input: &str) -> Result<ImportedModel, FormatError> {
+    match format {
+        Format::Json => serde_json::from_str(input)
+            .map(|request| ImportedModel {
+                request,
+                continuous: Vec::new(),
+            })
+            .map_err(|e| FormatError::new(e.to_string())),
+        Format::Lp => glpk_api_formats::read_lp(input).map(Into::into),
+        Format::Mps => glpk_api_formats::read_mps(input).map(Into::into),
+    }
+}
+
+/// Write a request in the given format.
+///
+/// The request must have passed `validate_solve_request`.
+pub fn write(format: Format, request: &SolveRequest) -> Result<String, FormatError> {
+    match format {
+        Format::Json => serde_json::to_string_pretty(request)
+            .map(|json| json + "\n")
+            .map_err(|e| FormatError::new(e.to_string())),
+        Format::Lp => glpk_api_formats::write_lp(&to_model(request)),
+        Format::Mps => glpk_api_formats::write_mps(&to_model(request)),
+    }
+}
+
+impl From<glpk_api_formats::ImportedModel> for ImportedModel {
+    fn from(imported: glpk_api_formats::ImportedModel) -> Self {
+        let model = imported.model;
+        let polyhedron = model.polyhedron;
+        ImportedModel {
+            request: SolveRequest {
+                polyhedron: SparseLEIntegerPolyhedron {
+                    a: ApiIntegerSparseMatrix {
+                        rows: polyhedron.a.rows,
+                        cols: polyhedron.a.cols,
+                        vals: polyhedron.a.vals,
+                        shape: ApiShape {
+                            nrows: polyhedron.a.shape.nrows,
+                            ncols: polyhedron.a.shape.ncols,
+                        },
+                    },
+                    b: polyhedron.b,
+                    variables: polyhedron
+                        .variables
+                        .into_iter()
+                        .map(|v| ApiVariable {
+                            id: v.id,
+                            bound: v.bound,
+                        })
+                        .collect(),
+                },
+                objectives: model.objectives,
+                direction: match model.direction {
+                    Direction::Maximize => SolverDirection::Maximize,
+                    Direction::Minimize => SolverDirection::Minimize,
+                },
+                solver: None,
+                time_limit_seconds: None,
+                mip_rel_gap: None,
+                include_log: false,
+                exact_lp: false,
+                highs: None,
+                solver_options: HashMap::new(),
+                objective_threads: None,
+                goal: None,
+                method: None,
+            },
+            continuous: imported.continuous,
+        }
+    }
+}
+
+/// The parts of a request that LP and MPS files hold
+fn to_model(request: &SolveRequest) -> Model {
+    let polyhedron = &request.polyhedron;
+    Model {
+        polyhedron: Polyhedron {
+            a: SparseMatrix {
+                rows: polyhedron.a.rows.clone(),
+                cols: polyhedron.a.cols.clone(),
+                vals: polyhedron.a.vals.clone(),
+                shape: Shape {
+                    nrows: polyhedron.a.shape.nrows,
+                    ncols: polyhedron.a.shape.ncols,
+                },
+            },
+            b: polyhedron.b.clone(),
+            variables: polyhedron
+                .variables
+                .iter()
+                .map(|v| Variable {
+                    id: v.id.clone(),
+                    bound: v.bound,
+                })
+                .collect(),
+        },
+        objectives: request.objectives.clone(),
+        direction: match request.direction {
+            SolverDirection::Maximize => Direction::Maximize,
+            SolverDirection::Minimize => Direction::Minimize,
+        },
+    }
+}
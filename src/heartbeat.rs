@@ -0,0 +1,76 @@
+//! Worker side of remote dispatch: register with a front-end and keep the
+//! registration alive with periodic heartbeats
+
+use crate::domain::solver_factory::SolverType;
+use crate::models::RegisterWorkerRequest;
+use std::time::Duration;
+
+pub struct HeartbeatConfig {
+    /// Base URL of the front-end instance
+    pub frontend_url: String,
+    /// This worker's base URL as reachable from the front-end
+    pub advertise_url: String,
+    /// Sent as `X-API-Key` when registering
+    pub token: Option<String>,
+    pub interval: Duration,
+}
+
+/// Register with the front-end every `interval`, for as long as the server runs.
+///
+/// Failures are logged and retried on the next tick, so workers can start
+/// before the front-end does.
+pub async fn run(config: HeartbeatConfig) {
+    let client = match reqwest::Client::builder()
+        .timeout(config.interval.max(Duration::from_secs(5)))
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => {
+            log::error!("Heartbeat disabled, failed to build HTTP client: {}", e);
+            return;
+        }
+    };
+
+    let endpoint = format!(
+        "{}/workers/register",
+        config.frontend_url.trim_end_matches('/')
+    );
+    let body = RegisterWorkerRequest {
+        url: config.advertise_url.clone(),
+        solvers: SolverType::all()
+            .iter()
+            .map(|solver_type| solver_type.name().to_string())
+            .collect(),
+    };
+
+    let mut registered = false;
+    loop {
+        let mut request = client.post(&endpoint).json(&body);
+        if let Some(token) = &config.token {
+            request = request.header("X-API-Key", token);
+        }
+
+        match request.send().await {
+            Ok(response) if response.status().is_success() => {
+                if !registered {
+                    log::info!(
+                        "Registered with front-end {} as {}",
+                        config.frontend_url,
+                        config.advertise_url
+                    );
+                    registered = true;
+                }
+            }
+            Ok(response) => {
+                log::warn!("Front-end rejected heartbeat: HTTP {}", response.status());
+                registered = false;
+            }
+            Err(e) => {
+                log::warn!("Heartbeat to front-end failed: {}", e);
+                registered = false;
+            }
+        }
+
+        actix_web::rt::time::sleep(config.interval).await;
+    }
+}
@@ -1,23 +1,43 @@
+mod cli;
 mod convert;
 mod domain;
+mod formats;
+mod heartbeat;
 mod models;
+mod streaming_json;
 
-use models::SolveRequest;
+use models::{
+    ApiSolveStats, BenchmarkRequest, HighsParallel, RegisterWorkerRequest, SetDefaultSolverRequest,
+    SolveRequest, SolverDefaults,
+};
 
-use domain::solver::Solver;
-use domain::solver_factory::{create_solver_with_cache, SolverType};
+use domain::benchmark::run_benchmark;
+use domain::goal;
+use domain::recent_solves::{fingerprint, RecentSolves};
+use domain::self_test::{run_self_tests, SelfTests};
+use domain::solve_executor::SolveExecutor;
+use domain::solver::{HighsOptions, SolveOptions, Solver, TerminationReason};
+use domain::solver_factory::{create_solver_chain, SolverType};
+use domain::solver_registry::SolverRegistry;
+use domain::solvers::{ParallelObjectivesSolver, RemoteSolver};
+use domain::validate;
+use domain::worker_pool::WorkerPool;
+use streaming_json::{StreamingJson, StreamingJsonConfig};
 
-use actix_web::body::BoxBody;
-use actix_web::http::header::HeaderName;
-use actix_web::middleware::{from_fn, Condition, Logger, Next};
+use actix_web::body::{BodySize, BoxBody, MessageBody};
+use actix_web::http::header::{HeaderName, HeaderValue, CONTENT_ENCODING, ETAG, IF_NONE_MATCH};
+use actix_web::middleware::{from_fn, Compress, Condition, Logger, Next};
 use actix_web::{
     dev::{ServiceRequest, ServiceResponse},
     Error,
 };
-use actix_web::{web, App, HttpResponse, HttpServer, Responder};
+use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer, Responder};
 
 use dotenv::dotenv;
+use std::collections::HashMap;
 use std::env;
+use std::num::NonZeroUsize;
+use std::time::Duration;
 
 use sentry_actix::Sentry;
 use std::sync::Arc;
@@ -26,42 +46,81 @@ use subtle::ConstantTimeEq;
 // ---------- Route handlers ----------
 /// POST /solve
 pub async fn solve(
-    req: web::Json<SolveRequest>,
-    solver: web::Data<Box<dyn Solver>>,
+    http_req: HttpRequest,
+    req: StreamingJson<SolveRequest>,
+    solver_registry: web::Data<SolverRegistry>,
     use_presolve: web::Data<bool>,
-    solver_semaphore: web::Data<Arc<tokio::sync::Semaphore>>,
+    executor: web::Data<SolveExecutor>,
+    recent_solves: Option<web::Data<RecentSolves>>,
+    solve_defaults: Option<web::Data<SolveDefaults>>,
 ) -> impl Responder {
     match validate_solve_request(&req) {
         Ok(_) => (),
         Err(response) => return response,
     }
 
-    // Acquire an owned permit asynchronously before spawning the blocking task.
-    let sem = solver_semaphore.get_ref().clone();
-    let permit = match sem.acquire_owned().await {
-        Ok(p) => p,
-        Err(e) => {
-            sentry::capture_message(
-                &format!("Failed to acquire semaphore permit: {}", e),
-                sentry::Level::Error,
-            );
-            return HttpResponse::InternalServerError()
-                .json(serde_json::json!({ "error": "Something went wrong"}));
+    let solver = match select_solver(&req, &solver_registry) {
+        Ok(solver) => solver,
+        Err(response) => return response,
+    };
+    // Server-wide options of the answering backend. Races mix backends and
+    // remote workers apply their own, so they get none
+    let backend_defaults = selected_type(&req, &solver_registry)
+        .and_then(|solver_type| solve_defaults.as_ref()?.backends.get(&solver_type).cloned())
+        .unwrap_or_default();
+    let highs_defaults = solve_defaults
+        .map(|defaults| defaults.highs)
+        .unwrap_or_default();
+
+    // Goals are rewritten into the problem before fingerprinting, so the tag
+    // covers the targets too
+    let mut req = req.into_inner();
+    let has_goal = match goal::rewrite_request(&mut req) {
+        Ok(has_goal) => has_goal,
+        Err(error) => {
+            return HttpResponse::UnprocessableEntity()
+                .json(serde_json::json!({ "error": error.details }));
         }
     };
 
+    let options = SolveOptions::for_request(&req, *use_presolve.get_ref(), &highs_defaults)
+        .with_defaults(&backend_defaults);
+
+    // Conditional request: skip solving if the client already holds the
+    // answer to this exact problem. Responses carrying a solver log are
+    // never tagged, as the log differs between runs, and neither are
+    // requests tuning backend parameters
+    let etag = recent_solves
+        .as_ref()
+        .filter(|_| !req.include_log && req.solver_options.is_empty())
+        .map(|_| {
+            fingerprint(
+                &req.polyhedron,
+                &req.objectives,
+                req.direction,
+                solver.name(),
+                &options,
+            )
+        });
+    if let (Some(recent), Some(tag)) = (&recent_solves, &etag) {
+        if if_none_match(&http_req, tag) && recent.contains(tag) {
+            return HttpResponse::NotModified()
+                .insert_header((ETAG, tag.as_str()))
+                .finish();
+        }
+    }
+
     let SolveRequest {
         polyhedron,
         objectives,
         direction,
-    } = req.into_inner();
-    let solve_task_result = tokio::task::spawn_blocking(move || {
-        // Hold the permit for the duration of the blocking solver call by moving
-        // it into the closure. It will be released automatically when dropped.
-        let _permit = permit;
-        solver.solve(polyhedron, objectives, direction, *use_presolve.get_ref())
-    })
-    .await;
+        ..
+    } = req;
+    // Stops the solve if the client disconnects before it finishes
+    let _cancel_on_disconnect = options.cancel.cancel_on_drop();
+    let solve_task_result = executor
+        .run(move || solver.solve_attributed(polyhedron, objectives, direction, &options))
+        .await;
 
     let solve_result = match solve_task_result {
         Err(e) => {
@@ -77,8 +136,26 @@ pub async fn solve(
     };
 
     match solve_result {
-        Ok(api_solutions) => {
-            HttpResponse::Ok().json(serde_json::json!({ "solutions": api_solutions }))
+        Ok((mut outcome, answered_by)) => {
+            if has_goal {
+                goal::strip_deviations(&mut outcome.solutions);
+            }
+            let mut response = HttpResponse::Ok();
+            response.insert_header((X_SOLVER.clone(), answered_by));
+            // A time-limited answer depends on timing, so it gets no tag
+            let complete = outcome.stats.termination != TerminationReason::TimeLimit;
+            if let (Some(recent), Some(tag), true) = (&recent_solves, &etag, complete) {
+                recent.insert(tag);
+                response.insert_header((ETAG, tag.as_str()));
+            }
+            let mut body = serde_json::json!({
+                "solutions": outcome.solutions,
+                "stats": ApiSolveStats::from(outcome.stats),
+            });
+            if let Some(log) = outcome.log {
+                body["log"] = log.into();
+            }
+            response.json(body)
         }
         Err(error) => {
             // Capture error with breadcrumb context
@@ -93,91 +170,225 @@ pub async fn solve(
     }
 }
 
-fn validate_solve_request(req: &SolveRequest) -> Result<(), HttpResponse> {
-    let variable_count = req.polyhedron.variables.len();
-    let column_count = req.polyhedron.a.shape.ncols;
-    if variable_count != column_count {
-        return Err(HttpResponse::UnprocessableEntity().json(
-            serde_json::json!({
-                "error": format!("Number of variables must match number of columns in A got {} variables and {} columns", variable_count, column_count)
-            }),
-        ));
-    }
-
-    let b_count = req.polyhedron.b.len();
-    let row_count = req.polyhedron.a.shape.nrows;
-    if b_count != row_count {
-        return Err(HttpResponse::UnprocessableEntity().json(
-            serde_json::json!({
-                "error": format!("Number of values in b must match number of rows in A got {} values and {} rows", b_count, row_count)
-            }),
-        ));
-    }
-
-    // Validate sparse matrix arrays have same length
-    let rows_len = req.polyhedron.a.rows.len();
-    let cols_len = req.polyhedron.a.cols.len();
-    let vals_len = req.polyhedron.a.vals.len();
-    if rows_len != cols_len || rows_len != vals_len {
-        return Err(HttpResponse::UnprocessableEntity().json(
-            serde_json::json!({
-                "error": format!("Sparse matrix arrays must have same length: got rows={}, cols={}, vals={}", rows_len, cols_len, vals_len)
-            }),
-        ));
-    }
-
-    // Validate sparse matrix indices are within bounds
-    for i in 0..rows_len {
-        let row = req.polyhedron.a.rows[i];
-        let col = req.polyhedron.a.cols[i];
-
-        if row < 0 || row >= row_count as i32 {
-            return Err(HttpResponse::UnprocessableEntity().json(
-                serde_json::json!({
-                    "error": format!("Row index {} at position {} is out of bounds [0, {})", row, i, row_count)
-                }),
-            ));
+/// POST /benchmark
+pub async fn benchmark(
+    req: StreamingJson<BenchmarkRequest>,
+    solver_registry: web::Data<SolverRegistry>,
+    use_presolve: web::Data<bool>,
+    executor: web::Data<SolveExecutor>,
+    config: web::Data<BenchmarkConfig>,
+) -> impl Responder {
+    let BenchmarkRequest {
+        problem,
+        time_limit_seconds,
+    } = req.into_inner();
+    if let Err(response) = validate_solve_request(&problem) {
+        return response;
+    }
+    if problem.goal.is_some() {
+        return HttpResponse::UnprocessableEntity().json(serde_json::json!({
+            "error": "goal is not supported by /benchmark"
+        }));
+    }
+
+    let time_limit = match time_limit_seconds {
+        Some(seconds) if !(seconds > 0.0 && seconds.is_finite()) => {
+            return HttpResponse::UnprocessableEntity().json(serde_json::json!({
+                "error": "time_limit_seconds must be a positive number"
+            }));
         }
+        Some(seconds) => Duration::from_secs_f64(seconds).min(config.max_time_limit),
+        None => config.max_time_limit,
+    };
 
-        if col < 0 || col >= column_count as i32 {
-            return Err(HttpResponse::UnprocessableEntity().json(
-                serde_json::json!({
-                    "error": format!("Column index {} at position {} is out of bounds [0, {})", col, i, column_count)
-                }),
-            ));
+    let solvers: Vec<_> = SolverType::all()
+        .into_iter()
+        .map(|solver_type| solver_registry.solver(solver_type))
+        .collect();
+    let options = SolveOptions::with_presolve(*use_presolve.get_ref());
+    let _cancel_on_disconnect = options.cancel.cancel_on_drop();
+    // Runs all backends in a single slot, like one long solve
+    let results = executor
+        .run(move || {
+            run_benchmark(
+                &solvers,
+                &problem.polyhedron,
+                &problem.objectives,
+                problem.direction,
+                &options,
+                time_limit,
+            )
+        })
+        .await;
+
+    match results {
+        Ok(results) => HttpResponse::Ok().json(serde_json::json!({
+            "time_limit_seconds": time_limit.as_secs_f64(),
+            "results": results,
+        })),
+        Err(e) => {
+            sentry::capture_message(
+                &format!("Benchmark did not complete successfully: {}", e),
+                sentry::Level::Error,
+            );
+            HttpResponse::InternalServerError()
+                .json(serde_json::json!({ "error": "Something went wrong" }))
         }
     }
+}
 
-    // Input size limits (prevent DoS/OOM)
-    const MAX_VARIABLES: usize = 100_000;
-    const MAX_CONSTRAINTS: usize = 100_000;
-    const MAX_NONZEROS: usize = 1_000_000;
+/// Whether the request's `If-None-Match` header lists the given entity tag
+fn if_none_match(req: &HttpRequest, tag: &str) -> bool {
+    req.headers()
+        .get_all(IF_NONE_MATCH)
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|candidate| candidate.trim())
+        .any(|candidate| candidate.strip_prefix("W/").unwrap_or(candidate) == tag)
+}
 
-    if variable_count > MAX_VARIABLES {
-        return Err(HttpResponse::UnprocessableEntity().json(
-            serde_json::json!({
-                "error": format!("Too many variables: {} exceeds limit of {}", variable_count, MAX_VARIABLES)
-            }),
-        ));
+/// Solver named by the request's `solver` field, or the default solver
+fn select_solver(
+    req: &SolveRequest,
+    solver_registry: &SolverRegistry,
+) -> Result<Arc<dyn Solver>, HttpResponse> {
+    let unprocessable = |error: String| {
+        HttpResponse::UnprocessableEntity().json(serde_json::json!({ "error": error }))
+    };
+    let solver = match req.solver.as_deref() {
+        None => solver_registry.default_solver(),
+        Some(RACE) => solver_registry
+            .race_solver()
+            .ok_or_else(|| unprocessable("Racing is not enabled, set RACE_SOLVERS".to_string()))?,
+        Some(name) => match solver_registry.remote_solver(name) {
+            Some(remote) => remote,
+            None => SolverType::try_from_name(name)
+                .map(|solver_type| solver_registry.solver(solver_type))
+                .map_err(|e| unprocessable(e.to_string()))?,
+        },
+    };
+    // Capped at the machine's cores, as every solve slot may ask for them
+    let cores = std::thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    Ok(match req.objective_threads {
+        Some(threads) if threads > 1 && cores > 1 => {
+            Arc::new(ParallelObjectivesSolver::new(solver, threads.min(cores)))
+        }
+        _ => solver,
+    })
+}
+
+/// Local backend named by the request's `solver` field, or the default
+/// backend when it names none
+///
+/// `None` when workers answer the request, as in `select_solver`.
+fn selected_type(req: &SolveRequest, solver_registry: &SolverRegistry) -> Option<SolverType> {
+    match req.solver.as_deref() {
+        None => solver_registry.default_type(),
+        Some(RACE) => None,
+        Some(name) if solver_registry.remote_solver(name).is_some() => None,
+        Some(name) => SolverType::try_from_name(name).ok(),
+    }
+}
+
+/// `solver` value racing the configured backends
+const RACE: &str = "race";
+
+fn validate_solve_request(req: &SolveRequest) -> Result<(), HttpResponse> {
+    validate::validate_solve_request(req).map_err(|error| {
+        HttpResponse::UnprocessableEntity().json(serde_json::json!({ "error": error.details }))
+    })
+}
+
+/// PUT /admin/default-solver
+pub async fn set_default_solver(
+    req: web::Json<SetDefaultSolverRequest>,
+    solver_registry: web::Data<SolverRegistry>,
+) -> impl Responder {
+    let solver_type = match SolverType::try_from_name(&req.solver) {
+        Ok(solver_type) => solver_type,
+        Err(error) => {
+            return HttpResponse::UnprocessableEntity()
+                .json(serde_json::json!({ "error": error.to_string() }));
+        }
+    };
+
+    let Some(previous) = solver_registry.set_default(solver_type) else {
+        return HttpResponse::Conflict().json(serde_json::json!({
+            "error": "Solves are forwarded to workers, which use their own default solver"
+        }));
+    };
+    let current = solver_registry.default_solver();
+    log::info!(
+        "Default solver changed from {} to {}",
+        previous.name(),
+        current.name()
+    );
+
+    HttpResponse::Ok().json(serde_json::json!({
+        "solver": current.name(),
+        "previous": previous.name(),
+    }))
+}
+
+/// GET /admin/licenses
+pub async fn license_status(solver_registry: web::Data<SolverRegistry>) -> impl Responder {
+    // Probing may block on a remote license server. The backends serving
+    // requests are probed, so no extra license is checked out.
+    let probe = web::block(move || {
+        SolverType::all()
+            .into_iter()
+            .filter_map(|solver_type| solver_registry.solver(solver_type).license_status())
+            .collect::<Vec<_>>()
+    })
+    .await;
+
+    match probe {
+        Ok(licenses) => HttpResponse::Ok().json(serde_json::json!({ "licenses": licenses })),
+        Err(e) => {
+            sentry::capture_message(
+                &format!("License probe did not complete successfully: {}", e),
+                sentry::Level::Error,
+            );
+            HttpResponse::InternalServerError()
+                .json(serde_json::json!({ "error": "Something went wrong" }))
+        }
     }
+}
 
-    if row_count > MAX_CONSTRAINTS {
-        return Err(HttpResponse::UnprocessableEntity().json(
-            serde_json::json!({
-                "error": format!("Too many constraints: {} exceeds limit of {}", row_count, MAX_CONSTRAINTS)
-            }),
-        ));
+/// POST /workers/register
+pub async fn register_worker(
+    req: web::Json<RegisterWorkerRequest>,
+    pool: web::Data<WorkerPool>,
+) -> impl Responder {
+    let req = req.into_inner();
+    if !(req.url.starts_with("http://") || req.url.starts_with("https://")) {
+        return HttpResponse::UnprocessableEntity().json(serde_json::json!({
+            "error": "Worker url must start with http:// or https://"
+        }));
     }
 
-    if rows_len > MAX_NONZEROS {
-        return Err(HttpResponse::UnprocessableEntity().json(
-            serde_json::json!({
-                "error": format!("Too many non-zero elements: {} exceeds limit of {}", rows_len, MAX_NONZEROS)
-            }),
-        ));
+    prune_workers(&pool);
+    if pool.register(&req.url, req.solvers.clone()) {
+        log::info!(
+            "Worker registered: {} ({})",
+            req.url,
+            req.solvers.join(", ")
+        );
     }
 
-    Ok(())
+    HttpResponse::Ok().json(serde_json::json!({ "registered": req.url }))
+}
+
+/// GET /workers
+pub async fn list_workers(pool: web::Data<WorkerPool>) -> impl Responder {
+    prune_workers(&pool);
+    HttpResponse::Ok().json(serde_json::json!({ "workers": pool.workers() }))
+}
+
+fn prune_workers(pool: &WorkerPool) {
+    for url in pool.prune() {
+        log::warn!("Worker {} missed its heartbeats and was removed", url);
+    }
 }
 
 /// GET /health
@@ -185,6 +396,27 @@ pub async fn health_check() -> impl Responder {
     HttpResponse::Ok().body("OK")
 }
 
+/// GET /readyz
+///
+/// Ready once every compiled backend has passed its startup self-test.
+pub async fn readiness(self_tests: web::Data<SelfTests>) -> impl Responder {
+    match self_tests.results() {
+        None => HttpResponse::ServiceUnavailable().json(serde_json::json!({
+            "ready": false,
+            "error": "Solver self-tests are still running",
+        })),
+        Some(results) => {
+            let ready = results.iter().all(|result| result.passed);
+            let body = serde_json::json!({ "ready": ready, "self_tests": results });
+            if ready {
+                HttpResponse::Ok().json(body)
+            } else {
+                HttpResponse::ServiceUnavailable().json(body)
+            }
+        }
+    }
+}
+
 /// GET /docs
 pub async fn docs() -> impl Responder {
     let docs_html = include_str!("../static/docs.html");
@@ -200,12 +432,25 @@ pub async fn root_redirect() -> impl Responder {
 
 // Middleware
 static X_API_KEY: HeaderName = HeaderName::from_static("x-api-key");
+/// Backend that answered a solve, which differs from the configured solver
+/// after falling back
+static X_SOLVER: HeaderName = HeaderName::from_static("x-solver");
 
 #[derive(Clone)]
 struct AuthConfig {
     token: String,
 }
 
+#[derive(Clone)]
+struct AdminAuthConfig {
+    token: String,
+}
+
+#[derive(Clone)]
+struct RegistrationAuthConfig {
+    token: String,
+}
+
 fn unauthorized_error() -> HttpResponse<BoxBody> {
     HttpResponse::Unauthorized()
         .json(serde_json::json!({ "error": "Unauthorized" }))
@@ -232,6 +477,36 @@ async fn token_auth(
         return Ok(req.into_response(internal_error()));
     };
 
+    authorize(&auth.token, req, next).await
+}
+
+async fn admin_token_auth(
+    req: ServiceRequest,
+    next: Next<BoxBody>,
+) -> Result<ServiceResponse<BoxBody>, Error> {
+    let Some(auth) = req.app_data::<web::Data<AdminAuthConfig>>().cloned() else {
+        return Ok(req.into_response(internal_error()));
+    };
+
+    authorize(&auth.token, req, next).await
+}
+
+async fn registration_token_auth(
+    req: ServiceRequest,
+    next: Next<BoxBody>,
+) -> Result<ServiceResponse<BoxBody>, Error> {
+    let Some(auth) = req.app_data::<web::Data<RegistrationAuthConfig>>().cloned() else {
+        return Ok(req.into_response(internal_error()));
+    };
+
+    authorize(&auth.token, req, next).await
+}
+
+async fn authorize(
+    expected_token: &str,
+    req: ServiceRequest,
+    next: Next<BoxBody>,
+) -> Result<ServiceResponse<BoxBody>, Error> {
     let Some(raw) = req.headers().get(&X_API_KEY) else {
         return Ok(req.into_response(unauthorized_error()));
     };
@@ -241,7 +516,7 @@ async fn token_auth(
     };
 
     // Use constant-time comparison to prevent timing attacks
-    let valid_token = auth.token.as_bytes().ct_eq(token.as_bytes()).into();
+    let valid_token = expected_token.as_bytes().ct_eq(token.as_bytes()).into();
 
     if valid_token {
         let res = next.call(req).await?;
@@ -251,6 +526,67 @@ async fn token_auth(
     Ok(req.into_response(forbidden_error()))
 }
 
+/// Server-wide solve settings beneath each request's own
+#[derive(Clone, Default)]
+pub struct SolveDefaults {
+    /// HiGHS settings from the `HIGHS_*` variables
+    highs: HighsOptions,
+    /// Options per backend from the `<BACKEND>_DEFAULTS` variables
+    backends: HashMap<SolverType, SolverDefaults>,
+}
+
+#[derive(Clone)]
+pub struct BenchmarkConfig {
+    /// Upper bound for each backend's time limit
+    max_time_limit: Duration,
+}
+
+#[derive(Clone)]
+struct CompressionConfig {
+    min_size: u64,
+}
+
+/// Opt small responses out of compression.
+///
+/// Must be wrapped inside `Compress`: marking the response as `identity`
+/// makes the compressor pass it through untouched, and without an encoding
+/// applied no `Vary` header is needed either. `strip_identity_encoding`
+/// outside `Compress` removes the marker again before it reaches the wire.
+async fn compression_threshold(
+    req: ServiceRequest,
+    next: Next<BoxBody>,
+) -> Result<ServiceResponse<BoxBody>, Error> {
+    let min_size = req
+        .app_data::<web::Data<CompressionConfig>>()
+        .map(|config| config.min_size)
+        .unwrap_or(0);
+
+    let mut res = next.call(req).await?;
+    if let BodySize::Sized(size) = res.response().body().size() {
+        if size < min_size && !res.headers().contains_key(CONTENT_ENCODING) {
+            res.headers_mut()
+                .insert(CONTENT_ENCODING, HeaderValue::from_static("identity"));
+        }
+    }
+
+    Ok(res)
+}
+
+/// Drop the `Content-Encoding: identity` that `compression_threshold` set
+/// once `Compress` has seen it; some clients and proxies mishandle it.
+///
+/// Must be wrapped outside `Compress`.
+async fn strip_identity_encoding<B: MessageBody>(
+    req: ServiceRequest,
+    next: Next<B>,
+) -> Result<ServiceResponse<B>, Error> {
+    let mut res = next.call(req).await?;
+    if res.headers().get(CONTENT_ENCODING) == Some(&HeaderValue::from_static("identity")) {
+        res.headers_mut().remove(CONTENT_ENCODING);
+    }
+    Ok(res)
+}
+
 fn init_sentry() -> sentry::ClientInitGuard {
     let dsn = env::var("SENTRY_DSN").expect("SENTRY_DSN not found");
     let environment = env::var("SENTRY_ENVIRONMENT").expect("SENTRY_ENVIRONMENT not found");
@@ -281,10 +617,62 @@ fn init_sentry() -> sentry::ClientInitGuard {
     ))
 }
 
+/// Server-wide options per backend, from `<BACKEND>_DEFAULTS` environment
+/// variables such as `HIGHS_DEFAULTS={"mip_rel_gap": 0.001}`
+fn solver_defaults() -> HashMap<SolverType, SolverDefaults> {
+    SolverType::all()
+        .into_iter()
+        .filter_map(|solver_type| {
+            let var = format!("{}_DEFAULTS", solver_type.name().to_uppercase());
+            let json = env::var(&var).ok()?;
+            let defaults: SolverDefaults = serde_json::from_str(&json).unwrap_or_else(|e| {
+                panic!("{} must be a JSON object of solve options: {}", var, e)
+            });
+            if let Some(seconds) = defaults.time_limit_seconds {
+                if !(seconds > 0.0 && seconds.is_finite()) {
+                    panic!("time_limit_seconds in {} must be a positive number", var);
+                }
+            }
+            if let Some(gap) = defaults.mip_rel_gap {
+                if !(gap >= 0.0 && gap.is_finite()) {
+                    panic!("mip_rel_gap in {} must be a non-negative number", var);
+                }
+            }
+            Some((solver_type, defaults))
+        })
+        .collect()
+}
+
+/// Comma-separated solver names from an environment variable
+fn solver_list(var: &str) -> Vec<SolverType> {
+    env::var(var)
+        .map(|names| {
+            names
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(|name| {
+                    SolverType::from_str(name)
+                        .unwrap_or_else(|| panic!("Unknown solver '{}' in {}", name, var))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 // ---------- Server bootstrap ----------
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     dotenv().ok();
+    // Filtered by RUST_LOG, e.g. RUST_LOG=debug for GLPK's solver output
+    env_logger::init();
+
+    // Any arguments select command-line mode instead of the server
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        std::process::exit(cli::run(&args));
+    }
+
     let port = env::var("PORT")
         .ok()
         .and_then(|s| s.parse::<u16>().ok())
@@ -295,6 +683,17 @@ async fn main() -> std::io::Result<()> {
         .and_then(|v| v.parse::<usize>().ok())
         .unwrap_or(2 * 1024 * 1024); // default 2 MB
 
+    // Response compression (gzip/brotli/zstd, negotiated via Accept-Encoding)
+    let compression_enabled = env::var("COMPRESSION")
+        .ok()
+        .and_then(|s| s.parse::<bool>().ok())
+        .unwrap_or(true);
+
+    let compression_min_size = env::var("COMPRESSION_MIN_SIZE")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or(1024); // default 1 KB
+
     let protect = env::var("PROTECT")
         .ok()
         .and_then(|s| s.parse::<bool>().ok())
@@ -306,6 +705,11 @@ async fn main() -> std::io::Result<()> {
         String::new()
     };
 
+    // Admin endpoints are only mounted when an admin token is configured
+    let admin_token = env::var("ADMIN_TOKEN").ok().filter(|t| !t.is_empty());
+    let admin_enabled = admin_token.is_some();
+    let admin_token = admin_token.unwrap_or_default();
+
     // Initialize Sentry if DSN is configured
     // Guard must be kept in scope until the server exits
     let sentry_enabled = env::var("SENTRY_DSN").is_ok();
@@ -316,11 +720,12 @@ async fn main() -> std::io::Result<()> {
         println!("Sentry monitoring disabled (no SENTRY_DSN configured)");
         None
     };
-    // Select solver based on environment variable (default: GLPK)
+    // Select solver based on environment variable (default: GLPK, or microlp
+    // in builds without GLPK)
     let solver_type = env::var("SOLVER")
         .ok()
         .and_then(|s| SolverType::from_str(&s))
-        .unwrap_or(SolverType::Glpk);
+        .unwrap_or_default();
 
     // Configure presolve (default: true)
     let use_presolve = env::var("USE_PRESOLVE")
@@ -328,18 +733,190 @@ async fn main() -> std::io::Result<()> {
         .and_then(|s| s.parse::<bool>().ok())
         .unwrap_or(true);
 
+    // HiGHS settings, which requests can override apart from threads
+    let highs_defaults = HighsOptions {
+        threads: env::var("HIGHS_THREADS")
+            .ok()
+            .map(|s| s.parse::<u32>().expect("HIGHS_THREADS must be a number")),
+        parallel: env::var("HIGHS_PARALLEL").ok().map(|s| {
+            HighsParallel::from_name(&s).expect("HIGHS_PARALLEL must be off, choose or on")
+        }),
+        simplex_strategy: env::var("HIGHS_SIMPLEX_STRATEGY").ok().map(|s| {
+            s.parse::<i32>()
+                .ok()
+                .filter(|strategy| (0..=4).contains(strategy))
+                .expect("HIGHS_SIMPLEX_STRATEGY must be between 0 and 4")
+        }),
+    };
+
+    // Per-backend solve options beneath each request's, e.g. HIGHS_DEFAULTS
+    let solve_defaults = SolveDefaults {
+        highs: highs_defaults,
+        backends: solver_defaults(),
+    };
+
     // Configure model cache size (default: 0 disabled, set to enable)
     let cache_size = env::var("MODEL_CACHE_SIZE")
         .ok()
         .and_then(|s| s.parse::<usize>().ok());
 
-    let solver = create_solver_with_cache(solver_type, cache_size);
+    // Dispatch solves to worker instances instead of solving locally
+    let worker_urls: Vec<String> = env::var("WORKER_URLS")
+        .map(|urls| {
+            urls.split(',')
+                .map(str::trim)
+                .filter(|url| !url.is_empty())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // Workers can also register themselves when a registration token is set
+    let registration_token = env::var("WORKER_REGISTRATION_TOKEN")
+        .ok()
+        .filter(|t| !t.is_empty());
+    let registration_enabled = registration_token.is_some();
+    let registration_token = registration_token.unwrap_or_default();
+
+    let worker_ttl = env::var("WORKER_TTL_SECONDS")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(Duration::from_secs(30));
+
+    let mut worker_pool = None;
+    // Fallback chain, e.g. DEFAULT_SOLVER=gurobi,highs,glpk (overrides SOLVER)
+    let solver_chain = solver_list("DEFAULT_SOLVER");
+
+    // Backends a request can race with `"solver": "race"`
+    let race_solvers = solver_list("RACE_SOLVERS");
+    if race_solvers.len() == 1 {
+        panic!("RACE_SOLVERS needs at least two solvers");
+    }
+
+    let mut solver_registry = if !worker_urls.is_empty() || registration_enabled {
+        let worker_timeout = env::var("WORKER_TIMEOUT_SECONDS")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(Duration::from_secs(300)); // default 5 minutes
+        let worker_token = env::var("WORKER_TOKEN").ok().filter(|t| !t.is_empty());
+
+        let pool = Arc::new(WorkerPool::new(worker_urls, worker_ttl));
+        println!("Dispatching solves to {} static worker(s)", pool.len());
+        if registration_enabled {
+            println!(
+                "Worker registration: enabled, {}s heartbeat TTL",
+                worker_ttl.as_secs()
+            );
+        }
+        worker_pool = Some(web::Data::from(pool.clone()));
+        SolverRegistry::with_remote(
+            RemoteSolver::new(pool, worker_token, worker_timeout),
+            cache_size,
+        )
+    } else if solver_chain.len() > 1 {
+        SolverRegistry::with_solver(
+            Arc::from(create_solver_chain(&solver_chain, cache_size)),
+            cache_size,
+        )
+    } else {
+        SolverRegistry::new(
+            solver_chain.first().copied().unwrap_or(solver_type),
+            cache_size,
+        )
+    };
+
+    if !race_solvers.is_empty() {
+        solver_registry.enable_race(&race_solvers);
+    }
+
+    // Solve a canned model on every compiled backend, in the background as
+    // license checks can be slow; /readyz reports the outcome
+    let self_tests = web::Data::new(SelfTests::default());
+    {
+        let self_tests = self_tests.clone();
+        std::thread::spawn(move || {
+            let results = run_self_tests(&SolverType::all());
+            for result in &results {
+                match &result.error {
+                    None => println!("Self-test passed: {}", result.solver),
+                    Some(error) => {
+                        let message = format!("Self-test failed: {}: {}", result.solver, error);
+                        println!("{}", message);
+                        sentry::capture_message(&message, sentry::Level::Error);
+                    }
+                }
+            }
+            self_tests.record(results);
+        });
+    }
+
+    // Register with a front-end instance as a worker
+    if let Ok(frontend_url) = env::var("FRONTEND_URL") {
+        let advertise_url =
+            env::var("ADVERTISE_URL").expect("ADVERTISE_URL required when FRONTEND_URL is set");
+        let interval = env::var("HEARTBEAT_SECONDS")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .filter(|&secs| secs > 0)
+            .map(Duration::from_secs)
+            .unwrap_or(Duration::from_secs(10));
+        println!(
+            "Sending heartbeats to {} every {}s",
+            frontend_url,
+            interval.as_secs()
+        );
+        actix_web::rt::spawn(heartbeat::run(heartbeat::HeartbeatConfig {
+            frontend_url,
+            advertise_url,
+            token: env::var("FRONTEND_TOKEN").ok().filter(|t| !t.is_empty()),
+            interval,
+        }));
+    }
+
+    // Upper bound on the per-backend time limit of POST /benchmark
+    let benchmark_max_time = env::var("BENCHMARK_MAX_SECONDS")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(Duration::from_secs(60));
+
+    // Remember recently solved requests for ETag/If-None-Match (0 disables)
+    let etag_cache_size = env::var("ETAG_CACHE_SIZE")
+        .ok()
+        .and_then(|s| s.parse::<usize>().ok())
+        .unwrap_or(1024);
+
+    let etag_ttl = env::var("ETAG_TTL_SECONDS")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(Duration::from_secs(300)); // default 5 minutes
+
+    let recent_solves = NonZeroUsize::new(etag_cache_size)
+        .map(|capacity| web::Data::new(RecentSolves::new(capacity, etag_ttl)));
 
     println!(
         "Server is {}",
         if protect { "protected" } else { "unprotected" }
     );
-    println!("Using solver: {}", solver.name());
+    if compression_enabled {
+        println!(
+            "Response compression: enabled for bodies >= {} bytes",
+            compression_min_size
+        );
+    } else {
+        println!("Response compression: disabled");
+    }
+    println!(
+        "Admin endpoints: {}",
+        if admin_enabled { "enabled" } else { "disabled" }
+    );
+    println!("Using solver: {}", solver_registry.default_solver().name());
+    if let Some(race) = solver_registry.race_solver() {
+        println!("Racing enabled: {}", race.name());
+    }
     println!(
         "Presolve: {}",
         if use_presolve { "enabled" } else { "disabled" }
@@ -348,10 +925,18 @@ async fn main() -> std::io::Result<()> {
         Some(cs) => println!("LRU Model builder cache: {} entries", cs),
         None => println!("LRU Model builder cache: disabled"),
     }
+    match &recent_solves {
+        Some(_) => println!(
+            "ETag cache: {} entries, {}s TTL",
+            etag_cache_size,
+            etag_ttl.as_secs()
+        ),
+        None => println!("ETag cache: disabled"),
+    }
     println!("Starting server on http://127.0.0.1:{}", port);
 
-    // Clone solver and presolve flag for use in the closure
-    let solver_data = web::Data::new(solver);
+    // Clone solver registry and presolve flag for use in the closure
+    let solver_data = web::Data::new(solver_registry);
     let presolve_data = web::Data::new(use_presolve);
 
     // Configure maximum concurrent blocking solver threads via env var.
@@ -363,18 +948,36 @@ async fn main() -> std::io::Result<()> {
         .ok()
         .and_then(|s| s.parse::<i32>().ok())
         .unwrap_or(1);
-    let solver_semaphore = match max_blocking_threads {
+    let executor = match max_blocking_threads {
         n if n < 1 => panic!("MAX_BLOCKING_THREADS must be >= 1"),
-        n => Arc::new(tokio::sync::Semaphore::new(n as usize)),
+        n => web::Data::new(SolveExecutor::new(n as usize)),
     };
 
     HttpServer::new(move || {
         App::new()
+            .wrap(Condition::new(
+                compression_enabled,
+                from_fn(compression_threshold),
+            ))
+            .wrap(Condition::new(compression_enabled, Compress::default()))
+            .wrap(Condition::new(
+                compression_enabled,
+                from_fn(strip_identity_encoding),
+            ))
             .wrap(Logger::default())
             .wrap(Condition::new(sentry_enabled, Sentry::new()))
             .app_data(solver_data.clone())
             .app_data(presolve_data.clone())
-            .app_data(web::Data::new(solver_semaphore.clone()))
+            .app_data(executor.clone())
+            .app_data(web::Data::new(solve_defaults.clone()))
+            .app_data(self_tests.clone())
+            .app_data(web::Data::new(CompressionConfig {
+                min_size: compression_min_size,
+            }))
+            .app_data(web::Data::new(StreamingJsonConfig { limit: json_limit }))
+            .app_data(web::Data::new(BenchmarkConfig {
+                max_time_limit: benchmark_max_time,
+            }))
             .app_data(
                 web::JsonConfig::default()
                     .limit(json_limit)
@@ -391,13 +994,48 @@ async fn main() -> std::io::Result<()> {
             .app_data(web::Data::new(AuthConfig {
                 token: token.clone(),
             }))
+            .app_data(web::Data::new(AdminAuthConfig {
+                token: admin_token.clone(),
+            }))
+            .app_data(web::Data::new(RegistrationAuthConfig {
+                token: registration_token.clone(),
+            }))
+            .configure(|cfg| {
+                if let Some(recent) = &recent_solves {
+                    cfg.app_data(recent.clone());
+                }
+                if let Some(pool) = &worker_pool {
+                    cfg.app_data(pool.clone());
+                }
+            })
             .route("/", web::get().to(root_redirect))
             .route("/health", web::get().to(health_check))
+            .route("/readyz", web::get().to(readiness))
             .route("/docs", web::get().to(docs))
+            // Registered before the catch-all scope below so /admin and /workers paths reach it
+            .configure(|cfg| {
+                if admin_enabled {
+                    cfg.service(
+                        web::scope("/admin")
+                            .wrap(from_fn(admin_token_auth))
+                            .route("/default-solver", web::put().to(set_default_solver))
+                            .route("/licenses", web::get().to(license_status)),
+                    );
+                }
+                if registration_enabled {
+                    cfg.service(
+                        web::scope("/workers")
+                            .wrap(from_fn(registration_token_auth))
+                            .route("", web::get().to(list_workers))
+                            .route("/register", web::post().to(register_worker)),
+                    );
+                }
+            })
             .service(
                 web::scope("")
                     .wrap(Condition::new(protect, from_fn(token_auth)))
-                    .route("/solve", web::post().to(solve)),
+                    .route("/solve", web::post().to(solve))
+                    .route("/benchmark", web::post().to(benchmark)),
             )
     })
     .bind(("0.0.0.0", port))?
@@ -447,9 +1085,65 @@ mod tests {
                 obj
             }],
             direction: SolverDirection::Maximize,
+            solver: None,
+            time_limit_seconds: None,
+            mip_rel_gap: None,
+            include_log: false,
+            exact_lp: false,
+            highs: None,
+            solver_options: HashMap::new(),
+            objective_threads: None,
+            goal: None,
+            method: None,
         }
     }
 
+    #[test]
+    fn select_solver_uses_requested_backend() {
+        let registry = SolverRegistry::new(SolverType::default(), None);
+        let mut req = make_valid_request();
+        req.solver = Some("microlp".into());
+        assert_eq!(
+            select_solver(&req, &registry).ok().unwrap().name(),
+            "microlp"
+        );
+
+        req.solver = Some("simplex-9000".into());
+        let resp = select_solver(&req, &registry).err().unwrap();
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[test]
+    fn selected_type_is_none_for_remote_dispatch() {
+        let mut req = make_valid_request();
+        req.solver = Some("microlp".into());
+        let registry = SolverRegistry::new(SolverType::default(), None);
+        assert_eq!(selected_type(&req, &registry), Some(SolverType::Microlp));
+
+        let pool = Arc::new(WorkerPool::new(Vec::new(), Duration::from_secs(60)));
+        let remote = RemoteSolver::new(pool, None, Duration::from_secs(5));
+        let registry = SolverRegistry::with_remote(remote, None);
+        assert_eq!(selected_type(&req, &registry), None);
+        req.solver = None;
+        assert_eq!(selected_type(&req, &registry), None);
+    }
+
+    #[test]
+    fn select_solver_race_requires_configuration() {
+        let mut registry = SolverRegistry::new(SolverType::default(), None);
+        let mut req = make_valid_request();
+        req.solver = Some("race".into());
+        let resp = select_solver(&req, &registry).err().unwrap();
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        registry.enable_race(&[SolverType::default(), SolverType::Microlp]);
+        assert!(select_solver(&req, &registry)
+            .ok()
+            .unwrap()
+            .name()
+            .starts_with("race("));
+    }
+
     #[test]
     fn validate_solve_request_valid_request() {
         let req = make_valid_request();
@@ -471,4 +1165,257 @@ mod tests {
         let resp = validate_solve_request(&req).unwrap_err();
         assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
     }
+
+    #[test]
+    fn validate_solve_request_non_positive_time_limit_should_return_422() {
+        let mut req = make_valid_request();
+        for seconds in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            req.time_limit_seconds = Some(seconds);
+            let resp = validate_solve_request(&req).unwrap_err();
+            assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        }
+
+        req.time_limit_seconds = Some(0.5);
+        assert!(validate_solve_request(&req).is_ok());
+    }
+
+    #[test]
+    fn validate_solve_request_negative_mip_gap_should_return_422() {
+        let mut req = make_valid_request();
+        req.mip_rel_gap = Some(-0.1);
+        let resp = validate_solve_request(&req).unwrap_err();
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        req.mip_rel_gap = Some(0.0);
+        assert!(validate_solve_request(&req).is_ok());
+    }
+
+    #[test]
+    fn validate_solve_request_goal_per_objective_should_return_422() {
+        let mut req = make_valid_request();
+        req.goal = Some(vec![1, 2]);
+        let resp = validate_solve_request(&req).unwrap_err();
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        req.goal = Some(vec![1; req.objectives.len()]);
+        assert!(validate_solve_request(&req).is_ok());
+    }
+
+    #[test]
+    fn validate_solve_request_zero_objective_threads_should_return_422() {
+        let mut req = make_valid_request();
+        req.objective_threads = Some(0);
+        let resp = validate_solve_request(&req).unwrap_err();
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        req.objective_threads = Some(4);
+        assert!(validate_solve_request(&req).is_ok());
+    }
+
+    #[test]
+    fn validate_solve_request_unknown_simplex_strategy_should_return_422() {
+        let mut req: SolveRequest = serde_json::from_value({
+            let mut body = serde_json::to_value(make_valid_request()).unwrap();
+            body["highs"] = serde_json::json!({ "parallel": "on", "simplex_strategy": 5 });
+            body
+        })
+        .unwrap();
+        let resp = validate_solve_request(&req).unwrap_err();
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        req.highs.as_mut().unwrap().simplex_strategy = Some(4);
+        assert!(validate_solve_request(&req).is_ok());
+    }
+
+    #[test]
+    fn if_none_match_accepts_lists_and_weak_tags() {
+        let req = actix_web::test::TestRequest::default()
+            .insert_header((IF_NONE_MATCH, "\"other\", W/\"abc\""))
+            .to_http_request();
+        assert!(if_none_match(&req, "\"abc\""));
+        assert!(!if_none_match(&req, "\"def\""));
+
+        let req = actix_web::test::TestRequest::default().to_http_request();
+        assert!(!if_none_match(&req, "\"abc\""));
+    }
+
+    fn compressed_test_app_body(size: usize) -> HttpResponse {
+        HttpResponse::Ok().body("x".repeat(size))
+    }
+
+    #[actix_web::test]
+    async fn benchmark_rejects_invalid_time_limit() {
+        let app = actix_web::test::init_service(
+            App::new()
+                .app_data(web::Data::new(SolverRegistry::new(
+                    SolverType::default(),
+                    None,
+                )))
+                .app_data(web::Data::new(true))
+                .app_data(web::Data::new(SolveExecutor::new(1)))
+                .app_data(web::Data::new(BenchmarkConfig {
+                    max_time_limit: Duration::from_secs(60),
+                }))
+                .route("/benchmark", web::post().to(benchmark)),
+        )
+        .await;
+
+        let mut body = serde_json::to_value(make_valid_request()).unwrap();
+        body["time_limit_seconds"] = serde_json::json!(-1);
+        let req = actix_web::test::TestRequest::post()
+            .uri("/benchmark")
+            .set_json(body)
+            .to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[actix_web::test]
+    async fn set_default_solver_conflicts_with_remote_dispatch() {
+        let pool = Arc::new(WorkerPool::new(Vec::new(), Duration::from_secs(60)));
+        let remote = RemoteSolver::new(pool, None, Duration::from_secs(5));
+        let app = actix_web::test::init_service(
+            App::new()
+                .app_data(web::Data::new(SolverRegistry::with_remote(remote, None)))
+                .route("/default-solver", web::put().to(set_default_solver)),
+        )
+        .await;
+
+        let req = actix_web::test::TestRequest::put()
+            .uri("/default-solver")
+            .set_json(serde_json::json!({ "solver": "microlp" }))
+            .to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+    }
+
+    #[actix_web::test]
+    async fn readyz_reports_self_tests() {
+        let self_tests = web::Data::new(SelfTests::default());
+        let app = actix_web::test::init_service(
+            App::new()
+                .app_data(self_tests.clone())
+                .route("/readyz", web::get().to(readiness)),
+        )
+        .await;
+        let get = || {
+            actix_web::test::TestRequest::get()
+                .uri("/readyz")
+                .to_request()
+        };
+
+        let resp = actix_web::test::call_service(&app, get()).await;
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        self_tests.record(run_self_tests(&[SolverType::Microlp]));
+        let resp = actix_web::test::call_service(&app, get()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: serde_json::Value = actix_web::test::read_body_json(resp).await;
+        assert_eq!(body["ready"], true);
+        assert_eq!(body["self_tests"][0]["solver"], "microlp");
+    }
+
+    #[actix_web::test]
+    async fn solve_reports_stats() {
+        let app = actix_web::test::init_service(
+            App::new()
+                .app_data(web::Data::new(SolverRegistry::new(
+                    SolverType::default(),
+                    None,
+                )))
+                .app_data(web::Data::new(true))
+                .app_data(web::Data::new(SolveExecutor::new(1)))
+                .route("/solve", web::post().to(solve)),
+        )
+        .await;
+
+        let mut request = make_valid_request();
+        request.solver = Some("microlp".into());
+        let req = actix_web::test::TestRequest::post()
+            .uri("/solve")
+            .set_json(&request)
+            .to_request();
+        let body: serde_json::Value = actix_web::test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["stats"]["termination"], "Completed");
+        assert!(body["stats"]["total_seconds"].as_f64().unwrap() >= 0.0);
+        assert!(body["stats"]["presolve_reductions"].is_null());
+    }
+
+    #[actix_web::test]
+    async fn compression_applies_to_large_responses() {
+        let app = actix_web::test::init_service(
+            App::new()
+                .wrap(from_fn(compression_threshold))
+                .wrap(Compress::default())
+                .wrap(from_fn(strip_identity_encoding))
+                .app_data(web::Data::new(CompressionConfig { min_size: 1024 }))
+                .route(
+                    "/large",
+                    web::get().to(|| async { compressed_test_app_body(4096) }),
+                ),
+        )
+        .await;
+
+        let req = actix_web::test::TestRequest::get()
+            .uri("/large")
+            .insert_header(("Accept-Encoding", "gzip"))
+            .to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.headers().get(CONTENT_ENCODING).unwrap(), "gzip");
+        assert!(resp.headers().contains_key("vary"));
+    }
+
+    #[actix_web::test]
+    async fn compression_skips_small_responses() {
+        let app = actix_web::test::init_service(
+            App::new()
+                .wrap(from_fn(compression_threshold))
+                .wrap(Compress::default())
+                .wrap(from_fn(strip_identity_encoding))
+                .app_data(web::Data::new(CompressionConfig { min_size: 1024 }))
+                .route(
+                    "/small",
+                    web::get().to(|| async { compressed_test_app_body(16) }),
+                ),
+        )
+        .await;
+
+        let req = actix_web::test::TestRequest::get()
+            .uri("/small")
+            .insert_header(("Accept-Encoding", "gzip"))
+            .to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert!(!resp.headers().contains_key(CONTENT_ENCODING));
+        let body = actix_web::test::read_body(resp).await;
+        assert_eq!(body.len(), 16);
+    }
+
+    #[cfg(feature = "glpk-solver")]
+    #[actix_web::test]
+    async fn set_default_solver_unknown_solver_should_return_422() {
+        let registry = web::Data::new(SolverRegistry::new(SolverType::Glpk, None));
+        let req = web::Json(SetDefaultSolverRequest {
+            solver: "simplex-9000".into(),
+        });
+        let http_req = actix_web::test::TestRequest::default().to_http_request();
+        let resp = set_default_solver(req, registry.clone())
+            .await
+            .respond_to(&http_req);
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(registry.default_type(), Some(SolverType::Glpk));
+    }
+
+    #[cfg(feature = "glpk-solver")]
+    #[actix_web::test]
+    async fn set_default_solver_known_solver_should_return_200() {
+        let registry = web::Data::new(SolverRegistry::new(SolverType::Glpk, None));
+        let req = web::Json(SetDefaultSolverRequest {
+            solver: "glpk".into(),
+        });
+        let http_req = actix_web::test::TestRequest::default().to_http_request();
+        let resp = set_default_solver(req, registry)
+            .await
+            .respond_to(&http_req);
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
 }
@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use glpk_rust::Bound;
+/// Inclusive (lower, upper) bound, matching GLPK's representation
+pub type Bound = (i32, i32);
 
 // ---------- API response types (decoupled from the lib) ----------
 
@@ -16,22 +17,46 @@ pub enum Status {
     SimplexFailed = 7,
     MIPFailed = 8,
     EmptySpace = 9,
+    /// Stopped at the request's time limit; holds the best solution found, if any
+    TimeLimit = 10,
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct ApiSolution {
     pub status: Status,
-    pub objective: i32,
+    /// Objective value as reported by the backend
+    pub objective: f64,
     pub solution: HashMap<String, i32>,
     pub error: Option<String>,
 }
 
+#[derive(Serialize)]
+pub enum TerminationReason {
+    Completed,
+    TimeLimit,
+    Failed,
+}
+
+#[derive(Serialize)]
+pub struct ApiPresolveReductions {
+    pub rows_removed: usize,
+    pub cols_removed: usize,
+}
+
+#[derive(Serialize)]
+pub struct ApiSolveStats {
+    pub total_seconds: f64,
+    /// Absent when presolve was off or the backend doesn't report it
+    pub presolve_reductions: Option<ApiPresolveReductions>,
+    pub termination: TerminationReason,
+}
+
 // ---------- API (wire) types: owned & serde-friendly ----------
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct ApiVariable {
     pub id: String,
-    pub bound: Bound, // (i32, i32) from glpk_rust
+    pub bound: Bound,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
@@ -48,7 +73,7 @@ pub struct ApiIntegerSparseMatrix {
     pub shape: ApiShape,
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum SolverDirection {
     Maximize,
@@ -57,11 +82,113 @@ pub enum SolverDirection {
 
 pub type ObjectiveOwned = HashMap<String, f64>;
 
-#[derive(Deserialize)]
+#[derive(Serialize, Deserialize)]
 pub struct SolveRequest {
     pub polyhedron: SparseLEIntegerPolyhedron,
     pub objectives: Vec<ObjectiveOwned>,
     pub direction: SolverDirection,
+    /// Backend for this request, or `"race"` to race the configured backends.
+    /// Uses the server's default solver when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub solver: Option<String>,
+    /// Stop after this many seconds and return the best solution found so far
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_limit_seconds: Option<f64>,
+    /// Stop the MIP search once the relative gap between the incumbent and
+    /// the best bound is at most this
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mip_rel_gap: Option<f64>,
+    /// Attach the backend's solver log to the response
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub include_log: bool,
+    /// Re-solve the LP relaxation in exact rational arithmetic before the
+    /// MIP phase
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub exact_lp: bool,
+    /// HiGHS settings for this request, over the server's `HIGHS_*` defaults
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub highs: Option<ApiHighsOptions>,
+    /// Backend parameters by name, e.g. `{"MIPFocus": 1}` for Gurobi
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub solver_options: HashMap<String, ApiSolverOption>,
+    /// Solve the objectives on up to this many threads at once
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub objective_threads: Option<usize>,
+    /// Target per objective: minimize each objective's distance to its
+    /// target instead of optimizing it in `direction`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub goal: Option<Vec<i32>>,
+    /// Algorithm for the LP relaxations, the backend's choice when absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method: Option<LpMethod>,
+}
+
+/// LP algorithm requested with `method`
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum LpMethod {
+    Primal,
+    Dual,
+    Barrier,
+}
+
+/// Server-wide options for one backend, from its `<BACKEND>_DEFAULTS`
+/// environment variable. Requests override them field by field.
+#[derive(Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SolverDefaults {
+    pub time_limit_seconds: Option<f64>,
+    pub mip_rel_gap: Option<f64>,
+    #[serde(default)]
+    pub solver_options: HashMap<String, ApiSolverOption>,
+}
+
+/// Value of a backend parameter in `solver_options`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum ApiSolverOption {
+    Int(i64),
+    Float(f64),
+    Text(String),
+}
+
+/// HiGHS `parallel` option
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum HighsParallel {
+    Off,
+    Choose,
+    On,
+}
+
+impl HighsParallel {
+    /// Parse the option value as HiGHS spells it
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "off" => Some(HighsParallel::Off),
+            "choose" => Some(HighsParallel::Choose),
+            "on" => Some(HighsParallel::On),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            HighsParallel::Off => "off",
+            HighsParallel::Choose => "choose",
+            HighsParallel::On => "on",
+        }
+    }
+}
+
+/// HiGHS settings a request can override; absent fields keep the default
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ApiHighsOptions {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parallel: Option<HighsParallel>,
+    /// 0 lets HiGHS choose, 1-3 are dual simplex variants, 4 is primal
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub simplex_strategy: Option<i32>,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
@@ -71,3 +198,75 @@ pub struct SparseLEIntegerPolyhedron {
     pub b: Vec<i32>, // LE right-hand side
     pub variables: Vec<ApiVariable>,
 }
+
+// ---------- Admin API types ----------
+
+#[derive(Deserialize)]
+pub struct SetDefaultSolverRequest {
+    pub solver: String,
+}
+
+#[derive(Serialize)]
+pub struct ApiLicenseStatus {
+    pub solver: String,
+    pub valid: bool,
+    pub error: Option<String>,
+    /// Expiry date as `YYYY-MM-DD`; `null` when the license doesn't expire
+    /// or the backend has no way to report it
+    pub expires: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct ApiSelfTest {
+    pub solver: String,
+    pub passed: bool,
+    pub error: Option<String>,
+}
+
+// ---------- Worker API types ----------
+
+#[derive(Serialize, Deserialize)]
+pub struct RegisterWorkerRequest {
+    /// Base URL the front-end should dispatch to
+    pub url: String,
+    /// Solver backends compiled into the worker
+    #[serde(default)]
+    pub solvers: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct ApiWorker {
+    pub url: String,
+    pub solvers: Vec<String>,
+    /// `false` for workers configured statically with `WORKER_URLS`
+    pub registered: bool,
+    pub healthy: bool,
+    pub last_seen_seconds: Option<u64>,
+}
+
+// ---------- Benchmark API types ----------
+
+#[derive(Deserialize)]
+pub struct BenchmarkRequest {
+    #[serde(flatten)]
+    pub problem: SolveRequest,
+    /// Per-backend limit, capped by `BENCHMARK_MAX_SECONDS`. Takes the
+    /// field from `problem`, whose solve time limit is unused here
+    pub time_limit_seconds: Option<f64>,
+}
+
+#[derive(Serialize)]
+pub struct ApiBenchmarkSolution {
+    pub status: Status,
+    pub objective: f64,
+}
+
+#[derive(Serialize)]
+pub struct ApiBenchmarkResult {
+    pub solver: String,
+    pub seconds: f64,
+    pub timed_out: bool,
+    /// One per objective; empty when the backend failed or timed out
+    pub solutions: Vec<ApiBenchmarkSolution>,
+    pub error: Option<String>,
+}
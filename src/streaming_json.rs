@@ -0,0 +1,243 @@
+use actix_web::dev::{Decompress, Payload};
+use actix_web::error::{InternalError, JsonPayloadError};
+use actix_web::http::header::CONTENT_LENGTH;
+use actix_web::web::{self, Bytes};
+use actix_web::{Error, FromRequest, HttpMessage, HttpRequest, HttpResponse};
+use bytes::Buf;
+use futures_util::future::LocalBoxFuture;
+use futures_util::StreamExt;
+use serde::de::DeserializeOwned;
+use std::io::{self, BufReader, Read};
+use std::ops::Deref;
+use tokio::sync::mpsc;
+
+/// Payload chunks buffered between the connection and the parser
+const CHANNEL_CAPACITY: usize = 16;
+
+const DEFAULT_LIMIT: usize = 2 * 1024 * 1024; // 2 MB
+
+/// Configuration for the [`StreamingJson`] extractor
+#[derive(Clone)]
+pub struct StreamingJsonConfig {
+    /// Maximum accepted (decompressed) body size in bytes
+    pub limit: usize,
+}
+
+impl Default for StreamingJsonConfig {
+    fn default() -> Self {
+        StreamingJsonConfig {
+            limit: DEFAULT_LIMIT,
+        }
+    }
+}
+
+/// JSON extractor that deserializes the request body while it is received.
+///
+/// Unlike `web::Json`, the body is never buffered as a whole: chunks are
+/// handed to `serde_json` on a blocking thread as they arrive, so peak memory
+/// is the deserialized value plus a few chunks instead of the raw body on top
+/// of it. Errors render the same `400 {"error": ...}` body as `web::Json`.
+pub struct StreamingJson<T>(pub T);
+
+impl<T> StreamingJson<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for StreamingJson<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: DeserializeOwned + Send + 'static> FromRequest for StreamingJson<T> {
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self, Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let limit = req
+            .app_data::<web::Data<StreamingJsonConfig>>()
+            .map(|config| config.limit)
+            .unwrap_or(DEFAULT_LIMIT);
+
+        if let Err(error) = check_headers(req, limit) {
+            return Box::pin(async move { Err(bad_request(error)) });
+        }
+
+        let payload = Decompress::from_headers(payload.take(), req.headers());
+        Box::pin(async move {
+            read_streaming(payload, limit)
+                .await
+                .map(StreamingJson)
+                .map_err(bad_request)
+        })
+    }
+}
+
+fn check_headers(req: &HttpRequest, limit: usize) -> Result<(), JsonPayloadError> {
+    let is_json = match req.mime_type() {
+        Ok(Some(mime)) => {
+            mime.subtype().as_str() == "json"
+                || mime.suffix().map(|suffix| suffix.as_str()) == Some("json")
+        }
+        _ => false,
+    };
+    if !is_json {
+        return Err(JsonPayloadError::ContentType);
+    }
+
+    let length = req
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<usize>().ok());
+    match length {
+        Some(length) if length > limit => {
+            Err(JsonPayloadError::OverflowKnownLength { length, limit })
+        }
+        _ => Ok(()),
+    }
+}
+
+async fn read_streaming<T: DeserializeOwned + Send + 'static>(
+    mut payload: Decompress<Payload>,
+    limit: usize,
+) -> Result<T, JsonPayloadError> {
+    let (tx, rx) = mpsc::channel::<Bytes>(CHANNEL_CAPACITY);
+    let parser = tokio::task::spawn_blocking(move || {
+        serde_json::from_reader::<_, T>(BufReader::new(ChannelReader {
+            rx,
+            chunk: Bytes::new(),
+        }))
+    });
+
+    let mut size = 0;
+    while let Some(chunk) = payload.next().await {
+        let chunk = chunk?;
+        size += chunk.len();
+        if size > limit {
+            // Dropping the sender ends the parser's input early
+            return Err(JsonPayloadError::Overflow { limit });
+        }
+        if tx.send(chunk).await.is_err() {
+            // Parser already gave up (e.g. syntax error); its error is reported below
+            break;
+        }
+    }
+    drop(tx);
+
+    match parser.await {
+        Ok(result) => result.map_err(JsonPayloadError::Deserialize),
+        Err(e) => {
+            sentry::capture_message(
+                &format!("JSON parser thread did not complete successfully: {}", e),
+                sentry::Level::Error,
+            );
+            Err(JsonPayloadError::Payload(
+                actix_web::error::PayloadError::Io(io::Error::other(e)),
+            ))
+        }
+    }
+}
+
+/// Blocking `Read` over payload chunks received from the async side
+struct ChannelReader {
+    rx: mpsc::Receiver<Bytes>,
+    chunk: Bytes,
+}
+
+impl Read for ChannelReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.chunk.is_empty() {
+            match self.rx.blocking_recv() {
+                Some(chunk) => self.chunk = chunk,
+                None => return Ok(0),
+            }
+        }
+
+        let n = buf.len().min(self.chunk.len());
+        buf[..n].copy_from_slice(&self.chunk[..n]);
+        self.chunk.advance(n);
+        Ok(n)
+    }
+}
+
+fn bad_request(error: JsonPayloadError) -> Error {
+    let error_string = error.to_string();
+    InternalError::from_response(
+        error,
+        HttpResponse::BadRequest().json(serde_json::json!({ "error": error_string })),
+    )
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::http::StatusCode;
+    use actix_web::test::TestRequest;
+    use serde::Deserialize;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Payload {
+        values: Vec<i32>,
+    }
+
+    #[actix_web::test]
+    async fn test_parses_json_body() {
+        let (req, mut payload) = TestRequest::post()
+            .insert_header(("content-type", "application/json"))
+            .set_payload(r#"{"values": [1, 2, 3]}"#)
+            .to_http_parts();
+
+        let parsed = StreamingJson::<Payload>::from_request(&req, &mut payload)
+            .await
+            .ok()
+            .unwrap();
+        assert_eq!(parsed.values, vec![1, 2, 3]);
+    }
+
+    #[actix_web::test]
+    async fn test_rejects_invalid_json() {
+        let (req, mut payload) = TestRequest::post()
+            .insert_header(("content-type", "application/json"))
+            .set_payload("invalid json")
+            .to_http_parts();
+
+        let error = StreamingJson::<Payload>::from_request(&req, &mut payload)
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.as_response_error().status_code(),
+            StatusCode::BAD_REQUEST
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_rejects_missing_content_type() {
+        let (req, mut payload) = TestRequest::post()
+            .set_payload(r#"{"values": []}"#)
+            .to_http_parts();
+
+        assert!(StreamingJson::<Payload>::from_request(&req, &mut payload)
+            .await
+            .is_err());
+    }
+
+    #[actix_web::test]
+    async fn test_rejects_body_over_limit() {
+        let (req, mut payload) = TestRequest::post()
+            .insert_header(("content-type", "application/json"))
+            .app_data(web::Data::new(StreamingJsonConfig { limit: 8 }))
+            .set_payload(r#"{"values": [1, 2, 3]}"#)
+            .to_http_parts();
+
+        assert!(StreamingJson::<Payload>::from_request(&req, &mut payload)
+            .await
+            .is_err());
+    }
+}